@@ -0,0 +1,129 @@
+//! Exporting, importing, inspecting, and pruning the LLM verdict cache.
+
+use crate::options::{CacheCommand, CacheOptions};
+use color_eyre::eyre::{self, WrapErr};
+use globetrotter::llm_judge::cache::{self, Cache, Summary};
+use std::time::SystemTime;
+
+impl crate::Globetrotter {
+    /// Runs a `cache` action on the verdict cache that `lint --llm-judge`
+    /// uses, in the `llm-judge` subdirectory of the cache directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache or the exported file cannot be read or
+    /// written, or an imported line is not a cached verdict.
+    pub fn cache(&self, options: &CacheOptions) -> eyre::Result<()> {
+        let dir = self.options.cache_dir().join("llm-judge");
+        let cache = Cache::open(Some(&dir), cache::DEFAULT_CAPACITY)
+            .wrap_err_with(|| eyre::eyre!("failed to open the verdict cache: {dir:?}"))?
+            .ok_or_else(|| eyre::eyre!("the verdict cache is disabled"))?;
+
+        match &options.command {
+            CacheCommand::Export(file) => {
+                let mut output = std::fs::File::create(&file.path)
+                    .map(std::io::BufWriter::new)
+                    .wrap_err_with(|| eyre::eyre!("failed to create: {:?}", file.path))?;
+                let exported = cache
+                    .export(&mut output)
+                    .wrap_err_with(|| eyre::eyre!("failed to export to: {:?}", file.path))?;
+                tracing::info!(exported, path = %file.path.display(), "exported verdict cache");
+            }
+            CacheCommand::Import(file) => {
+                let mut input = std::fs::File::open(&file.path)
+                    .map(std::io::BufReader::new)
+                    .wrap_err_with(|| eyre::eyre!("failed to read: {:?}", file.path))?;
+                let imported = cache
+                    .import(&mut input)
+                    .wrap_err_with(|| eyre::eyre!("failed to import: {:?}", file.path))?;
+                tracing::info!(
+                    added = imported.added,
+                    present = imported.present,
+                    path = %file.path.display(),
+                    "imported verdict cache"
+                );
+            }
+            CacheCommand::Stats => {
+                let summary = cache.summary()?;
+                println!("{}", render(&summary, SystemTime::now()));
+            }
+            CacheCommand::Prune(prune) => {
+                let removed = cache.prune(prune.older_than)?;
+                tracing::info!(removed, "pruned verdict cache");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The summary as lines of text, with the age of the oldest verdict relative
+/// to `now`.
+fn render(summary: &Summary, now: SystemTime) -> String {
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "cache sizes are far below 2^52 bytes"
+    )]
+    let kib = summary.bytes as f64 / 1024.0;
+    let mut lines = vec![
+        format!("verdicts: {}", summary.entries),
+        format!("size: {kib:.1} KiB"),
+    ];
+    let lookups = summary.lookups;
+    lines.push(match lookups.hit_rate() {
+        Some(rate) => format!(
+            "hit rate: {:.1}% ({} hits, {} misses)",
+            rate * 100.0,
+            lookups.hits,
+            lookups.misses
+        ),
+        None => "hit rate: no lookups yet".to_string(),
+    });
+    if let Some(oldest) = summary.oldest {
+        let days = now.duration_since(oldest).unwrap_or_default().as_secs() / 86_400;
+        lines.push(format!("oldest: last used {days} days ago"));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::options::parse_age;
+    use globetrotter::llm_judge::cache::{Lookups, Summary};
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::time::{Duration, SystemTime};
+
+    #[test_util::test]
+    fn renders_the_summary() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_hours(100 * 24);
+        let summary = Summary {
+            entries: 3,
+            bytes: 3 * 1024,
+            oldest: Some(now - Duration::from_hours(12 * 24) - Duration::from_mins(1)),
+            lookups: Lookups { hits: 9, misses: 1 },
+        };
+        sim_assert_eq!(
+            have: render(&summary, now),
+            want: indoc! {"
+                verdicts: 3
+                size: 3.0 KiB
+                hit rate: 90.0% (9 hits, 1 misses)
+                oldest: last used 12 days ago"}
+        );
+        sim_assert_eq!(
+            have: render(&Summary::default(), now),
+            want: "verdicts: 0\nsize: 0.0 KiB\nhit rate: no lookups yet"
+        );
+    }
+
+    #[test_util::test]
+    fn parses_ages() {
+        assert_eq!(parse_age("30d"), Ok(Duration::from_hours(30 * 24)));
+        assert_eq!(parse_age("2w"), Ok(Duration::from_hours(14 * 24)));
+        assert_eq!(parse_age("90m"), Ok(Duration::from_mins(90)));
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("-1h").is_err());
+    }
+}
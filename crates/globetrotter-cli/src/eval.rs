@@ -0,0 +1,54 @@
+//! Measuring the LLM judge against a labelled dataset.
+
+use crate::options::EvalJudgeOptions;
+use color_eyre::eyre::{self, WrapErr};
+use globetrotter::llm_judge::eval::{self, Report};
+
+impl crate::Globetrotter {
+    /// Judges every labelled key of the dataset and prints precision, recall,
+    /// and confidence calibration of the findings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dataset or prompt cannot be read, the dataset
+    /// is invalid, or judging aborts.
+    pub async fn eval_judge(self, options: &EvalJudgeOptions) -> eyre::Result<()> {
+        let params = options.params(&self.options.cache_dir())?;
+        let dataset = tokio::fs::read_to_string(&options.dataset)
+            .await
+            .wrap_err_with(|| eyre::eyre!("failed to read: {:?}", options.dataset))?;
+        let cases = eval::parse(&dataset)
+            .wrap_err_with(|| eyre::eyre!("invalid dataset: {:?}", options.dataset))?;
+        let report = globetrotter::llm_judge::evaluate(&params, &cases).await?;
+        if options.mistakes {
+            for line in mistakes(&report) {
+                println!("{line}");
+            }
+            println!();
+        }
+        println!("{report}");
+        Ok(())
+    }
+}
+
+/// One line per false positive and per missed drift.
+fn mistakes(report: &Report) -> Vec<String> {
+    let false_positives = report
+        .findings
+        .iter()
+        .filter(|finding| !finding.correct)
+        .map(|finding| {
+            format!(
+                "false positive: {} ({}, {:.0}% confident): {}",
+                finding.key,
+                finding.language,
+                finding.confidence * 100.0,
+                finding.problem
+            )
+        });
+    let missed = report
+        .missed
+        .iter()
+        .map(|missed| format!("missed: {} ({})", missed.key, missed.language));
+    false_positives.chain(missed).collect()
+}
@@ -0,0 +1,376 @@
+//! Moving hard-coded, user-facing strings from source code into a translation
+//! file.
+
+use crate::options::ExtractOptions;
+use crate::rename::{self, Edit};
+use color_eyre::eyre::{self, WrapErr};
+use globetrotter::executor;
+use globetrotter::extract::{Candidate, CandidateKind, Extractor, propose_key};
+use globetrotter::model::naming::CaseStyle;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use toml_edit::{Item, Table, value};
+
+/// The key chosen for one candidate string.
+#[derive(Debug, PartialEq, Eq)]
+struct Extraction {
+    candidate: Candidate,
+    /// The full key, including the prefix of the translation file.
+    key: String,
+    /// Whether the key is new, rather than an existing key with the same text.
+    new: bool,
+}
+
+impl crate::Globetrotter {
+    /// Moves the user-facing strings found in source code into a translation
+    /// file.
+    ///
+    /// Each string gets a key derived from its file and its first words,
+    /// under the prefix of the `--into` file, and is appended there in the
+    /// config's source language. A string whose text is already the
+    /// source-language text of a key reuses that key. With `--rewrite`, the
+    /// strings in source code are replaced by calls of the accessor. With
+    /// `--dry-run`, a diff is printed instead of writing any file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `--into` is not an input of a loaded config, the
+    /// config has no languages, or a file cannot be read, parsed, or written.
+    pub async fn extract(self, options: &ExtractOptions) -> eyre::Result<()> {
+        let dry_run = self.options.dry_run.unwrap_or(false);
+        let strict = self.options.strict.unwrap_or(false);
+        let mut configs = self.configs;
+        if let Some(config) = crate::lint::ad_hoc_translation_config(&self.options.translations) {
+            configs.push(config);
+        }
+
+        let into = tokio::fs::canonicalize(&options.into)
+            .await
+            .wrap_err_with(|| eyre::eyre!("failed to open: {:?}", options.into))?;
+        let mut found = None;
+        for config_file in &configs {
+            let mut diagnostics = Vec::new();
+            let inputs = executor::resolve_inputs(config_file, strict, &mut diagnostics)?;
+            for diagnostic in &diagnostics {
+                self.diagnostic_printer.emit(diagnostic).await?;
+            }
+            if inputs.iter().any(|input| input.path == into) {
+                found = Some((config_file, inputs));
+                break;
+            }
+        }
+        let Some((config_file, inputs)) = found else {
+            eyre::bail!("{:?} is not an input of any loaded config", options.into);
+        };
+        let config = &config_file.config;
+        let Some(language) = config.source_language() else {
+            eyre::bail!("config `{}` has no languages", config.name);
+        };
+        let case = config
+            .lint
+            .key_naming
+            .as_ref()
+            .and_then(|naming| naming.case)
+            .unwrap_or(CaseStyle::Snake);
+
+        // The source-language text of every key of the config, to reuse keys
+        // and avoid collisions.
+        let mut files = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            files.push(rename::read_file(input, 0).await?);
+        }
+        let existing = source_texts(&files, language.code());
+        let Some(file) = files.iter_mut().find(|file| file.path == into) else {
+            eyre::bail!("failed to read: {:?}", options.into);
+        };
+
+        let dirs = if options.dirs.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            options.dirs.clone()
+        };
+        let extractor = extractor(options);
+        let excluded = executor::output_dirs(&configs);
+        let candidates =
+            tokio::task::spawn_blocking(move || extractor.scan(&dirs, &excluded)).await?;
+        if candidates.is_empty() {
+            tracing::info!("no user-facing strings found");
+            return Ok(());
+        }
+
+        let scope = match &options.prefix {
+            Some(prefix) => format!("{}{prefix}.", file.key_prefix),
+            None => file.key_prefix.clone(),
+        };
+        let extractions = plan(candidates, &existing, &scope, case);
+
+        let language = language.code();
+        for extraction in &extractions {
+            tracing::info!(
+                key = extraction.key,
+                text = extraction.candidate.text,
+                "{} {}",
+                extraction.candidate,
+                if extraction.new {
+                    "extracted"
+                } else {
+                    "reused"
+                },
+            );
+            if !extraction.new {
+                continue;
+            }
+            let Some(local) = extraction.key.strip_prefix(&file.key_prefix) else {
+                continue;
+            };
+            let segments: Vec<String> = local.split('.').map(str::to_string).collect();
+            let mut table = Table::new();
+            table.insert(language, value(extraction.candidate.text.as_str()));
+            table.set_position(Some(rename::next_position(file.doc.as_table())));
+            // Appended tables are separated from the one before by a blank line.
+            if !file.doc.is_empty() {
+                table.decor_mut().set_prefix("\n");
+            }
+            rename::insert_translation(file.doc.as_table_mut(), &segments, Item::Table(table))?;
+        }
+        rename::save(&file.path, &file.original, &file.doc.to_string(), dry_run).await?;
+
+        if options.rewrite {
+            rewrite_sources(&extractions, options, dry_run).await?;
+        }
+        Ok(())
+    }
+}
+
+/// An extractor for the functions and attributes of `options`, or the
+/// defaults.
+fn extractor(options: &ExtractOptions) -> Extractor {
+    let mut extractor = Extractor::new();
+    if !options.functions.is_empty() {
+        extractor = extractor.with_functions(options.functions.clone());
+    }
+    if !options.attributes.is_empty() {
+        extractor = extractor.with_attributes(options.attributes.clone());
+    }
+    extractor
+}
+
+/// The source-language text of every key defined in `files`, by full key.
+fn source_texts(files: &[rename::TranslationFile], language: &str) -> BTreeMap<String, String> {
+    let mut texts = BTreeMap::new();
+    for file in files {
+        let mut paths = Vec::new();
+        rename::translation_paths(file.doc.as_table(), &mut Vec::new(), &mut paths);
+        for segments in paths {
+            let text = rename::translation_at(file.doc.as_table(), &segments)
+                .and_then(|table| table.get(language))
+                .and_then(Item::as_str)
+                .unwrap_or_default()
+                .to_string();
+            texts.insert(format!("{}{}", file.key_prefix, segments.join(".")), text);
+        }
+    }
+    texts
+}
+
+/// Replaces every extracted string in source code by a call of the accessor
+/// for the file's language, or prints the difference in a dry run.
+async fn rewrite_sources(
+    extractions: &[Extraction],
+    options: &ExtractOptions,
+    dry_run: bool,
+) -> eyre::Result<()> {
+    let mut sources: BTreeMap<PathBuf, Vec<&Extraction>> = BTreeMap::new();
+    for extraction in extractions {
+        sources
+            .entry(extraction.candidate.path.clone())
+            .or_default()
+            .push(extraction);
+    }
+    for (path, extractions) in sources {
+        let original = tokio::fs::read_to_string(&path)
+            .await
+            .wrap_err_with(|| eyre::eyre!("failed to read: {path:?}"))?;
+        let is_rust = path.extension().is_some_and(|extension| extension == "rs");
+        let edits = extractions
+            .into_iter()
+            .map(|extraction| {
+                let new = match (is_rust, &options.rust_accessor) {
+                    #[cfg(feature = "rust")]
+                    (true, None) => rust_variant(&extraction.key),
+                    #[cfg(not(feature = "rust"))]
+                    (true, None) => eyre::bail!(
+                        "rewriting Rust requires `--rust-accessor` in builds without the `rust` \
+                         feature"
+                    ),
+                    (true, Some(accessor)) => replacement(extraction, accessor),
+                    (false, _) => replacement(extraction, &options.accessor),
+                };
+                Ok(Edit {
+                    range: extraction.candidate.range.clone(),
+                    old: original
+                        .get(extraction.candidate.range.clone())
+                        .unwrap_or_default()
+                        .to_string(),
+                    new,
+                })
+            })
+            .collect::<eyre::Result<_>>()?;
+        let updated = rename::rewrite(&original, edits);
+        rename::save(&path, &original, &updated, dry_run).await?;
+    }
+    Ok(())
+}
+
+/// Chooses a key for every candidate, under `scope`.
+///
+/// A candidate whose text is the source-language text of an `existing` key,
+/// or of a key chosen for an earlier candidate, reuses it. Otherwise the
+/// proposed key is numbered until it is unused.
+fn plan(
+    candidates: Vec<Candidate>,
+    existing: &BTreeMap<String, String>,
+    scope: &str,
+    case: CaseStyle,
+) -> Vec<Extraction> {
+    let mut keys = existing.clone();
+    let mut extractions = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if let Some((key, _)) = keys.iter().find(|(_, text)| **text == candidate.text) {
+            extractions.push(Extraction {
+                key: key.clone(),
+                new: !existing.contains_key(key),
+                candidate,
+            });
+            continue;
+        }
+        let proposed = format!(
+            "{scope}{}",
+            propose_key(&candidate.path, &candidate.text, case)
+        );
+        let mut key = proposed.clone();
+        let mut number = 2;
+        while keys.contains_key(&key) {
+            key = numbered(&proposed, number, case);
+            number += 1;
+        }
+        keys.insert(key.clone(), candidate.text.clone());
+        extractions.push(Extraction {
+            key,
+            new: true,
+            candidate,
+        });
+    }
+    // Later candidates reusing a key chosen in this run do not add it again.
+    let mut added = std::collections::BTreeSet::new();
+    for extraction in &mut extractions {
+        if extraction.new && !added.insert(extraction.key.clone()) {
+            extraction.new = false;
+        }
+    }
+    extractions
+}
+
+/// `key` with `number` appended to its last segment, in `case`.
+fn numbered(key: &str, number: usize, case: CaseStyle) -> String {
+    match key.rsplit_once('.') {
+        Some((parent, last)) => format!("{parent}.{}", case.convert(&format!("{last} {number}"))),
+        None => case.convert(&format!("{key} {number}")),
+    }
+}
+
+/// The variant of the generated Rust `Translation` enum naming `key`.
+#[cfg(feature = "rust")]
+fn rust_variant(key: &str) -> String {
+    format!(
+        "Translation::{}",
+        globetrotter::rust::key_to_rust_enum_variant(key)
+    )
+}
+
+/// The accessor call replacing a candidate in source code. JSX text and
+/// attribute values become expressions.
+fn replacement(extraction: &Extraction, accessor: &str) -> String {
+    let call = format!("{accessor}(\"{}\")", extraction.key);
+    match extraction.candidate.kind {
+        CandidateKind::JsxText | CandidateKind::Attribute => format!("{{{call}}}"),
+        CandidateKind::Argument => call,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "rust")]
+    use super::rust_variant;
+    use super::{Extraction, plan, replacement};
+    use globetrotter::extract::{Candidate, CandidateKind};
+    use globetrotter::model::naming::CaseStyle;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn candidate(kind: CandidateKind, text: &str) -> Candidate {
+        Candidate {
+            path: PathBuf::from("src/Cart.tsx"),
+            line: 1,
+            column: 1,
+            range: 0..0,
+            kind,
+            context: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test_util::test]
+    fn plans_new_numbered_and_reused_keys() {
+        let existing = BTreeMap::from([
+            ("app.cart.checkout".to_string(), "Checkout".to_string()),
+            ("app.cart.close".to_string(), "Close cart".to_string()),
+        ]);
+        let candidates = vec![
+            candidate(CandidateKind::JsxText, "Checkout"),
+            candidate(CandidateKind::Attribute, "Close"),
+            candidate(CandidateKind::Argument, "Close!"),
+            candidate(CandidateKind::Attribute, "Close"),
+        ];
+        let planned: Vec<_> = plan(candidates, &existing, "app.", CaseStyle::Snake)
+            .into_iter()
+            .map(|extraction| (extraction.key, extraction.new))
+            .collect();
+        sim_assert_eq!(
+            have: planned,
+            want: vec![
+                ("app.cart.checkout".to_string(), false),
+                ("app.cart.close_2".to_string(), true),
+                ("app.cart.close_3".to_string(), true),
+                ("app.cart.close_2".to_string(), false),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn replaces_jsx_with_expressions() {
+        let extraction = |kind| Extraction {
+            candidate: candidate(kind, "Close"),
+            key: "app.cart.close".to_string(),
+            new: true,
+        };
+        sim_assert_eq!(
+            have: replacement(&extraction(CandidateKind::Attribute), "t"),
+            want: r#"{t("app.cart.close")}"#
+        );
+        sim_assert_eq!(
+            have: replacement(&extraction(CandidateKind::Argument), "t!"),
+            want: r#"t!("app.cart.close")"#
+        );
+    }
+
+    #[cfg(feature = "rust")]
+    #[test_util::test]
+    fn names_generated_rust_variants() {
+        sim_assert_eq!(
+            have: rust_variant("app.cart.close"),
+            want: "Translation::AppCartClose"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! In-place repair of Unicode hygiene issues in translation files.
+
+use color_eyre::eyre::{self, WrapErr};
+use globetrotter::model::{Language, unicode};
+use toml_edit::{Item, Table, Value};
+
+impl crate::Globetrotter {
+    /// NFC-normalizes translations and strips invisible characters and stray
+    /// bidi controls in every translation file, in place.
+    ///
+    /// Only language values change; comments, formatting, and every other key
+    /// are preserved. With `--dry-run`, files that would change are reported
+    /// but not written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a translation file cannot be read, parsed as TOML, or
+    /// written back.
+    pub(crate) async fn fix_unicode(&self) -> eyre::Result<()> {
+        let dry_run = self.options.dry_run.unwrap_or(false);
+        for path in self.translation_files("fix").await? {
+            let original = tokio::fs::read_to_string(&path)
+                .await
+                .wrap_err_with(|| eyre::eyre!("failed to read: {path:?}"))?;
+            let fixed =
+                fix_str(&original).wrap_err_with(|| eyre::eyre!("failed to fix: {path:?}"))?;
+            if fixed == original {
+                continue;
+            }
+            if dry_run {
+                tracing::info!(path = %path.display(), "would fix unicode issues");
+            } else {
+                tokio::fs::write(&path, &fixed)
+                    .await
+                    .wrap_err_with(|| eyre::eyre!("failed to write: {path:?}"))?;
+                tracing::info!(path = %path.display(), "fixed unicode issues");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Applies [`unicode::fix`] to every language value of a TOML document.
+fn fix_str(input: &str) -> eyre::Result<String> {
+    let mut doc: toml_edit::DocumentMut = input.parse()?;
+    fix_table(doc.as_table_mut());
+    Ok(doc.to_string())
+}
+
+fn fix_table(table: &mut Table) {
+    for (key, item) in table.iter_mut() {
+        match item {
+            Item::Table(child) => fix_table(child),
+            Item::Value(value) => fix_value(key.get(), value),
+            Item::ArrayOfTables(_) | Item::None => {}
+        }
+    }
+}
+
+fn fix_value(key: &str, value: &mut Value) {
+    match value {
+        Value::InlineTable(table) => {
+            for (key, value) in table.iter_mut() {
+                fix_value(key.get(), value);
+            }
+        }
+        Value::String(text) => {
+            let Ok(language) = key.parse::<Language>() else {
+                return;
+            };
+            let fixed = unicode::fix(text.value(), language);
+            if fixed != *text.value() {
+                let decor = text.decor().clone();
+                *value = text
+                    .as_repr()
+                    .and_then(|repr| repr.as_raw().as_str())
+                    .and_then(|original| requote(original, &fixed))
+                    .unwrap_or_else(|| Value::from(fixed));
+                *value.decor_mut() = decor;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Writes `text` as a TOML string with the same delimiters as the string
+/// `original`, e.g. a literal `'…'` or multi-line `"""…"""` string.
+///
+/// Returns `None` if `text` cannot be written that way, such as a literal
+/// string containing `'`.
+fn requote(original: &str, text: &str) -> Option<Value> {
+    let delimiter = ["'''", r#"""""#, "'", "\""]
+        .into_iter()
+        .find(|delimiter| original.starts_with(delimiter))?;
+    let multi_line = delimiter.len() == 3;
+    // A line break right after the opening delimiter is not part of the value.
+    let opening = if multi_line
+        && original
+            .get(delimiter.len()..)
+            .is_some_and(|rest| rest.starts_with('\n') || rest.starts_with("\r\n"))
+    {
+        "\n"
+    } else {
+        ""
+    };
+    let body = if delimiter.starts_with('"') {
+        escape(text, multi_line)
+    } else {
+        text.to_string()
+    };
+    let value: Value = format!("{delimiter}{opening}{body}{delimiter}")
+        .parse()
+        .ok()?;
+    (value.as_str() == Some(text)).then_some(value)
+}
+
+/// Escapes `text` for a basic string, keeping quotes, line breaks, and tabs as
+/// they are in a multi-line string.
+fn escape(text: &str, multi_line: bool) -> String {
+    use std::fmt::Write;
+
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\\"),
+            '"' | '\n' | '\t' if multi_line => escaped.push(c),
+            '"' => escaped.push_str(r#"\""#),
+            '\n' => escaped.push_str(r"\n"),
+            '\t' => escaped.push_str(r"\t"),
+            // Writing to a `String` cannot fail.
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04X}", u32::from(c));
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fix_str;
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn fixes_language_values_only() {
+        let input = indoc! {"
+            # greeting
+            [greeting]
+            en = \"Cafe\u{301}\u{200b}\" # trailing
+            he = \"\u{200f}שלום\"
+            arguments = { name = \"\u{200b}\" }
+        "};
+        let want = indoc! {"
+            # greeting
+            [greeting]
+            en = \"Café\" # trailing
+            he = \"\u{200f}שלום\"
+            arguments = { name = \"\u{200b}\" }
+        "};
+        sim_assert_eq!(have: fix_str(input)?, want: want);
+    }
+
+    #[test_util::test]
+    fn keeps_string_delimiters() {
+        let input = indoc! {"
+            [terms]
+            en = \"\"\"
+            Cafe\u{301}\u{200b} \"menu\" \\\\ more
+            second line\"\"\"
+            de = 'C:\\Cafe\u{301}\u{200b}'
+            fr = '''
+            Cafe\u{301}\u{200b}
+            '''
+            it = \"Cafe\u{301}\u{200b}\\t\\\"menu\\\"\"
+        "};
+        let want = indoc! {"
+            [terms]
+            en = \"\"\"
+            Café \"menu\" \\\\ more
+            second line\"\"\"
+            de = 'C:\\Café'
+            fr = '''
+            Café
+            '''
+            it = \"Café\\t\\\"menu\\\"\"
+        "};
+        sim_assert_eq!(have: fix_str(input)?, want: want);
+    }
+}
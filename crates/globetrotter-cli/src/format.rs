@@ -19,33 +19,7 @@ impl crate::Globetrotter {
     /// written back, or — in `--check` mode — if any file is not already
     /// formatted.
     pub async fn format(self, options: &FormatOptions) -> eyre::Result<()> {
-        let strict = self.options.strict.unwrap_or(false);
-
-        // Resolve configured translation files before adding explicit paths.
-        let mut diagnostics = Vec::new();
-        let files =
-            globetrotter::executor::resolve_input_files(&self.configs, strict, &mut diagnostics);
-        for diagnostic in &diagnostics {
-            self.diagnostic_printer.emit(diagnostic).await?;
-        }
-
-        // Canonicalize all sources so aliases and overlapping globs cannot
-        // format the same file twice.
-        let mut paths = Vec::with_capacity(files.len() + self.options.translations.len());
-        for path in files.iter().chain(&self.options.translations) {
-            let path = tokio::fs::canonicalize(path)
-                .await
-                .wrap_err_with(|| eyre::eyre!("failed to open: {path:?}"))?;
-            paths.push(path);
-        }
-        paths.sort();
-        paths.dedup();
-
-        if paths.is_empty() {
-            eyre::bail!(
-                "no translation files found to format; pass --translation <FILE> or --config <FILE>"
-            );
-        }
+        let paths = self.translation_files("format").await?;
 
         // Format or verify every unique translation file.
         let mut unformatted: Vec<PathBuf> = Vec::new();
@@ -81,6 +55,46 @@ impl crate::Globetrotter {
 
         Ok(())
     }
+
+    /// Resolves the translation files of the loaded configurations and of any
+    /// `--translation` paths, canonicalized and deduplicated.
+    ///
+    /// `action` names the command in the error for an empty result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be opened or if no files are found.
+    pub(crate) async fn translation_files(&self, action: &str) -> eyre::Result<Vec<PathBuf>> {
+        let strict = self.options.strict.unwrap_or(false);
+
+        // Resolve configured translation files before adding explicit paths.
+        let mut diagnostics = Vec::new();
+        let files =
+            globetrotter::executor::resolve_input_files(&self.configs, strict, &mut diagnostics);
+        for diagnostic in &diagnostics {
+            self.diagnostic_printer.emit(diagnostic).await?;
+        }
+
+        // Canonicalize all sources so aliases and overlapping globs cannot
+        // process the same file twice.
+        let mut paths = Vec::with_capacity(files.len() + self.options.translations.len());
+        for path in files.iter().chain(&self.options.translations) {
+            let path = tokio::fs::canonicalize(path)
+                .await
+                .wrap_err_with(|| eyre::eyre!("failed to open: {path:?}"))?;
+            paths.push(path);
+        }
+        paths.sort();
+        paths.dedup();
+
+        if paths.is_empty() {
+            eyre::bail!(
+                "no translation files found to {action}; pass --translation <FILE> or --config <FILE>"
+            );
+        }
+
+        Ok(paths)
+    }
 }
 
 /// Sort the keys of a TOML document, preserving comments and formatting.
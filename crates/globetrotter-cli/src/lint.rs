@@ -12,7 +12,7 @@ use std::process::ExitCode;
 ///
 /// Settings such as `--engine` are deliberately not copied into this config:
 /// they reach it through the executor's overrides layer, like any other config.
-fn ad_hoc_translation_config(
+pub(crate) fn ad_hoc_translation_config(
     translations: &[PathBuf],
 ) -> Option<ConfigFile<globetrotter::model::diagnostics::FileId>> {
     if translations.is_empty() {
@@ -38,7 +38,10 @@ impl crate::Globetrotter {
     /// but never used), and exact duplicate strings. With `--usages`, also reports
     /// keys not referenced in the given source directories. With `--llm-judge`,
     /// asks an LLM whether each key's languages all tell the user the same thing
-    /// (a review aid, emitted as notes). No files are written.
+    /// (a review aid, emitted as notes), and `--review` then offers the
+    /// judge's suggested corrections one at a time. No files are written,
+    /// except that `--fix` first repairs Unicode hygiene issues in place and
+    /// `--review` writes the accepted corrections.
     ///
     /// Returns [`ExitCode::FAILURE`] (with a one-line summary) if any issues
     /// were found, otherwise [`ExitCode::SUCCESS`]. Genuine errors (missing or
@@ -50,6 +53,9 @@ impl crate::Globetrotter {
     /// cannot be read or parsed.
     pub async fn lint(self, options: &LintOptions) -> eyre::Result<ExitCode> {
         let start = std::time::Instant::now();
+        if options.fix {
+            self.fix_unicode().await?;
+        }
         let mut configs = self.configs;
 
         // Direct translation paths form one synthetic config so they use the
@@ -91,9 +97,19 @@ impl crate::Globetrotter {
             );
         }
 
+        // The review runs after the lint, against the verdicts it cached.
+        #[cfg(feature = "llm-judge")]
+        let review = match &llm_judge {
+            Some(params) if options.llm_judge.review => {
+                Some((executor.clone(), configs.clone(), params.clone()))
+            }
+            _ => None,
+        };
+
         let params = LintParams {
             detect_duplicates: !options.no_duplicates,
             usages: options.usages.clone(),
+            calls: options.calls.clone(),
             llm_judge,
         };
 
@@ -101,6 +117,14 @@ impl crate::Globetrotter {
         println!();
         let result = executor.lint(configs, &params).await;
         let elapsed = format_duration(start.elapsed());
+
+        #[cfg(feature = "llm-judge")]
+        if let Some((executor, configs, params)) = review
+            && matches!(result, Ok(_) | Err(globetrotter::Error::Failed(_)))
+        {
+            let dry_run = self.options.dry_run.unwrap_or(false);
+            crate::review::review(&executor, &configs, &params, dry_run).await?;
+        }
         match result {
             Ok(_) => {
                 tracing::info!("no issues found in {elapsed}");
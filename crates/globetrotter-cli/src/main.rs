@@ -4,10 +4,26 @@
 //! a shared set of translation files and configuration.
 
 mod base_dir;
+#[cfg(feature = "llm-judge")]
+mod cache;
+#[cfg(feature = "llm-judge")]
+mod eval;
+#[cfg(feature = "tree-sitter")]
+mod extract;
+mod fix;
 mod format;
 mod lint;
 mod options;
+mod rename;
+#[cfg(feature = "llm-judge")]
+mod review;
+mod stale;
 mod telemetry;
+#[cfg(feature = "llm-judge")]
+mod translate;
+#[cfg(feature = "llm-judge")]
+mod triage;
+mod usages;
 
 use clap::Parser;
 use codespan_reporting::diagnostic::{Diagnostic, Severity};
@@ -215,6 +231,42 @@ async fn main() -> eyre::Result<std::process::ExitCode> {
             std::process::ExitCode::SUCCESS
         }
         Some(options::Command::Lint(lint_options)) => globetrotter.lint(&lint_options).await?,
+        Some(options::Command::Usages(usages_options)) => {
+            globetrotter.usages(&usages_options).await?
+        }
+        Some(options::Command::Rename(rename_options)) => {
+            globetrotter.rename(&rename_options).await?;
+            std::process::ExitCode::SUCCESS
+        }
+        Some(options::Command::Stale(stale_options)) => {
+            globetrotter.stale(&stale_options).await?;
+            std::process::ExitCode::SUCCESS
+        }
+        #[cfg(feature = "tree-sitter")]
+        Some(options::Command::Extract(extract_options)) => {
+            globetrotter.extract(&extract_options).await?;
+            std::process::ExitCode::SUCCESS
+        }
+        #[cfg(feature = "llm-judge")]
+        Some(options::Command::Translate(translate_options)) => {
+            globetrotter.translate(&translate_options).await?;
+            std::process::ExitCode::SUCCESS
+        }
+        #[cfg(feature = "llm-judge")]
+        Some(options::Command::Triage(triage_options)) => {
+            globetrotter.triage(&triage_options).await?;
+            std::process::ExitCode::SUCCESS
+        }
+        #[cfg(feature = "llm-judge")]
+        Some(options::Command::EvalJudge(eval_options)) => {
+            globetrotter.eval_judge(&eval_options).await?;
+            std::process::ExitCode::SUCCESS
+        }
+        #[cfg(feature = "llm-judge")]
+        Some(options::Command::Cache(cache_options)) => {
+            globetrotter.cache(&cache_options)?;
+            std::process::ExitCode::SUCCESS
+        }
     };
 
     tracing::debug!(elapsed = ?start.elapsed(), "completed");
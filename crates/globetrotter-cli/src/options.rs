@@ -116,6 +116,38 @@ pub struct LlmJudgeOptions {
     )]
     pub concurrency: usize,
 
+    /// Retries of a rate-limited or failed request.
+    ///
+    /// Retries back off exponentially, or wait as long as the endpoint's
+    /// `Retry-After` header asks.
+    #[clap(
+        long = "llm-max-retries",
+        value_name = "N",
+        default_value_t = 3,
+        requires = "enabled"
+    )]
+    pub max_retries: usize,
+
+    /// Maximum number of requests started per minute.
+    #[clap(long = "llm-rpm", value_name = "N", requires = "enabled")]
+    pub requests_per_minute: Option<u32>,
+
+    /// Maximum number of tokens per minute.
+    ///
+    /// Prompts are estimated before they are sent and corrected by the usage
+    /// the endpoint reports.
+    #[clap(long = "llm-tpm", value_name = "N", requires = "enabled")]
+    pub tokens_per_minute: Option<u32>,
+
+    /// Total number of tokens after which no further request is sent.
+    ///
+    /// The keys left are skipped and counted in the summary; their verdicts
+    /// are not cached, so the next run picks them up. Requests in flight count
+    /// with their estimated prompt, so the total may exceed the budget by the
+    /// responses of requests already sent.
+    #[clap(long = "llm-token-budget", value_name = "N", requires = "enabled")]
+    pub token_budget: Option<u64>,
+
     /// Sampling temperature. The default `0` keeps verdicts reproducible (and
     /// cacheable) across runs.
     #[clap(
@@ -170,6 +202,30 @@ pub struct LlmJudgeOptions {
         requires = "enabled"
     )]
     pub cache_capacity: usize,
+
+    /// Pack several keys into one request, up to this estimated number of
+    /// prompt tokens; `0` sends one request per key.
+    ///
+    /// Batching saves repeating the judging rules for every key, which makes
+    /// large catalogs much faster to judge. Verdicts are still cached per key,
+    /// and a batch whose answer cannot be parsed is judged again one key at a
+    /// time. It is ignored with `--llm-prompt`.
+    #[clap(
+        long = "llm-batch-tokens",
+        value_name = "N",
+        default_value_t = 0,
+        requires = "enabled"
+    )]
+    pub batch_tokens: usize,
+
+    /// After linting, go through the findings that come with a suggested
+    /// correction one at a time and write the accepted ones back.
+    ///
+    /// Each suggestion is shown as a diff of the current and the suggested
+    /// text and applied on `y`, skipped on `n`, and `q` ends the review. With
+    /// `--dry-run`, the changes are printed instead of written.
+    #[clap(long = "review", action = clap::ArgAction::SetTrue, requires = "enabled")]
+    pub review: bool,
 }
 
 /// Parses a `--llm-min-confidence` value, requiring the 0–1 range so an
@@ -184,6 +240,43 @@ fn parse_confidence(value: &str) -> Result<f64, String> {
     }
 }
 
+/// Parses a `--older-than` age: a whole number followed by `s`, `m`, `h`,
+/// `d`, or `w`, such as `30d`.
+#[cfg(feature = "llm-judge")]
+pub(crate) fn parse_age(value: &str) -> Result<std::time::Duration, String> {
+    let invalid = || format!("`{value}` is not an age such as `30d`, `12h`, or `2w`");
+    let unit = value.chars().last().ok_or_else(invalid)?;
+    let seconds: u64 = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3_600,
+        'd' => 86_400,
+        'w' => 604_800,
+        _ => return Err(invalid()),
+    };
+    let count: u64 = value
+        .get(..value.len() - 1)
+        .and_then(|count| count.parse().ok())
+        .ok_or_else(invalid)?;
+    Ok(std::time::Duration::from_secs(
+        count.saturating_mul(seconds),
+    ))
+}
+
+/// Reads the `--llm-prompt` template file, if one is given.
+#[cfg(feature = "llm-judge")]
+fn read_prompt(path: Option<&std::path::Path>) -> std::io::Result<Option<String>> {
+    path.map(|path| {
+        std::fs::read_to_string(path).map_err(|error| {
+            std::io::Error::new(
+                error.kind(),
+                format!("failed to read --llm-prompt {}: {error}", path.display()),
+            )
+        })
+    })
+    .transpose()
+}
+
 #[cfg(feature = "llm-judge")]
 impl LlmJudgeOptions {
     /// Builds executor parameters when `--llm-judge` is set.
@@ -201,15 +294,7 @@ impl LlmJudgeOptions {
         if !self.enabled {
             return Ok(None);
         }
-        let template = match &self.prompt {
-            Some(path) => Some(std::fs::read_to_string(path).map_err(|error| {
-                std::io::Error::new(
-                    error.kind(),
-                    format!("failed to read --llm-prompt {}: {error}", path.display()),
-                )
-            })?),
-            None => None,
-        };
+        let template = read_prompt(self.prompt.as_deref())?;
         Ok(Some(globetrotter::executor::LlmJudgeParams {
             base_url: self.base_url.clone(),
             model: self.model.clone(),
@@ -221,6 +306,11 @@ impl LlmJudgeOptions {
             min_confidence: self.min_confidence,
             cache_dir: Some(cache_dir.join("llm-judge")),
             cache_capacity: self.cache_capacity,
+            batch_tokens: self.batch_tokens,
+            max_retries: self.max_retries,
+            requests_per_minute: self.requests_per_minute,
+            tokens_per_minute: self.tokens_per_minute,
+            token_budget: self.token_budget,
         }))
     }
 }
@@ -228,22 +318,521 @@ impl LlmJudgeOptions {
 /// Options for the `lint` subcommand.
 #[derive(Parser, Debug)]
 pub struct LintOptions {
-    /// Report translation keys never referenced in this source directory.
+    /// Report translation keys never referenced in this source directory,
+    /// and keys used there but not defined.
     ///
-    /// Repeatable. When omitted, the unused-key check is skipped.
+    /// Repeatable. When omitted, both checks are skipped.
     #[clap(long = "usages", value_name = "DIR")]
     pub usages: Vec<PathBuf>,
 
+    /// A translation function whose literal first argument is a key, such as
+    /// `i18n.t` or the Rust macro `t!`.
+    ///
+    /// Repeatable. Replaces the defaults `t`, `$t`, `i18n.t`, and `t!`.
+    #[clap(long = "call", value_name = "NAME")]
+    pub calls: Vec<String>,
+
     /// Disable duplicate-translation detection entirely.
     #[clap(long = "no-duplicates", action = clap::ArgAction::SetTrue)]
     pub no_duplicates: bool,
 
+    /// NFC-normalize translations and remove invisible characters and stray
+    /// bidi controls in place before linting.
+    #[clap(long = "fix", action = clap::ArgAction::SetTrue)]
+    pub fix: bool,
+
     /// LLM-judged consistency review (only with the `llm-judge` feature).
     #[cfg(feature = "llm-judge")]
     #[clap(flatten)]
     pub llm_judge: LlmJudgeOptions,
 }
 
+/// Output format of the `usages` subcommand.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum UsagesFormat {
+    /// One block per key, listing the location of each usage.
+    #[default]
+    Text,
+    /// A JSON array with one object per key.
+    Json,
+}
+
+/// Options for the `usages` subcommand.
+#[derive(Parser, Debug)]
+pub struct UsagesOptions {
+    /// Keys to look up, or globs such as `app.cart.*`.
+    #[clap(value_name = "KEY", required = true)]
+    pub keys: Vec<String>,
+
+    /// Source directory to scan.
+    ///
+    /// Repeatable. Defaults to the current directory.
+    #[clap(long = "dir", value_name = "DIR")]
+    pub dirs: Vec<PathBuf>,
+
+    /// A translation function whose literal first argument is a key.
+    ///
+    /// Repeatable. Replaces the defaults `t`, `$t`, `i18n.t`, and `t!`.
+    #[clap(long = "call", value_name = "NAME")]
+    pub calls: Vec<String>,
+
+    /// Output format.
+    #[clap(long = "format", value_enum, default_value_t = UsagesFormat::Text)]
+    pub format: UsagesFormat,
+}
+
+/// Options for the `rename` subcommand.
+#[derive(Parser, Debug)]
+pub struct RenameOptions {
+    /// The key to rename, or a glob such as `app.cart.*`.
+    #[clap(value_name = "OLD")]
+    pub old: String,
+
+    /// The new key. Each `*` stands for what the corresponding `*` of `OLD`
+    /// matched, as in `shop.cart.*`.
+    #[clap(value_name = "NEW")]
+    pub new: String,
+
+    /// Rewrite usages of the renamed keys in this source directory.
+    ///
+    /// Repeatable. When omitted, only translation files change.
+    #[clap(long = "usages", value_name = "DIR")]
+    pub usages: Vec<PathBuf>,
+
+    /// A translation function whose literal first argument is a key.
+    ///
+    /// Repeatable. Replaces the defaults `t`, `$t`, `i18n.t`, and `t!`.
+    #[clap(long = "call", value_name = "NAME")]
+    pub calls: Vec<String>,
+}
+
+/// Options for the `stale` subcommand.
+#[derive(Parser, Debug)]
+pub struct StaleOptions {
+    /// The stale action.
+    #[command(subcommand)]
+    pub command: StaleCommand,
+}
+
+/// Actions of the `stale` subcommand.
+#[derive(Subcommand, Debug)]
+pub enum StaleCommand {
+    /// Lists translations whose source-language text changed since review.
+    #[command(name = "list")]
+    List(StaleListOptions),
+
+    /// Records translations as reviewed against the current source text.
+    #[command(name = "mark")]
+    Mark(StaleMarkOptions),
+}
+
+/// Options for `stale list`.
+#[derive(Parser, Debug)]
+pub struct StaleListOptions {
+    /// Also list current and untracked translations, with their state.
+    #[clap(long = "all", action = clap::ArgAction::SetTrue)]
+    pub all: bool,
+}
+
+/// Options for `stale mark`.
+///
+/// Requires keys, languages, or `--all`, so that a bare `stale mark` does not
+/// mark every translation as reviewed by accident.
+#[derive(Parser, Debug)]
+#[clap(group(
+    clap::ArgGroup::new("selection")
+        .required(true)
+        .multiple(true)
+        .args(["keys", "languages", "all"])
+))]
+pub struct StaleMarkOptions {
+    /// Full translation keys to mark.
+    #[clap(value_name = "KEY")]
+    pub keys: Vec<String>,
+
+    /// Only mark translations in this language.
+    ///
+    /// Repeatable. When omitted, every language of the given keys is marked.
+    #[clap(long = "language", value_name = "LANG")]
+    pub languages: Vec<model::Language>,
+
+    /// Mark every translation, and remove keys and languages that no longer
+    /// exist from the source lock.
+    #[clap(
+        long = "all",
+        action = clap::ArgAction::SetTrue,
+        conflicts_with_all = ["keys", "languages"]
+    )]
+    pub all: bool,
+}
+
+/// Options for the `extract` subcommand.
+#[cfg(feature = "tree-sitter")]
+#[derive(Parser, Debug)]
+pub struct ExtractOptions {
+    /// Source directories to scan for hard-coded strings.
+    ///
+    /// Defaults to the current directory.
+    #[clap(value_name = "DIR")]
+    pub dirs: Vec<PathBuf>,
+
+    /// The translation file new keys are appended to.
+    ///
+    /// Must be an input of a loaded config, whose source language the strings
+    /// are written in.
+    #[clap(long = "into", value_name = "FILE")]
+    pub into: PathBuf,
+
+    /// A key prefix for the new keys, below the prefix of the translation file.
+    #[clap(long = "prefix", value_name = "KEY")]
+    pub prefix: Option<String>,
+
+    /// A function whose literal first argument is user-facing text.
+    ///
+    /// Repeatable. Replaces the defaults `alert` and `confirm`. Rust macros are
+    /// named with their `!`.
+    #[clap(long = "function", value_name = "NAME")]
+    pub functions: Vec<String>,
+
+    /// A JSX attribute whose string value is user-facing text.
+    ///
+    /// Repeatable. Replaces the defaults `aria-label`, `alt`, `placeholder`,
+    /// and `title`.
+    #[clap(long = "attribute", value_name = "NAME")]
+    pub attributes: Vec<String>,
+
+    /// Replace the extracted strings in source code by translation calls.
+    #[clap(long = "rewrite", action = clap::ArgAction::SetTrue)]
+    pub rewrite: bool,
+
+    /// The translation function called in rewritten TypeScript and
+    /// JavaScript.
+    #[clap(long = "accessor", value_name = "NAME", default_value = "t")]
+    pub accessor: String,
+
+    /// A translation function or macro called in rewritten Rust instead of
+    /// naming the variant of the generated `Translation` enum.
+    #[clap(long = "rust-accessor", value_name = "NAME")]
+    pub rust_accessor: Option<String>,
+}
+
+/// Options for the `translate` subcommand.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct TranslateOptions {
+    /// The languages to translate into, such as `fr,es`.
+    ///
+    /// Defaults to every configured language other than the source language.
+    #[clap(long = "to", value_name = "LANG", value_delimiter = ',')]
+    pub to: Vec<model::Language>,
+
+    /// The language to translate from.
+    ///
+    /// Defaults to the config's source language, which is its
+    /// `source_language` or else its first language.
+    #[clap(long = "from", value_name = "LANG")]
+    pub from: Option<model::Language>,
+
+    /// Only fill in languages a key lacks.
+    ///
+    /// Without it, languages still marked `status = "machine"` are translated
+    /// again too.
+    #[clap(long = "missing", action = clap::ArgAction::SetTrue)]
+    pub missing: bool,
+
+    /// The endpoint to translate with.
+    #[clap(flatten)]
+    pub endpoint: LlmEndpointOptions,
+}
+
+/// The OpenAI-compatible endpoint of the subcommands that always use one.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct LlmEndpointOptions {
+    /// Base URL of the OpenAI-compatible endpoint.
+    #[clap(
+        long = "llm-base-url",
+        value_name = "URL",
+        default_value = "http://localhost:11434/v1"
+    )]
+    pub base_url: String,
+
+    /// Model name as known to the endpoint.
+    #[clap(long = "llm-model", value_name = "MODEL", default_value = "gemma4:12b")]
+    pub model: String,
+
+    /// Name of the environment variable holding the API key.
+    #[clap(
+        long = "llm-api-key-env",
+        value_name = "ENV",
+        default_value = "OPENAI_API_KEY"
+    )]
+    pub api_key_env: String,
+
+    /// Maximum number of concurrent requests.
+    #[clap(long = "llm-concurrency", value_name = "N", default_value_t = 8)]
+    pub concurrency: usize,
+
+    /// Retries of a rate-limited or failed request, backing off
+    /// exponentially or as the `Retry-After` header asks.
+    #[clap(long = "llm-max-retries", value_name = "N", default_value_t = 3)]
+    pub max_retries: usize,
+
+    /// Maximum number of requests started per minute.
+    #[clap(long = "llm-rpm", value_name = "N")]
+    pub requests_per_minute: Option<u32>,
+
+    /// Maximum number of tokens per minute.
+    #[clap(long = "llm-tpm", value_name = "N")]
+    pub tokens_per_minute: Option<u32>,
+
+    /// Total number of tokens after which no further request is sent.
+    ///
+    /// Requests in flight count with their estimated prompt, so the total may
+    /// exceed the budget by the responses of requests already sent.
+    #[clap(long = "llm-token-budget", value_name = "N")]
+    pub token_budget: Option<u64>,
+
+    /// Sampling temperature.
+    #[clap(long = "llm-temperature", value_name = "T", default_value_t = 0.0)]
+    pub temperature: f32,
+
+    /// Reasoning effort, for models that support it.
+    #[clap(long = "llm-effort", value_enum, default_value_t = LlmEffort::Medium)]
+    pub effort: LlmEffort,
+}
+
+#[cfg(feature = "llm-judge")]
+impl LlmEndpointOptions {
+    /// The endpoint settings, as executor parameters without a prompt
+    /// template, confidence threshold, or cache.
+    pub fn params(&self) -> globetrotter::executor::LlmJudgeParams {
+        globetrotter::executor::LlmJudgeParams {
+            base_url: self.base_url.clone(),
+            model: self.model.clone(),
+            api_key_env: self.api_key_env.clone(),
+            concurrency: self.concurrency,
+            temperature: self.temperature,
+            effort: self.effort.into(),
+            template: None,
+            min_confidence: 0.0,
+            cache_dir: None,
+            cache_capacity: 0,
+            batch_tokens: 0,
+            max_retries: self.max_retries,
+            requests_per_minute: self.requests_per_minute,
+            tokens_per_minute: self.tokens_per_minute,
+            token_budget: self.token_budget,
+        }
+    }
+}
+
+/// Options for the `triage` subcommand.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct TriageOptions {
+    /// The triage action.
+    #[command(subcommand)]
+    pub command: TriageCommand,
+}
+
+/// Actions of the `triage` subcommand.
+#[cfg(feature = "llm-judge")]
+#[derive(Subcommand, Debug)]
+pub enum TriageCommand {
+    /// Lists the judge's findings without a recorded decision.
+    #[command(name = "list")]
+    List(TriageListOptions),
+
+    /// Records a finding as a false positive, hidden until the key changes.
+    #[command(name = "dismiss")]
+    Dismiss(TriageDecisionOptions),
+
+    /// Records a finding as a real issue, still reported with the note.
+    #[command(name = "confirm")]
+    Confirm(TriageDecisionOptions),
+}
+
+/// Options for `triage list`.
+///
+/// The judge settings must match those of `lint --llm-judge` for the listed
+/// findings to be answered from its verdict cache.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct TriageListOptions {
+    /// The endpoint to judge with.
+    #[clap(flatten)]
+    pub endpoint: LlmEndpointOptions,
+
+    /// Minimum confidence a finding needs to be listed.
+    #[clap(
+        long = "llm-min-confidence",
+        value_name = "MIN",
+        value_parser = parse_confidence,
+        default_value_t = 0.0
+    )]
+    pub min_confidence: f64,
+
+    /// File with a custom judge prompt template.
+    #[clap(long = "llm-prompt", value_name = "FILE")]
+    pub prompt: Option<PathBuf>,
+
+    /// Maximum number of cached verdicts kept on disk; `0` disables the cache.
+    #[clap(
+        long = "llm-cache-capacity",
+        value_name = "N",
+        default_value_t = 100_000
+    )]
+    pub cache_capacity: usize,
+
+    /// Also list dismissed and confirmed findings, with their decision.
+    #[clap(long = "all", action = clap::ArgAction::SetTrue)]
+    pub all: bool,
+}
+
+#[cfg(feature = "llm-judge")]
+impl TriageListOptions {
+    /// Builds executor parameters for judging.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `--llm-prompt` file cannot be read.
+    pub fn params(
+        &self,
+        cache_dir: &std::path::Path,
+    ) -> std::io::Result<globetrotter::executor::LlmJudgeParams> {
+        Ok(globetrotter::executor::LlmJudgeParams {
+            template: read_prompt(self.prompt.as_deref())?,
+            min_confidence: self.min_confidence,
+            cache_dir: Some(cache_dir.join("llm-judge")),
+            cache_capacity: self.cache_capacity,
+            ..self.endpoint.params()
+        })
+    }
+}
+
+/// Options for `triage dismiss` and `triage confirm`.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct TriageDecisionOptions {
+    /// The full translation key of the finding.
+    #[clap(value_name = "KEY")]
+    pub key: String,
+
+    /// The flagged language.
+    #[clap(value_name = "LANG")]
+    pub language: model::Language,
+
+    /// Why the decision was made.
+    #[clap(long = "note", value_name = "TEXT")]
+    pub note: Option<String>,
+}
+
+/// Options for the `eval-judge` subcommand.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct EvalJudgeOptions {
+    /// JSON Lines file of labelled keys, one
+    /// `{"key": …, "languages": {…}, "drifted": […]}` object per line.
+    #[clap(value_name = "DATASET")]
+    pub dataset: PathBuf,
+
+    /// The endpoint to evaluate.
+    #[clap(flatten)]
+    pub endpoint: LlmEndpointOptions,
+
+    /// File with a custom judge prompt template to evaluate.
+    #[clap(long = "llm-prompt", value_name = "FILE")]
+    pub prompt: Option<PathBuf>,
+
+    /// Pack several keys into one request, up to this estimated number of
+    /// prompt tokens; `0` sends one request per key.
+    #[clap(long = "llm-batch-tokens", value_name = "N", default_value_t = 0)]
+    pub batch_tokens: usize,
+
+    /// Maximum number of cached verdicts kept on disk; `0` disables the cache.
+    #[clap(
+        long = "llm-cache-capacity",
+        value_name = "N",
+        default_value_t = 100_000
+    )]
+    pub cache_capacity: usize,
+
+    /// Also list the false positives and the missed drifts.
+    #[clap(long = "mistakes", action = clap::ArgAction::SetTrue)]
+    pub mistakes: bool,
+}
+
+#[cfg(feature = "llm-judge")]
+impl EvalJudgeOptions {
+    /// Builds executor parameters for judging every finding, whatever its
+    /// confidence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `--llm-prompt` file cannot be read.
+    pub fn params(
+        &self,
+        cache_dir: &std::path::Path,
+    ) -> std::io::Result<globetrotter::executor::LlmJudgeParams> {
+        Ok(globetrotter::executor::LlmJudgeParams {
+            template: read_prompt(self.prompt.as_deref())?,
+            cache_dir: Some(cache_dir.join("llm-judge")),
+            cache_capacity: self.cache_capacity,
+            batch_tokens: self.batch_tokens,
+            ..self.endpoint.params()
+        })
+    }
+}
+
+/// Options for the `cache` subcommand.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct CacheOptions {
+    /// The cache action.
+    #[command(subcommand)]
+    pub command: CacheCommand,
+}
+
+/// Actions of the `cache` subcommand.
+#[cfg(feature = "llm-judge")]
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Writes every cached verdict to one JSON Lines file.
+    #[command(name = "export")]
+    Export(CacheFileOptions),
+
+    /// Adds the verdicts of an exported file, keeping those already cached.
+    #[command(name = "import")]
+    Import(CacheFileOptions),
+
+    /// Prints the number and size of the cached verdicts and the hit rate.
+    #[command(name = "stats")]
+    Stats,
+
+    /// Removes the verdicts not used recently.
+    #[command(name = "prune")]
+    Prune(CachePruneOptions),
+}
+
+/// Options for `cache export` and `cache import`.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct CacheFileOptions {
+    /// The exported file.
+    #[clap(value_name = "FILE")]
+    pub path: PathBuf,
+}
+
+/// Options for `cache prune`.
+#[cfg(feature = "llm-judge")]
+#[derive(Parser, Debug)]
+pub struct CachePruneOptions {
+    /// Remove verdicts not used for this long, such as `30d`, `12h`, or `2w`.
+    #[clap(long = "older-than", value_name = "AGE", value_parser = parse_age)]
+    pub older_than: std::time::Duration,
+}
+
 /// Top-level CLI commands.
 #[derive(Subcommand, Debug)]
 pub enum Command {
@@ -254,6 +843,43 @@ pub enum Command {
     /// Lints translation files and reports any issues.
     #[command(name = "lint")]
     Lint(LintOptions),
+
+    /// Lists where translation keys are used in source code.
+    #[command(name = "usages")]
+    Usages(UsagesOptions),
+
+    /// Renames translation keys and rewrites their usages in source code.
+    #[command(name = "rename")]
+    Rename(RenameOptions),
+
+    /// Lists stale translations and marks them as reviewed.
+    #[command(name = "stale")]
+    Stale(StaleOptions),
+
+    /// Moves hard-coded strings from source code into a translation file.
+    #[cfg(feature = "tree-sitter")]
+    #[command(name = "extract")]
+    Extract(ExtractOptions),
+
+    /// Fills in missing languages by machine translation.
+    #[cfg(feature = "llm-judge")]
+    #[command(name = "translate")]
+    Translate(TranslateOptions),
+
+    /// Lists and records decisions about LLM judge findings.
+    #[cfg(feature = "llm-judge")]
+    #[command(name = "triage")]
+    Triage(TriageOptions),
+
+    /// Measures the LLM judge against a labelled dataset.
+    #[cfg(feature = "llm-judge")]
+    #[command(name = "eval-judge")]
+    EvalJudge(EvalJudgeOptions),
+
+    /// Exports, imports, inspects, and prunes the LLM verdict cache.
+    #[cfg(feature = "llm-judge")]
+    #[command(name = "cache")]
+    Cache(CacheOptions),
 }
 
 /// Top-level CLI options for the `globetrotter` binary.
@@ -0,0 +1,1038 @@
+//! Renaming and moving translation keys across translation files and source
+//! code.
+
+use crate::options::RenameOptions;
+use color_eyre::eyre::{self, WrapErr};
+use globetrotter::config::v1::Configs;
+use globetrotter::executor::{self, UsagesParams};
+use globetrotter::model::diagnostics::{FileId, Span};
+use globetrotter::model::toml::RESERVED_FIELDS;
+use globetrotter::progress::Logger;
+use globetrotter::usages::{KeyUsages, UsageKind};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use toml_edit::{Decor, DocumentMut, InlineTable, Item, Table, TableLike, Value};
+
+/// Maps keys matching a pattern with `*` wildcards to a replacement, in which
+/// each `*` stands for what the corresponding `*` of the pattern matched.
+#[derive(Debug)]
+struct Rename {
+    /// The literal parts of the pattern, between its wildcards.
+    old: Vec<String>,
+    /// The literal parts of the replacement, between its wildcards.
+    new: Vec<String>,
+}
+
+impl Rename {
+    fn new(old: &str, new: &str) -> eyre::Result<Self> {
+        let old: Vec<String> = old.split('*').map(str::to_string).collect();
+        let new: Vec<String> = new.split('*').map(str::to_string).collect();
+        if old.len() != new.len() {
+            eyre::bail!("the old and new key must have the same number of `*` wildcards");
+        }
+        Ok(Self { old, new })
+    }
+
+    /// The new name of `key`, if it matches the pattern.
+    fn apply(&self, key: &str) -> Option<String> {
+        let (first, rest) = self.old.split_first()?;
+        let key = key.strip_prefix(first.as_str())?;
+        let mut captures = Vec::new();
+        if rest.is_empty() {
+            if !key.is_empty() {
+                return None;
+            }
+        } else if !capture(rest, key, &mut captures) {
+            return None;
+        }
+        let mut renamed = String::new();
+        for (index, part) in self.new.iter().enumerate() {
+            if let Some(capture) = index.checked_sub(1).and_then(|index| captures.get(index)) {
+                renamed.push_str(capture);
+            }
+            renamed.push_str(part);
+        }
+        Some(renamed)
+    }
+}
+
+/// Matches `text` against a wildcard followed by `parts`, each part preceded
+/// by a wildcard, pushing what each wildcard matched onto `captures`.
+fn capture<'a>(parts: &[String], text: &'a str, captures: &mut Vec<&'a str>) -> bool {
+    match parts {
+        [] => false,
+        [last] => match text.strip_suffix(last.as_str()) {
+            Some(matched) => {
+                captures.push(matched);
+                true
+            }
+            None => false,
+        },
+        [next, rest @ ..] => {
+            for (index, _) in text.match_indices(next.as_str()) {
+                let (Some(matched), Some(remaining)) =
+                    (text.get(..index), text.get(index + next.len()..))
+                else {
+                    continue;
+                };
+                captures.push(matched);
+                if capture(rest, remaining, captures) {
+                    return true;
+                }
+                captures.pop();
+            }
+            false
+        }
+    }
+}
+
+/// A translation file being edited.
+pub(crate) struct TranslationFile {
+    pub(crate) path: PathBuf,
+    /// The prefix every key of the file is loaded under.
+    pub(crate) key_prefix: String,
+    /// The index of the config the file belongs to.
+    pub(crate) config: usize,
+    pub(crate) original: String,
+    pub(crate) doc: DocumentMut,
+}
+
+/// Renaming one key, possibly into another file.
+#[derive(Debug, PartialEq, Eq)]
+struct Move {
+    key: String,
+    new_key: String,
+    /// The index of the file defining the key.
+    from: usize,
+    /// The table path of the key within its file.
+    segments: Vec<String>,
+    /// The index of the file the renamed key is written to.
+    to: usize,
+    /// The table path of the renamed key within its new file.
+    new_segments: Vec<String>,
+}
+
+/// A replacement of `old` by `new` within `range` of a source file.
+pub(crate) struct Edit {
+    pub(crate) range: Span,
+    pub(crate) old: String,
+    pub(crate) new: String,
+}
+
+impl crate::Globetrotter {
+    /// Renames every key matching `OLD` to `NEW`.
+    ///
+    /// The defining table is edited in place, keeping its comments. When the
+    /// new key starts with the prefix of another input of the same config, the
+    /// table moves to that file. With `--usages`, string literals and
+    /// generated identifiers referring to a renamed key are rewritten too;
+    /// dynamic key prefixes are reported for a manual check. With
+    /// `--dry-run`, a diff is printed instead of writing any file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no key matches, a matching key is defined in more
+    /// than one translation file, a new key already exists or fits no
+    /// translation file, or a file cannot be read, parsed, or written.
+    pub async fn rename(self, options: &RenameOptions) -> eyre::Result<()> {
+        let dry_run = self.options.dry_run.unwrap_or(false);
+        let rename = Rename::new(&options.old, &options.new)?;
+        let mut configs = self.configs;
+        if let Some(config) = crate::lint::ad_hoc_translation_config(&self.options.translations) {
+            configs.push(config);
+        }
+        let strict = self.options.strict.unwrap_or(false);
+
+        let mut files = Vec::new();
+        let mut seen = BTreeSet::new();
+        for (index, config_file) in configs.iter().enumerate() {
+            let mut diagnostics = Vec::new();
+            let inputs = executor::resolve_inputs(config_file, strict, &mut diagnostics)?;
+            for diagnostic in &diagnostics {
+                self.diagnostic_printer.emit(diagnostic).await?;
+            }
+            for input in inputs {
+                if seen.insert(input.path.clone()) {
+                    files.push(read_file(input, index).await?);
+                }
+            }
+        }
+
+        let moves = plan(&files, &rename)?;
+        if moves.is_empty() {
+            eyre::bail!("no translation key matches `{}`", options.old);
+        }
+
+        let mut sources = BTreeMap::new();
+        if !options.usages.is_empty() {
+            let executor = globetrotter::Executor {
+                overrides: self.options.settings_layer(),
+                global_base_dir_for_display: self.global_base_dir_for_display,
+                logger: Logger::new(&configs),
+                diagnostic_printer: self.diagnostic_printer,
+                handlebars: handlebars::Handlebars::default(),
+                max_keys: None,
+            };
+            let params = UsagesParams {
+                keys: moves.iter().map(|m| m.key.clone()).collect(),
+                dirs: options.usages.clone(),
+                calls: options.calls.clone(),
+            };
+            let usages = executor.find_usages(&configs, &params).await?;
+            sources = source_edits(&configs, &files, &moves, &usages);
+        }
+
+        apply(&mut files, &moves)?;
+        for m in &moves {
+            tracing::info!(
+                from = m.key,
+                to = m.new_key,
+                path = %files.get(m.to).map(|file| file.path.display().to_string()).unwrap_or_default(),
+                "renamed",
+            );
+        }
+
+        for file in &files {
+            save(&file.path, &file.original, &file.doc.to_string(), dry_run).await?;
+        }
+        for (path, edits) in sources {
+            let original = tokio::fs::read_to_string(&path)
+                .await
+                .wrap_err_with(|| eyre::eyre!("failed to read: {path:?}"))?;
+            save(&path, &original, &rewrite(&original, edits), dry_run).await?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn read_file(
+    input: executor::InputFile,
+    config: usize,
+) -> eyre::Result<TranslationFile> {
+    let original = tokio::fs::read_to_string(&input.path)
+        .await
+        .wrap_err_with(|| eyre::eyre!("failed to read: {:?}", input.path))?;
+    let doc = original
+        .parse()
+        .wrap_err_with(|| eyre::eyre!("failed to parse: {:?}", input.path))?;
+    Ok(TranslationFile {
+        path: input.path,
+        key_prefix: input.key_prefix,
+        config,
+        original,
+        doc,
+    })
+}
+
+/// Writes `updated` to `path` if it differs from `original`, or prints the
+/// difference in a dry run.
+pub(crate) async fn save(
+    path: &std::path::Path,
+    original: &str,
+    updated: &str,
+    dry_run: bool,
+) -> eyre::Result<()> {
+    if original == updated {
+        return Ok(());
+    }
+    if dry_run {
+        let name = path.display().to_string();
+        print!(
+            "{}",
+            similar::TextDiff::from_lines(original, updated)
+                .unified_diff()
+                .header(&name, &name)
+        );
+    } else {
+        tokio::fs::write(path, updated)
+            .await
+            .wrap_err_with(|| eyre::eyre!("failed to write: {path:?}"))?;
+    }
+    Ok(())
+}
+
+/// Returns `true` if `table` holds the text of a language, which makes it a
+/// translation.
+fn is_translation(table: &dyn TableLike) -> bool {
+    table
+        .iter()
+        .any(|(key, item)| !RESERVED_FIELDS.contains(&key) && item.is_str())
+}
+
+/// Returns `true` if the entry `key` of a translation table belongs to the
+/// translation itself rather than to a nested key.
+fn is_field(key: &str, item: &Item) -> bool {
+    RESERVED_FIELDS.contains(&key)
+        || !(item.is_table_like()
+            || item.is_array_of_tables()
+            || item.as_value().is_some_and(is_nested))
+}
+
+/// Returns `true` if `value` is an inline table or an array of them, which
+/// hold nested keys.
+fn is_nested(value: &Value) -> bool {
+    value.is_inline_table()
+        || value
+            .as_array()
+            .is_some_and(|array| array.iter().any(Value::is_inline_table))
+}
+
+/// The tables of `item`: itself if it is a table or inline table, or the
+/// elements of an array of tables.
+fn tables(item: &Item) -> Vec<&dyn TableLike> {
+    match item {
+        Item::ArrayOfTables(array) => array.iter().map(|table| table as &dyn TableLike).collect(),
+        Item::Value(Value::Array(array)) => array
+            .iter()
+            .filter_map(Value::as_inline_table)
+            .map(|table| table as &dyn TableLike)
+            .collect(),
+        item => item.as_table_like().into_iter().collect(),
+    }
+}
+
+fn tables_mut(item: &mut Item) -> Vec<&mut dyn TableLike> {
+    match item {
+        Item::ArrayOfTables(array) => array
+            .iter_mut()
+            .map(|table| table as &mut dyn TableLike)
+            .collect(),
+        Item::Value(Value::Array(array)) => array
+            .iter_mut()
+            .filter_map(Value::as_inline_table_mut)
+            .map(|table| table as &mut dyn TableLike)
+            .collect(),
+        item => item.as_table_like_mut().into_iter().collect(),
+    }
+}
+
+/// The table paths of the translations in `table`, descending into tables,
+/// inline tables, and arrays of tables like the translation loader. A table
+/// holding the text of a language is a translation.
+pub(crate) fn translation_paths(
+    table: &dyn TableLike,
+    path: &mut Vec<String>,
+    out: &mut Vec<Vec<String>>,
+) {
+    if !path.is_empty() && is_translation(table) {
+        out.push(path.clone());
+    }
+    for (key, item) in table.iter() {
+        if RESERVED_FIELDS.contains(&key) {
+            continue;
+        }
+        for child in tables(item) {
+            path.push(key.to_string());
+            translation_paths(child, path, out);
+            path.pop();
+        }
+    }
+}
+
+/// The translation at `segments` of `table`. Within an array of tables, the
+/// first element holding the translation is used.
+pub(crate) fn translation_at<'a>(
+    table: &'a dyn TableLike,
+    segments: &[String],
+) -> Option<&'a dyn TableLike> {
+    locate(table, segments, false).map(|(table, _)| table)
+}
+
+/// Returns `true` if the translation at `segments` of `table` is written as
+/// an inline table.
+#[cfg(feature = "llm-judge")]
+pub(crate) fn is_inline_translation(table: &dyn TableLike, segments: &[String]) -> bool {
+    locate(table, segments, false).is_some_and(|(_, inline)| inline)
+}
+
+/// The translation at `segments` of `table`, and whether it is an inline
+/// table.
+fn locate<'a>(
+    table: &'a dyn TableLike,
+    segments: &[String],
+    inline: bool,
+) -> Option<(&'a dyn TableLike, bool)> {
+    let Some((key, rest)) = segments.split_first() else {
+        return is_translation(table).then_some((table, inline));
+    };
+    let item = table.get(key)?;
+    tables(item)
+        .into_iter()
+        .find_map(|child| locate(child, rest, item.is_value()))
+}
+
+/// The mutable translation at `segments` of `table`, as found by
+/// [`translation_at`].
+#[cfg(feature = "llm-judge")]
+pub(crate) fn translation_at_mut<'a>(
+    table: &'a mut dyn TableLike,
+    segments: &[String],
+) -> Option<&'a mut dyn TableLike> {
+    let Some((key, rest)) = segments.split_first() else {
+        return if is_translation(table) {
+            Some(table)
+        } else {
+            None
+        };
+    };
+    tables_mut(table.get_mut(key)?)
+        .into_iter()
+        .find(|child| translation_at(&**child, rest).is_some())
+        .and_then(|child| translation_at_mut(child, rest))
+}
+
+/// The file a key named `key` belongs in: among the files of `config` whose
+/// prefix `key` starts with, the one with the longest prefix, preferring
+/// `current`.
+fn target_file(
+    files: &[TranslationFile],
+    config: usize,
+    key: &str,
+    current: usize,
+) -> Option<usize> {
+    let fits = |file: &TranslationFile| {
+        file.config == config
+            && key
+                .strip_prefix(file.key_prefix.as_str())
+                .is_some_and(|local| !local.is_empty())
+    };
+    let longest = files
+        .iter()
+        .filter(|file| fits(file))
+        .map(|file| file.key_prefix.len())
+        .max()?;
+    match files.get(current) {
+        Some(file) if fits(file) && file.key_prefix.len() == longest => Some(current),
+        _ => files
+            .iter()
+            .position(|file| fits(file) && file.key_prefix.len() == longest),
+    }
+}
+
+/// Plans the renames of every key `rename` matches.
+fn plan(files: &[TranslationFile], rename: &Rename) -> eyre::Result<Vec<Move>> {
+    let mut defined: BTreeMap<String, Vec<(usize, Vec<String>)>> = BTreeMap::new();
+    for (index, file) in files.iter().enumerate() {
+        let mut paths = Vec::new();
+        translation_paths(file.doc.as_table(), &mut Vec::new(), &mut paths);
+        for segments in paths {
+            let key = format!("{}{}", file.key_prefix, segments.join("."));
+            defined.entry(key).or_default().push((index, segments));
+        }
+    }
+
+    let mut moves = Vec::new();
+    for (key, definitions) in &defined {
+        let Some(new_key) = rename.apply(key).filter(|new_key| new_key != key) else {
+            continue;
+        };
+        let [(from, segments)] = definitions.as_slice() else {
+            let paths: Vec<String> = definitions
+                .iter()
+                .filter_map(|(index, _)| files.get(*index))
+                .map(|file| file.path.display().to_string())
+                .collect();
+            eyre::bail!(
+                "cannot rename `{key}` to `{new_key}`: the key is defined in {}",
+                paths.join(", ")
+            );
+        };
+        let config = files.get(*from).map_or(0, |file| file.config);
+        let Some(to) = target_file(files, config, &new_key, *from) else {
+            eyre::bail!(
+                "cannot rename `{key}` to `{new_key}`: no translation file accepts the key"
+            );
+        };
+        let local = files
+            .get(to)
+            .and_then(|file| new_key.strip_prefix(file.key_prefix.as_str()))
+            .unwrap_or(&new_key);
+        let new_segments: Vec<String> = local.split('.').map(str::to_string).collect();
+        if new_segments.iter().any(String::is_empty) {
+            eyre::bail!("cannot rename `{key}` to `{new_key}`: the key has an empty segment");
+        }
+        moves.push(Move {
+            key: key.clone(),
+            new_key,
+            from: *from,
+            segments: segments.clone(),
+            to,
+            new_segments,
+        });
+    }
+
+    // A new key may reuse a name that is renamed away, but not a kept one.
+    let renamed: BTreeSet<&str> = moves.iter().map(|m| m.key.as_str()).collect();
+    let mut new_keys = BTreeSet::new();
+    for m in &moves {
+        if !new_keys.insert(m.new_key.as_str())
+            || (defined.contains_key(&m.new_key) && !renamed.contains(m.new_key.as_str()))
+        {
+            eyre::bail!(
+                "cannot rename `{}` to `{}`: the key already exists",
+                m.key,
+                m.new_key
+            );
+        }
+    }
+    Ok(moves)
+}
+
+/// Removes the translation at `segments` from `table`, returning its values,
+/// comments, and position, as a table or, if it was written as one, an inline
+/// table. Nested translations stay in place; parent tables left empty are
+/// removed.
+fn take_translation(table: &mut dyn TableLike, segments: &[String]) -> Option<Item> {
+    let (key, rest) = segments.split_first()?;
+    let item = table.get_mut(key)?;
+    let taken = if rest.is_empty() {
+        take_fields(item)
+    } else {
+        tables_mut(item)
+            .into_iter()
+            .find(|child| translation_at(&**child, rest).is_some())
+            .and_then(|child| take_translation(child, rest))
+    };
+    match item {
+        // The table held the translation, so it only is empty now.
+        Item::Table(child) if child.is_empty() => {
+            table.remove(key);
+        }
+        Item::Value(Value::InlineTable(child)) if child.is_empty() => {
+            table.remove(key);
+        }
+        Item::ArrayOfTables(array) => {
+            array.retain(|table| !table.is_empty());
+            if array.is_empty() {
+                table.remove(key);
+            }
+        }
+        _ => {}
+    }
+    taken
+}
+
+/// Removes the values of the translation in `item`, the first one holding a
+/// translation if `item` is an array of tables.
+fn take_fields(item: &mut Item) -> Option<Item> {
+    match item {
+        Item::Table(child) => Some(Item::Table(take_table_fields(child))),
+        Item::Value(Value::InlineTable(child)) => Some(take_inline_fields(child)),
+        Item::ArrayOfTables(array) => array
+            .iter_mut()
+            .find(|child| is_translation(*child))
+            .map(|child| Item::Table(take_table_fields(child))),
+        Item::Value(Value::Array(array)) => array
+            .iter_mut()
+            .filter_map(Value::as_inline_table_mut)
+            .find(|child| is_translation(*child))
+            .map(take_inline_fields),
+        _ => None,
+    }
+}
+
+fn take_table_fields(child: &mut Table) -> Table {
+    let mut taken = Table::new();
+    let fields: Vec<String> = child
+        .iter()
+        .filter(|(key, item)| is_field(key, item))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for field in fields {
+        if let Some((key, item)) = child.remove_entry(&field) {
+            taken.insert_formatted(&key, item);
+        }
+    }
+    *taken.decor_mut() = child.decor().clone();
+    taken.set_position(child.position());
+    child.set_implicit(true);
+    *child.decor_mut() = Decor::default();
+    taken
+}
+
+fn take_inline_fields(child: &mut InlineTable) -> Item {
+    let mut taken = InlineTable::new();
+    let fields: Vec<String> = child
+        .iter()
+        .filter(|(key, value)| RESERVED_FIELDS.contains(key) || !is_nested(value))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for field in fields {
+        if let Some(value) = child.remove(&field) {
+            taken.insert(&field, value);
+        }
+    }
+    taken.fmt();
+    Item::Value(Value::InlineTable(taken))
+}
+
+/// The translation `moved` as a table, converting an inline table.
+fn into_table(moved: Item) -> eyre::Result<Table> {
+    match moved {
+        Item::Table(table) => Ok(table),
+        Item::Value(Value::InlineTable(table)) => Ok(table.into_table()),
+        _ => eyre::bail!("a translation must be a table"),
+    }
+}
+
+/// Inserts the translation `moved`, a table or an inline table, at `segments`
+/// of `table`, creating parent tables as needed.
+pub(crate) fn insert_translation(
+    table: &mut dyn TableLike,
+    segments: &[String],
+    moved: Item,
+) -> eyre::Result<()> {
+    let Some((key, rest)) = segments.split_first() else {
+        eyre::bail!("cannot insert a translation without a key");
+    };
+    if !rest.is_empty() {
+        let child = table.entry(key).or_insert_with(|| {
+            let mut parent = Table::new();
+            parent.set_implicit(true);
+            Item::Table(parent)
+        });
+        let Some(child) = child.as_table_like_mut() else {
+            eyre::bail!("`{key}` is a value, not a table");
+        };
+        return insert_translation(child, rest, moved);
+    }
+
+    match table.get_mut(key) {
+        None => {
+            let moved = match moved {
+                Item::Table(mut moved) => {
+                    moved.set_implicit(false);
+                    moved.set_dotted(false);
+                    Item::Table(moved)
+                }
+                moved => moved,
+            };
+            table.insert(key, moved);
+        }
+        // A table holding only nested translations gains the values.
+        Some(Item::Table(existing)) if !existing.iter().any(|(key, item)| is_field(key, item)) => {
+            let mut moved = into_table(moved)?;
+            let fields: Vec<String> = moved.iter().map(|(key, _)| key.to_string()).collect();
+            for field in fields {
+                if let Some((key, item)) = moved.remove_entry(&field) {
+                    existing.insert_formatted(&key, item);
+                }
+            }
+            existing.set_implicit(false);
+            existing.set_dotted(false);
+            *existing.decor_mut() = moved.decor().clone();
+            existing.set_position(moved.position());
+        }
+        Some(Item::Value(Value::InlineTable(existing)))
+            if !existing
+                .iter()
+                .any(|(key, value)| RESERVED_FIELDS.contains(&key) || !is_nested(value)) =>
+        {
+            let moved = into_table(moved)?.into_inline_table();
+            for (key, value) in moved {
+                existing.insert(&key, value);
+            }
+            existing.fmt();
+        }
+        Some(_) => eyre::bail!("`{key}` already exists"),
+    }
+    Ok(())
+}
+
+/// The position after every table of `table`, where a table moved into its
+/// document is appended.
+pub(crate) fn next_position(table: &Table) -> isize {
+    table
+        .iter()
+        .filter_map(|(_, item)| item.as_table())
+        .map(|child| next_position(child).max(child.position().map_or(0, |position| position + 1)))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Removes every renamed translation from its file, then inserts it under its
+/// new key, so keys may take over names that are renamed away.
+fn apply(files: &mut [TranslationFile], moves: &[Move]) -> eyre::Result<()> {
+    let mut taken = Vec::with_capacity(moves.len());
+    for m in moves {
+        let table = files
+            .get_mut(m.from)
+            .and_then(|file| take_translation(file.doc.as_table_mut(), &m.segments))
+            .ok_or_else(|| eyre::eyre!("translation `{}` not found", m.key))?;
+        taken.push(table);
+    }
+    for (m, mut moved) in moves.iter().zip(taken) {
+        let Some(file) = files.get_mut(m.to) else {
+            eyre::bail!("translation file of `{}` not found", m.new_key);
+        };
+        if m.to != m.from {
+            // Moved into another file, the translation is appended as a table.
+            let mut table = into_table(moved)?;
+            table.set_position(Some(next_position(file.doc.as_table())));
+            // Appended tables are separated from the one before by a blank line.
+            let prefix = decor_prefix(table.decor()).to_string();
+            if !file.doc.is_empty() && !prefix.starts_with('\n') {
+                table.decor_mut().set_prefix(format!("\n{prefix}"));
+            }
+            moved = Item::Table(table);
+        }
+        if let (Item::Value(_), Some((_, parents))) = (&moved, m.new_segments.split_last()) {
+            append_parent(&mut file.doc, parents)?;
+        }
+        insert_translation(file.doc.as_table_mut(), &m.new_segments, moved)?;
+    }
+    for file in files {
+        trim_leading_blank_lines(&mut file.doc);
+    }
+    Ok(())
+}
+
+/// Appends the table at `segments` of `doc` like a moved table, unless it is
+/// written already, so that an inline table inserted into it has a header.
+fn append_parent(doc: &mut DocumentMut, segments: &[String]) -> eyre::Result<()> {
+    if segments.is_empty() {
+        return Ok(());
+    }
+    let position = next_position(doc.as_table());
+    let prefix = if doc.is_empty() { "" } else { "\n" };
+    let exists = segments
+        .iter()
+        .try_fold(doc.as_table() as &dyn TableLike, |table, segment| {
+            table.get(segment)?.as_table_like()
+        })
+        .is_some();
+    if !exists {
+        let mut parent = Table::new();
+        parent.set_position(Some(position));
+        parent.decor_mut().set_prefix(prefix);
+        return insert_translation(doc.as_table_mut(), segments, Item::Table(parent));
+    }
+    let existing = segments
+        .iter()
+        .try_fold(doc.as_item_mut(), |item, segment| item.get_mut(segment));
+    if let Some(Item::Table(existing)) = existing
+        && existing.is_implicit()
+        && !existing.iter().any(|(key, item)| is_field(key, item))
+    {
+        // The header goes right above the first of its nested tables.
+        let position = first_position(existing).unwrap_or(position);
+        existing.set_implicit(false);
+        existing.set_position(Some(position));
+        existing.decor_mut().set_prefix(prefix);
+    }
+    Ok(())
+}
+
+fn decor_prefix(decor: &Decor) -> &str {
+    decor
+        .prefix()
+        .and_then(|prefix| prefix.as_str())
+        .unwrap_or_default()
+}
+
+/// The smallest position of a table header rendered in `table`.
+fn first_position(table: &Table) -> Option<isize> {
+    table
+        .iter()
+        .filter_map(|(_, item)| item.as_table())
+        .flat_map(|child| {
+            let own = (!child.is_implicit() && !child.is_dotted())
+                .then(|| child.position())
+                .flatten();
+            own.into_iter().chain(first_position(child))
+        })
+        .min()
+}
+
+/// Drops the blank lines before the first table header of `doc`, which remain
+/// when the table before it moved away.
+pub(crate) fn trim_leading_blank_lines(doc: &mut DocumentMut) {
+    if doc.iter().any(|(_, item)| item.is_value()) {
+        return;
+    }
+    let Some(first) = first_position(doc.as_table()) else {
+        return;
+    };
+    if let Some(table) = table_at_position(doc.as_table_mut(), first) {
+        let trimmed = decor_prefix(table.decor())
+            .trim_start_matches(['\n', '\r'])
+            .to_string();
+        table.decor_mut().set_prefix(trimmed);
+    }
+}
+
+fn table_at_position(table: &mut Table, position: isize) -> Option<&mut Table> {
+    for (_, item) in table.iter_mut() {
+        let Some(child) = item.as_table_mut() else {
+            continue;
+        };
+        if !child.is_implicit() && !child.is_dotted() && child.position() == Some(position) {
+            return Some(child);
+        }
+        if let Some(found) = table_at_position(child, position) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// The source edits renaming every literal and generated-identifier usage of
+/// a moved key, by file. Dynamic prefixes cannot be rewritten and are
+/// reported instead.
+fn source_edits(
+    configs: &Configs<FileId>,
+    files: &[TranslationFile],
+    moves: &[Move],
+    usages: &[KeyUsages],
+) -> BTreeMap<PathBuf, Vec<Edit>> {
+    let mut edits: BTreeMap<PathBuf, Vec<Edit>> = BTreeMap::new();
+    let mut prefixes = BTreeSet::new();
+    for key in usages {
+        let Some(m) = moves.iter().find(|m| m.key == key.key) else {
+            continue;
+        };
+        let Some(config) = files.get(m.from).and_then(|file| configs.get(file.config)) else {
+            continue;
+        };
+        let old_forms = executor::key_forms(&config.config, &m.key);
+        let new_forms = executor::key_forms(&config.config, &m.new_key);
+        for usage in &key.usages {
+            if usage.kind == UsageKind::Prefix {
+                if prefixes.insert(usage.to_string()) {
+                    tracing::warn!(%usage, prefix = usage.text, "dynamic key prefix may need a manual update");
+                }
+                continue;
+            }
+            let Some(new) = old_forms
+                .iter()
+                .position(|form| *form == usage.text)
+                .and_then(|index| new_forms.get(index))
+            else {
+                continue;
+            };
+            edits.entry(usage.path.clone()).or_default().push(Edit {
+                range: usage.range.clone(),
+                old: usage.text.clone(),
+                new: new.clone(),
+            });
+        }
+    }
+    edits
+}
+
+/// Applies `edits` to `content`, replacing the first occurrence of each old
+/// text within its range. Of overlapping edits, such as a call and the text
+/// match inside it, only the first applies.
+pub(crate) fn rewrite(content: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|edit| edit.range.start);
+    let mut out = String::with_capacity(content.len());
+    let mut end = 0;
+    for edit in edits {
+        if edit.range.start < end {
+            continue;
+        }
+        let (Some(before), Some(target)) = (
+            content.get(end..edit.range.start),
+            content.get(edit.range.clone()),
+        ) else {
+            continue;
+        };
+        out.push_str(before);
+        out.push_str(&target.replacen(&edit.old, &edit.new, 1));
+        end = edit.range.end;
+    }
+    out.push_str(content.get(end..).unwrap_or_default());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edit, Rename, TranslationFile, apply, plan, rewrite};
+    use color_eyre::eyre;
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::PathBuf;
+
+    fn file(path: &str, key_prefix: &str, content: &str) -> eyre::Result<TranslationFile> {
+        Ok(TranslationFile {
+            path: PathBuf::from(path),
+            key_prefix: key_prefix.to_string(),
+            config: 0,
+            original: content.to_string(),
+            doc: content.parse()?,
+        })
+    }
+
+    #[test_util::test]
+    fn renames_keys_matching_wildcards() {
+        let rename = Rename::new("app.cart.*", "shop.cart.*")?;
+        sim_assert_eq!(have: rename.apply("app.cart.total.label"), want: Some("shop.cart.total.label".to_string()));
+        sim_assert_eq!(have: rename.apply("app.title"), want: None);
+
+        let rename = Rename::new("*.title.*", "*.heading.*")?;
+        sim_assert_eq!(have: rename.apply("app.title.short"), want: Some("app.heading.short".to_string()));
+
+        let rename = Rename::new("app.title", "app.heading")?;
+        sim_assert_eq!(have: rename.apply("app.title.short"), want: None);
+        assert!(Rename::new("app.*", "app").is_err());
+    }
+
+    #[test_util::test]
+    fn moves_tables_between_files_by_prefix() {
+        let mut files = vec![
+            file(
+                "app.toml",
+                "app.",
+                indoc! {r#"
+                    # The cart heading.
+                    [cart.title]
+                    en = "Cart"
+
+                    [cart.total]
+                    en = "Total"
+
+                    [cart.total.hint]
+                    en = "Incl. VAT"
+
+                    [title]
+                    en = "App"
+                "#},
+            )?,
+            file(
+                "shop.toml",
+                "shop.",
+                indoc! {r#"
+                    [name]
+                    en = "Shop"
+                "#},
+            )?,
+        ];
+        let moves = plan(&files, &Rename::new("app.cart.*", "shop.cart.*")?)?;
+        apply(&mut files, &moves)?;
+
+        let have: Vec<String> = files.iter().map(|file| file.doc.to_string()).collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                indoc! {r#"
+                    [title]
+                    en = "App"
+                "#}
+                .to_string(),
+                indoc! {r#"
+                    [name]
+                    en = "Shop"
+
+                    # The cart heading.
+                    [cart.title]
+                    en = "Cart"
+
+                    [cart.total]
+                    en = "Total"
+
+                    [cart.total.hint]
+                    en = "Incl. VAT"
+                "#}
+                .to_string(),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn renames_keys_written_as_inline_tables() {
+        let mut files = vec![file(
+            "app.toml",
+            "",
+            indoc! {r#"
+                [app]
+                save = { en = "Save", de = "Speichern" }
+                cancel = { en = "Cancel", allow = ["typography"] }
+
+                [app.close]
+                en = "Close"
+                arguments = { name = "string" }
+            "#},
+        )?];
+        let moves = plan(&files, &Rename::new("app.save", "app.store")?)?;
+        apply(&mut files, &moves)?;
+        let moves = plan(&files, &Rename::new("app.*", "shop.*")?)?;
+        sim_assert_eq!(
+            have: moves.iter().map(|m| m.key.as_str()).collect::<Vec<_>>(),
+            want: vec!["app.cancel", "app.close", "app.store"]
+        );
+        apply(&mut files, &moves)?;
+
+        sim_assert_eq!(
+            have: files.iter().map(|file| file.doc.to_string()).collect::<Vec<_>>(),
+            want: vec![indoc! {r#"
+                [shop]
+                cancel = { en = "Cancel", allow = ["typography"] }
+                store = { en = "Save", de = "Speichern" }
+
+                [shop.close]
+                en = "Close"
+                arguments = { name = "string" }
+            "#}
+            .to_string()]
+        );
+    }
+
+    #[test_util::test]
+    fn rejects_renaming_onto_an_existing_key() {
+        let files = vec![file(
+            "app.toml",
+            "",
+            indoc! {r#"
+                [a]
+                en = "A"
+
+                [b]
+                en = "B"
+            "#},
+        )?];
+        assert!(plan(&files, &Rename::new("a", "b")?).is_err());
+        // Swapping through a wildcard is fine: `b` is renamed away too.
+        sim_assert_eq!(have: plan(&files, &Rename::new("*", "x.*")?)?.len(), want: 2);
+    }
+
+    #[test_util::test]
+    fn rejects_renaming_keys_defined_in_several_files() {
+        let files = vec![
+            file("a.toml", "", "[title]\nen = \"A\"\n")?,
+            file("b.toml", "", "[title]\nen = \"B\"\n")?,
+        ];
+        let err = plan(&files, &Rename::new("title", "heading")?).err();
+        sim_assert_eq!(
+            have: err.map(|err| err.to_string()),
+            want: Some(
+                "cannot rename `title` to `heading`: the key is defined in a.toml, b.toml".to_string()
+            )
+        );
+    }
+
+    #[test_util::test]
+    fn rewrites_usages_once() {
+        let content = "t('app.title'); Translation::AppTitle";
+        let edits = vec![
+            Edit {
+                range: 2..13,
+                old: "app.title".to_string(),
+                new: "app.heading".to_string(),
+            },
+            Edit {
+                range: 3..12,
+                old: "app.title".to_string(),
+                new: "app.heading".to_string(),
+            },
+            Edit {
+                range: 29..37,
+                old: "AppTitle".to_string(),
+                new: "AppHeading".to_string(),
+            },
+        ];
+        sim_assert_eq!(
+            have: rewrite(content, edits),
+            want: "t('app.heading'); Translation::AppHeading"
+        );
+    }
+}
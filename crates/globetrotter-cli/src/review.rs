@@ -0,0 +1,284 @@
+//! Interactive review of the corrections suggested by the LLM judge.
+
+use crate::rename;
+use color_eyre::eyre::{self, WrapErr};
+use colored::Colorize;
+use globetrotter::Executor;
+use globetrotter::config::v1::Configs;
+use globetrotter::executor::LlmJudgeParams;
+use globetrotter::llm_judge::{self, ReviewFinding};
+use globetrotter::model::diagnostics::FileId;
+use globetrotter::triage::Status;
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use toml_edit::{DocumentMut, Item, Value};
+
+/// What the reviewer answered for one suggestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    Accept,
+    Reject,
+    Quit,
+}
+
+/// Offers every suggested correction of the judge for review and writes the
+/// accepted ones into their translation files, or prints the difference in a
+/// dry run.
+///
+/// # Errors
+///
+/// Returns an error if judging fails, the terminal cannot be read or written,
+/// or a translation file cannot be read, parsed, or written.
+pub(crate) async fn review(
+    executor: &Executor,
+    configs: &Configs<FileId>,
+    params: &LlmJudgeParams,
+    dry_run: bool,
+) -> eyre::Result<()> {
+    let suggestions: Vec<ReviewFinding> = executor
+        .llm_findings(configs, params)
+        .await?
+        .into_iter()
+        .filter(is_reviewable)
+        .collect();
+    if suggestions.is_empty() {
+        tracing::info!("no suggested corrections to review");
+        return Ok(());
+    }
+
+    let accepted = tokio::task::spawn_blocking(move || {
+        ask(
+            suggestions,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stderr(),
+        )
+    })
+    .await??;
+
+    let mut files: BTreeMap<PathBuf, Vec<ReviewFinding>> = BTreeMap::new();
+    for suggestion in accepted {
+        files
+            .entry(suggestion.path.clone())
+            .or_default()
+            .push(suggestion);
+    }
+    let mut applied = 0;
+    for (path, suggestions) in files {
+        let original = tokio::fs::read_to_string(&path)
+            .await
+            .wrap_err_with(|| eyre::eyre!("failed to read: {path:?}"))?;
+        let mut doc: DocumentMut = original
+            .parse()
+            .wrap_err_with(|| eyre::eyre!("failed to parse: {path:?}"))?;
+        for suggestion in &suggestions {
+            if apply(&mut doc, suggestion) {
+                applied += 1;
+            } else {
+                tracing::warn!(
+                    key = suggestion.key,
+                    language = suggestion.language,
+                    "skipping suggestion: the translation changed since it was judged"
+                );
+            }
+        }
+        rename::save(&path, &original, &doc.to_string(), dry_run).await?;
+    }
+    tracing::info!(applied, "review finished");
+    Ok(())
+}
+
+/// Whether a finding suggests a change worth offering: one that was not
+/// dismissed, differs from the current text, and keeps its placeholders.
+///
+/// A suggestion that drops or renames a placeholder is skipped with a warning.
+fn is_reviewable(finding: &ReviewFinding) -> bool {
+    let Some(suggested) = finding.suggestion.as_deref() else {
+        return false;
+    };
+    let dismissed = finding
+        .decision
+        .as_ref()
+        .is_some_and(|entry| entry.status == Status::Dismissed);
+    if dismissed || suggested == finding.current {
+        return false;
+    }
+    if !llm_judge::keeps_variables(&finding.current, suggested) {
+        tracing::warn!(
+            key = finding.key,
+            language = finding.language,
+            "skipping suggestion: it does not keep the placeholders of the translation"
+        );
+        return false;
+    }
+    true
+}
+
+/// Shows each suggestion on `output` and reads the answer from `input`,
+/// returning the accepted suggestions. The end of input ends the review.
+fn ask(
+    suggestions: Vec<ReviewFinding>,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> std::io::Result<Vec<ReviewFinding>> {
+    let total = suggestions.len();
+    let mut accepted = Vec::new();
+    for (index, suggestion) in suggestions.into_iter().enumerate() {
+        writeln!(
+            output,
+            "\n[{}/{total}] {} ({}, {:.0}% confident)",
+            index + 1,
+            suggestion.key.bold(),
+            suggestion.language,
+            suggestion.confidence * 100.0,
+        )?;
+        writeln!(output, "{}", suggestion.problem.dimmed())?;
+        writeln!(output, "{}", format!("- {}", suggestion.current).red())?;
+        let suggested = suggestion.suggestion.as_deref().unwrap_or_default();
+        writeln!(output, "{}", format!("+ {suggested}").green())?;
+        match read_answer(input, output)? {
+            Answer::Accept => accepted.push(suggestion),
+            Answer::Reject => {}
+            Answer::Quit => break,
+        }
+    }
+    Ok(accepted)
+}
+
+/// Prompts until the answer is one of `y`, `n`, or `q`.
+fn read_answer(input: &mut impl BufRead, output: &mut impl Write) -> std::io::Result<Answer> {
+    loop {
+        write!(output, "apply this correction? [y]es, [n]o, [q]uit: ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(Answer::Quit);
+        }
+        match line.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => return Ok(Answer::Accept),
+            "n" | "no" => return Ok(Answer::Reject),
+            "q" | "quit" => return Ok(Answer::Quit),
+            _ => {}
+        }
+    }
+}
+
+/// Replaces the text of the suggestion's language in `doc`, keeping the
+/// value's comments. Returns `false` if there is no suggestion, the key is
+/// not found, or its text is no longer the one that was judged.
+fn apply(doc: &mut DocumentMut, suggestion: &ReviewFinding) -> bool {
+    let Some(suggested) = &suggestion.suggestion else {
+        return false;
+    };
+    let Some(local) = suggestion.key.strip_prefix(&suggestion.key_prefix) else {
+        return false;
+    };
+    let Some(Item::Value(value)) = local
+        .split('.')
+        .try_fold(doc.as_item_mut(), |item, segment| item.get_mut(segment))
+        .and_then(|table| table.get_mut(&suggestion.language))
+    else {
+        return false;
+    };
+    if value.as_str() != Some(suggestion.current.as_str()) {
+        return false;
+    }
+    let decor = value.decor().clone();
+    *value = Value::from(suggested.as_str());
+    *value.decor_mut() = decor;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, ask, is_reviewable};
+    use globetrotter::llm_judge::ReviewFinding;
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::PathBuf;
+    use toml_edit::DocumentMut;
+
+    fn suggestion(key: &str, current: &str, suggested: &str) -> ReviewFinding {
+        ReviewFinding {
+            config: 0,
+            path: PathBuf::from("app.toml"),
+            key_prefix: "app.".to_string(),
+            key: key.to_string(),
+            language: "fr".to_string(),
+            problem: "users of fr are told to discard".to_string(),
+            confidence: 0.9,
+            current: current.to_string(),
+            suggestion: Some(suggested.to_string()),
+            hash: "aaaa".to_string(),
+            decision: None,
+        }
+    }
+
+    #[test_util::test]
+    fn skips_suggestions_that_change_placeholders() {
+        let suggestions = [
+            suggestion("app.a", "Salut {{name}}", "Bonjour {{name}}"),
+            suggestion("app.b", "Salut {{name}}", "Bonjour"),
+            suggestion("app.c", "Salut {{name}}", "Bonjour {{user}}"),
+            suggestion("app.d", "Salut", "Salut"),
+        ];
+        let offered: Vec<&str> = suggestions
+            .iter()
+            .filter(|finding| is_reviewable(finding))
+            .map(|finding| finding.key.as_str())
+            .collect();
+        sim_assert_eq!(have: offered, want: vec!["app.a"]);
+    }
+
+    #[test_util::test]
+    fn asks_until_quit() {
+        let suggestions = vec![
+            suggestion("app.a", "Supprimer", "Enregistrer"),
+            suggestion("app.b", "Fermer", "Ouvrir"),
+            suggestion("app.c", "Oui", "Non"),
+            suggestion("app.d", "Plus", "Moins"),
+            suggestion("app.e", "Haut", "Bas"),
+        ];
+        let mut output = Vec::new();
+        let accepted = ask(
+            suggestions,
+            &mut "y\nmaybe\nn\nyes\nq\n".as_bytes(),
+            &mut output,
+        )?;
+        sim_assert_eq!(
+            have: accepted.iter().map(|suggestion| suggestion.key.as_str()).collect::<Vec<_>>(),
+            want: vec!["app.a", "app.c"]
+        );
+        let output = String::from_utf8(output)?;
+        assert!(output.contains("[2/5]"), "{output}");
+        assert!(output.contains("Enregistrer"), "{output}");
+        assert!(!output.contains("[5/5]"), "{output}");
+    }
+
+    #[test_util::test]
+    fn applies_unchanged_translations_only() {
+        let mut doc: DocumentMut = indoc! {r#"
+            [actions.save]
+            en = "Save"
+            fr = "Supprimer" # reviewed
+        "#}
+        .parse()?;
+        assert!(apply(
+            &mut doc,
+            &suggestion("app.actions.save", "Supprimer", "Enregistrer")
+        ));
+        assert!(!apply(
+            &mut doc,
+            &suggestion("app.actions.save", "Supprimer", "Sauver")
+        ));
+        sim_assert_eq!(
+            have: doc.to_string(),
+            want: indoc! {r#"
+                [actions.save]
+                en = "Save"
+                fr = "Enregistrer" # reviewed
+            "#}
+        );
+    }
+}
@@ -0,0 +1,347 @@
+//! Listing stale translations and marking them as reviewed in the source lock.
+
+use crate::options::{StaleCommand, StaleListOptions, StaleMarkOptions, StaleOptions};
+use crate::rename;
+use color_eyre::eyre::{self, WrapErr};
+use globetrotter::Executor;
+use globetrotter::config::v1::Configs;
+use globetrotter::model::diagnostics::FileId;
+use globetrotter::progress::Logger;
+use globetrotter::stale::{SourceLock, State, Tracked};
+use std::collections::{BTreeMap, BTreeSet};
+use toml_edit::{DocumentMut, Item, Table, TableLike, value};
+
+impl crate::Globetrotter {
+    /// Lists the stale translations, or records translations as reviewed
+    /// against their current source-language text in the source lock of
+    /// their config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a translation file or source lock cannot be read,
+    /// parsed, or written, no config has a source lock, or a key to mark is
+    /// not defined.
+    pub async fn stale(self, options: &StaleOptions) -> eyre::Result<()> {
+        let dry_run = self.options.dry_run.unwrap_or(false);
+        let mut configs = self.configs;
+        if let Some(config) = crate::lint::ad_hoc_translation_config(&self.options.translations) {
+            configs.push(config);
+        }
+
+        let logger = Logger::new(&configs);
+        let executor = globetrotter::Executor {
+            overrides: globetrotter::config::SettingsLayer {
+                dry_run: Some(true),
+                ..self.options.settings_layer()
+            },
+            global_base_dir_for_display: self.global_base_dir_for_display,
+            logger,
+            diagnostic_printer: self.diagnostic_printer,
+            handlebars: handlebars::Handlebars::default(),
+            max_keys: self.options.max_keys,
+        };
+
+        match &options.command {
+            StaleCommand::List(list) => list_stale(&executor, &configs, list).await,
+            StaleCommand::Mark(mark) => mark_reviewed(&executor, &configs, mark, dry_run).await,
+        }
+    }
+}
+
+/// The tracked translations of every config with a source lock.
+async fn tracked(
+    executor: &Executor,
+    configs: &Configs<FileId>,
+) -> eyre::Result<Vec<(std::path::PathBuf, SourceLock, Vec<Tracked>)>> {
+    let mut locks = Vec::new();
+    for config_file in configs {
+        if let Some(tracked) = executor.tracked_translations(config_file).await? {
+            locks.push(tracked);
+        }
+    }
+    if locks.is_empty() {
+        eyre::bail!(
+            "no config has a source lock; set `lint.source_lock` and a source language to track \
+             stale translations"
+        );
+    }
+    Ok(locks)
+}
+
+/// Prints the stale translations, or every translation with `--all`.
+async fn list_stale(
+    executor: &Executor,
+    configs: &Configs<FileId>,
+    options: &StaleListOptions,
+) -> eyre::Result<()> {
+    let mut stale = 0;
+    let mut total = 0;
+    for (_, _, tracked) in tracked(executor, configs).await? {
+        for translation in &tracked {
+            total += 1;
+            if translation.state == State::Stale {
+                stale += 1;
+            }
+            if options.all || translation.state == State::Stale {
+                println!(
+                    "{} ({}): {}",
+                    translation.key, translation.language, translation.state
+                );
+            }
+        }
+    }
+    tracing::info!(stale, total, "listed stale translations");
+    Ok(())
+}
+
+/// Records the selected translations as reviewed in the source lock of their
+/// config, or prints the difference in a dry run.
+async fn mark_reviewed(
+    executor: &Executor,
+    configs: &Configs<FileId>,
+    options: &StaleMarkOptions,
+    dry_run: bool,
+) -> eyre::Result<()> {
+    let locks = tracked(executor, configs).await?;
+    let found: BTreeSet<&str> = locks
+        .iter()
+        .flat_map(|(_, _, tracked)| tracked.iter().map(|translation| translation.key.as_str()))
+        .collect();
+    let missing: Vec<&str> = options
+        .keys
+        .iter()
+        .map(String::as_str)
+        .filter(|key| !found.contains(key))
+        .collect();
+    if !missing.is_empty() {
+        eyre::bail!("translation key `{}` is not defined", missing.join("`, `"));
+    }
+
+    for (path, mut lock, tracked) in locks {
+        let marked = mark(&mut lock, &tracked, options);
+        if marked == 0 && !options.all {
+            continue;
+        }
+        let original = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => {
+                return Err(error).wrap_err_with(|| eyre::eyre!("failed to read: {path:?}"));
+            }
+        };
+        let updated =
+            render(&original, &lock).wrap_err_with(|| eyre::eyre!("failed to update: {path:?}"))?;
+        rename::save(&path, &original, &updated, dry_run).await?;
+        tracing::info!(marked, path = %path.display(), "marked reviewed");
+    }
+    Ok(())
+}
+
+/// Records the current source hash of the translations selected by
+/// `options`, returning how many were not current before.
+///
+/// Marking every translation with `--all` also drops entries of keys and
+/// languages that no longer exist.
+fn mark(lock: &mut SourceLock, tracked: &[Tracked], options: &StaleMarkOptions) -> usize {
+    if options.all {
+        lock.keys.clear();
+    }
+    let selected = tracked.iter().filter(|translation| {
+        (options.keys.is_empty() || options.keys.contains(&translation.key))
+            && (options.languages.is_empty() || options.languages.contains(&translation.language))
+    });
+    let mut marked = 0;
+    for translation in selected {
+        if translation.state != State::Current {
+            marked += 1;
+        }
+        lock.keys
+            .entry(translation.key.clone())
+            .or_default()
+            .insert(
+                translation.language.code().to_string(),
+                translation.source_hash.clone(),
+            );
+    }
+    marked
+}
+
+/// Writes `lock` into `original`, the source lock as read, editing it in
+/// place so that comments and the order of entries are kept. New keys get a
+/// table of their own at the end.
+fn render(original: &str, lock: &SourceLock) -> eyre::Result<String> {
+    let mut doc: DocumentMut = original.parse()?;
+    doc.retain(|key, _| lock.keys.contains_key(key));
+    for (key, languages) in &lock.keys {
+        let Some(table) = doc
+            .entry(key)
+            .or_insert_with(|| Item::Table(Table::new()))
+            .as_table_like_mut()
+        else {
+            eyre::bail!("`{key}` must be a table of language hashes");
+        };
+        update_hashes(table, languages);
+    }
+    rename::trim_leading_blank_lines(&mut doc);
+    Ok(doc.to_string())
+}
+
+/// Sets the hash of every language of `languages` in `table`, keeping the
+/// decor of changed values, and drops the languages no longer recorded.
+fn update_hashes(table: &mut dyn TableLike, languages: &BTreeMap<String, String>) {
+    let dropped: Vec<String> = table
+        .iter()
+        .map(|(language, _)| language.to_string())
+        .filter(|language| !languages.contains_key(language))
+        .collect();
+    for language in dropped {
+        table.remove(&language);
+    }
+    for (language, hash) in languages {
+        match table.get_mut(language).and_then(Item::as_value_mut) {
+            Some(existing) if existing.as_str() == Some(hash) => {}
+            Some(existing) => {
+                let decor = existing.decor().clone();
+                *existing = hash.as_str().into();
+                *existing.decor_mut() = decor;
+            }
+            None => {
+                table.insert(language, value(hash.as_str()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mark, render};
+    use crate::options::StaleMarkOptions;
+    use globetrotter::model::Language;
+    use globetrotter::stale::{SourceLock, State, Tracked};
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    fn tracked(key: &str, language: Language, hash: &str, state: State) -> Tracked {
+        Tracked {
+            key: key.to_string(),
+            language,
+            source_hash: hash.to_string(),
+            state,
+        }
+    }
+
+    #[test_util::test]
+    fn marks_selected_translations_as_reviewed() {
+        let original = indoc! {r#"
+            ["cart.legacy"]
+            de = "aaaa"
+
+            ["cart.title"]
+            de = "bbbb"
+            fr = "aaaa"
+        "#};
+        let mut lock = SourceLock::parse(original).map_err(color_eyre::eyre::Report::msg)?;
+        let tracked = [
+            tracked("cart.empty", Language::De, "cccc", State::Untracked),
+            tracked("cart.title", Language::De, "bbbb", State::Current),
+            tracked("cart.title", Language::Fr, "bbbb", State::Stale),
+        ];
+
+        let options = StaleMarkOptions {
+            keys: vec!["cart.title".to_string()],
+            languages: vec![Language::Fr],
+            all: false,
+        };
+        sim_assert_eq!(have: mark(&mut lock, &tracked, &options), want: 1);
+        let marked = render(original, &lock)?;
+        sim_assert_eq!(
+            have: &marked,
+            want: indoc! {r#"
+                ["cart.legacy"]
+                de = "aaaa"
+
+                ["cart.title"]
+                de = "bbbb"
+                fr = "bbbb"
+            "#}
+        );
+
+        let options = StaleMarkOptions {
+            keys: vec![],
+            languages: vec![],
+            all: true,
+        };
+        sim_assert_eq!(have: mark(&mut lock, &tracked, &options), want: 2);
+        let rendered = render(&marked, &lock)?;
+        sim_assert_eq!(
+            have: &rendered,
+            want: indoc! {r#"
+                ["cart.title"]
+                de = "bbbb"
+                fr = "bbbb"
+
+                ["cart.empty"]
+                de = "cccc"
+            "#}
+        );
+        sim_assert_eq!(have: SourceLock::parse(&rendered), want: Ok(lock));
+    }
+
+    #[test_util::test]
+    fn keeps_comments_and_order_of_the_lock() {
+        let original = indoc! {r#"
+            # Reviewed by the German team.
+            ["cart.title"]
+            fr = "aaaa" # checked twice
+            de = "bbbb"
+
+            ["cart.empty"]
+            de = "cccc"
+        "#};
+        let mut lock = SourceLock::parse(original).map_err(color_eyre::eyre::Report::msg)?;
+        let tracked = [
+            tracked("cart.title", Language::Fr, "dddd", State::Stale),
+            tracked("cart.total", Language::De, "eeee", State::Untracked),
+        ];
+        let options = StaleMarkOptions {
+            keys: vec![],
+            languages: vec![],
+            all: false,
+        };
+        sim_assert_eq!(have: mark(&mut lock, &tracked, &options), want: 2);
+        sim_assert_eq!(
+            have: render(original, &lock)?,
+            want: indoc! {r#"
+                # Reviewed by the German team.
+                ["cart.title"]
+                fr = "dddd" # checked twice
+                de = "bbbb"
+
+                ["cart.empty"]
+                de = "cccc"
+
+                ["cart.total"]
+                de = "eeee"
+            "#}
+        );
+    }
+
+    #[test_util::test]
+    fn requires_a_selection_to_mark() {
+        use clap::Parser;
+
+        for (args, valid) in [
+            (&["mark"][..], false),
+            (&["mark", "--all"], true),
+            (&["mark", "cart.title"], true),
+            (&["mark", "--language", "fr"], true),
+            (&["mark", "cart.title", "--all"], false),
+        ] {
+            sim_assert_eq!(
+                have: StaleMarkOptions::try_parse_from(args).is_ok(),
+                want: valid,
+                "{args:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! Filling in missing languages by machine translation.
+
+use crate::options::TranslateOptions;
+use crate::rename::{self, TranslationFile};
+use color_eyre::eyre;
+use globetrotter::Language;
+use globetrotter::executor;
+use globetrotter::llm_judge::LanguageText;
+use globetrotter::llm_judge::translate::{Translated, TranslationInput};
+use globetrotter::model::status::Status;
+use std::collections::{BTreeSet, HashMap};
+use toml_edit::{InlineTable, Item, TableLike, Value, value};
+
+/// A key with languages to translate.
+#[derive(Debug, PartialEq, Eq)]
+struct Pending {
+    /// The index of the file defining the key.
+    file: usize,
+    /// The table path of the key within its file.
+    segments: Vec<String>,
+    /// The full key.
+    key: String,
+    source_language: &'static str,
+    source: String,
+    targets: Vec<&'static str>,
+}
+
+impl crate::Globetrotter {
+    /// Fills in the languages keys lack by machine translation.
+    ///
+    /// Each key's source-language text is translated into its missing
+    /// languages with template placeholders protected, and every translation
+    /// is checked to use the same template variables as its source. Accepted
+    /// translations are written next to the source text and marked
+    /// `status = { <lang> = "machine" }` for review. Without `--missing`,
+    /// languages still marked `machine` are translated again. With
+    /// `--dry-run`, a diff is printed instead of writing any file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a translation file cannot be read, parsed, or
+    /// written, or the endpoint keeps failing.
+    pub async fn translate(self, options: &TranslateOptions) -> eyre::Result<()> {
+        let dry_run = self.options.dry_run.unwrap_or(false);
+        let strict = self.options.strict.unwrap_or(false);
+        let mut configs = self.configs;
+        if let Some(config) = crate::lint::ad_hoc_translation_config(&self.options.translations) {
+            configs.push(config);
+        }
+
+        let mut files = Vec::new();
+        let mut seen = BTreeSet::new();
+        for (index, config_file) in configs.iter().enumerate() {
+            let mut diagnostics = Vec::new();
+            let inputs = executor::resolve_inputs(config_file, strict, &mut diagnostics)?;
+            for diagnostic in &diagnostics {
+                self.diagnostic_printer.emit(diagnostic).await?;
+            }
+            for input in inputs {
+                if seen.insert(input.path.clone()) {
+                    files.push(rename::read_file(input, index).await?);
+                }
+            }
+        }
+
+        let mut pending = Vec::new();
+        for (index, file) in files.iter().enumerate() {
+            let Some(config) = configs
+                .get(file.config)
+                .map(|config_file| &config_file.config)
+            else {
+                continue;
+            };
+            let Some(source) = options.from.or_else(|| config.source_language()) else {
+                tracing::warn!(
+                    config = %config.name,
+                    "skipping config without languages; pass --from <LANG>"
+                );
+                continue;
+            };
+            let targets: Vec<&'static str> = if options.to.is_empty() {
+                config
+                    .languages
+                    .iter()
+                    .map(|language| language.as_ref().code())
+                    .collect()
+            } else {
+                options.to.iter().map(Language::code).collect()
+            };
+            pending.extend(pending_translations(
+                file,
+                index,
+                source.code(),
+                &targets,
+                options.missing,
+            ));
+        }
+        if pending.is_empty() {
+            tracing::info!("nothing to translate");
+            return Ok(());
+        }
+
+        let inputs: Vec<TranslationInput<'_>> = pending
+            .iter()
+            .map(|pending| TranslationInput {
+                key: &pending.key,
+                source: LanguageText {
+                    language: pending.source_language,
+                    text: &pending.source,
+                },
+                targets: pending.targets.clone(),
+            })
+            .collect();
+        let translator = globetrotter::llm_judge::translator(&options.endpoint.params());
+        let mut translated = Vec::new();
+        let stats = translator
+            .translate(&inputs, &(), &mut |translation| {
+                translated.push(translation);
+            })
+            .await?;
+
+        let (written, rejected) = apply_translations(&mut files, &pending, translated)?;
+        for file in &files {
+            rename::save(&file.path, &file.original, &file.doc.to_string(), dry_run).await?;
+        }
+        tracing::info!(
+            translated = written,
+            rejected = stats.rejected + rejected,
+            failed = stats.failed,
+            skipped = stats.skipped,
+            tokens = stats.tokens,
+            retries = stats.retries,
+            "machine translation finished"
+        );
+        Ok(())
+    }
+}
+
+/// Writes the `translated` texts of `pending` keys into `files`, rejecting
+/// those whose template variables differ from the source. Returns the number
+/// of written and rejected translations.
+fn apply_translations(
+    files: &mut [TranslationFile],
+    pending: &[Pending],
+    translated: Vec<Translated>,
+) -> eyre::Result<(usize, usize)> {
+    let by_key: HashMap<&str, &Pending> = pending
+        .iter()
+        .map(|pending| (pending.key.as_str(), pending))
+        .collect();
+    let mut written = 0;
+    let mut rejected = 0;
+    for translation in translated {
+        let Some(pending) = by_key.get(translation.key.as_str()) else {
+            continue;
+        };
+        if !globetrotter::llm_judge::keeps_variables(&pending.source, &translation.text) {
+            tracing::warn!(
+                key = translation.key,
+                language = translation.language,
+                text = translation.text,
+                "rejected machine translation whose template variables differ from the source"
+            );
+            rejected += 1;
+            continue;
+        }
+        let Some(file) = files.get_mut(pending.file) else {
+            continue;
+        };
+        write_translation(
+            file.doc.as_table_mut(),
+            &pending.segments,
+            &translation.language,
+            &translation.text,
+        )?;
+        written += 1;
+    }
+    Ok((written, rejected))
+}
+
+/// The keys of `file` with a `source` text that lack one of `targets`, or,
+/// unless `missing_only`, still have a machine translation for one.
+fn pending_translations(
+    file: &TranslationFile,
+    index: usize,
+    source: &'static str,
+    targets: &[&'static str],
+    missing_only: bool,
+) -> Vec<Pending> {
+    let mut paths = Vec::new();
+    rename::translation_paths(file.doc.as_table(), &mut Vec::new(), &mut paths);
+    let mut pending = Vec::new();
+    let mut seen = BTreeSet::new();
+    for segments in paths {
+        // Elements of an array of tables share a path.
+        if !seen.insert(segments.clone()) {
+            continue;
+        }
+        let Some(table) = rename::translation_at(file.doc.as_table(), &segments) else {
+            continue;
+        };
+        let Some(text) = table.get(source).and_then(Item::as_str) else {
+            continue;
+        };
+        let targets: Vec<&'static str> = targets
+            .iter()
+            .copied()
+            .filter(|target| *target != source)
+            .filter(|target| {
+                !table.contains_key(target) || (!missing_only && is_machine(table, target))
+            })
+            .collect();
+        if targets.is_empty() {
+            continue;
+        }
+        pending.push(Pending {
+            file: index,
+            key: format!("{}{}", file.key_prefix, segments.join(".")),
+            segments,
+            source_language: source,
+            source: text.to_string(),
+            targets,
+        });
+    }
+    pending
+}
+
+/// Returns `true` if `language` of the translation `table` is marked as a
+/// machine translation.
+fn is_machine(table: &dyn TableLike, language: &str) -> bool {
+    let machine: &'static str = Status::Machine.into();
+    match table.get("status") {
+        Some(item) if item.as_str() == Some(machine) => true,
+        Some(item) => {
+            item.as_inline_table()
+                .and_then(|statuses| statuses.get(language))
+                .and_then(Value::as_str)
+                == Some(machine)
+        }
+        None => false,
+    }
+}
+
+/// Writes `text` for `language` into the translation at `segments` and marks
+/// it as a machine translation. The `status` stays the last entry.
+fn write_translation(
+    table: &mut dyn TableLike,
+    segments: &[String],
+    language: &str,
+    text: &str,
+) -> eyre::Result<()> {
+    let inline = rename::is_inline_translation(&*table, segments);
+    let Some(table) = rename::translation_at_mut(table, segments) else {
+        eyre::bail!("translation `{}` not found", segments.join("."));
+    };
+    let machine: &'static str = Status::Machine.into();
+    let status = table.remove("status");
+    table.insert(language, value(text));
+    let status = match status {
+        // Every language of the key is already marked.
+        Some(item) if item.as_str() == Some(machine) => item,
+        Some(Item::Value(Value::InlineTable(mut statuses))) => {
+            statuses.insert(language, machine.into());
+            Item::Value(Value::InlineTable(statuses))
+        }
+        _ => {
+            let mut statuses = InlineTable::new();
+            statuses.insert(language, machine.into());
+            value(statuses)
+        }
+    };
+    table.insert("status", status);
+    if inline {
+        // Spacing is kept by standard tables but uniform within inline ones.
+        table.fmt();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pending_translations, write_translation};
+    use crate::rename::TranslationFile;
+    use color_eyre::eyre;
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::PathBuf;
+
+    fn file(content: &str) -> eyre::Result<TranslationFile> {
+        Ok(TranslationFile {
+            path: PathBuf::from("app.toml"),
+            key_prefix: "app.".to_string(),
+            config: 0,
+            original: content.to_string(),
+            doc: content.parse()?,
+        })
+    }
+
+    #[test_util::test]
+    fn selects_missing_and_machine_languages() {
+        let file = file(indoc! {r#"
+            [cart.title]
+            en = "Your cart"
+            fr = "Votre panier"
+            es = "Tu carrito"
+            status = { es = "machine" }
+
+            [cart.empty]
+            en = "Your cart is empty"
+        "#})?;
+        let pending = |missing_only| {
+            pending_translations(&file, 0, "en", &["en", "fr", "es"], missing_only)
+                .into_iter()
+                .map(|pending| (pending.key, pending.targets))
+                .collect::<Vec<_>>()
+        };
+        sim_assert_eq!(
+            have: pending(true),
+            want: vec![("app.cart.empty".to_string(), vec!["fr", "es"])]
+        );
+        sim_assert_eq!(
+            have: pending(false),
+            want: vec![
+                ("app.cart.title".to_string(), vec!["es"]),
+                ("app.cart.empty".to_string(), vec!["fr", "es"]),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn writes_translations_marked_as_machine() {
+        let mut file = file(indoc! {r#"
+            # Shown above the cart.
+            [cart.title]
+            en = "Your cart"
+            status = { es = "machine" }
+        "#})?;
+        let segments = ["cart".to_string(), "title".to_string()];
+        write_translation(file.doc.as_table_mut(), &segments, "es", "Su carrito")?;
+        write_translation(file.doc.as_table_mut(), &segments, "fr", "Votre panier")?;
+        sim_assert_eq!(
+            have: file.doc.to_string(),
+            want: indoc! {r#"
+                # Shown above the cart.
+                [cart.title]
+                en = "Your cart"
+                es = "Su carrito"
+                fr = "Votre panier"
+                status = { es = "machine", fr = "machine" }
+            "#}
+        );
+
+        let mut inline = self::file(indoc! {r#"
+            [cart]
+            empty = { en = "Empty" }
+        "#})?;
+        let segments = ["cart".to_string(), "empty".to_string()];
+        write_translation(inline.doc.as_table_mut(), &segments, "fr", "Vide")?;
+        sim_assert_eq!(
+            have: inline.doc.to_string(),
+            want: indoc! {r#"
+                [cart]
+                empty = { en = "Empty", fr = "Vide", status = { fr = "machine" } }
+            "#}
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Listing and recording decisions about LLM judge findings.
+
+use crate::options::{TriageCommand, TriageDecisionOptions, TriageListOptions, TriageOptions};
+use crate::rename;
+use color_eyre::eyre::{self, WrapErr};
+use globetrotter::Executor;
+use globetrotter::config::v1::Configs;
+use globetrotter::executor::LlmJudgeParams;
+use globetrotter::llm_judge::ReviewFinding;
+use globetrotter::model::diagnostics::FileId;
+use globetrotter::progress::Logger;
+use globetrotter::triage::Status;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, value};
+
+impl crate::Globetrotter {
+    /// Lists the judge's open findings, or records a decision about one in
+    /// the triage file of the config defining its key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a translation or triage file cannot be read,
+    /// parsed, or written, the key is not defined, its config has no triage
+    /// file, or judging fails.
+    pub async fn triage(self, options: &TriageOptions) -> eyre::Result<()> {
+        let dry_run = self.options.dry_run.unwrap_or(false);
+        let cache_dir = self.options.cache_dir();
+        let mut configs = self.configs;
+        if let Some(config) = crate::lint::ad_hoc_translation_config(&self.options.translations) {
+            configs.push(config);
+        }
+
+        let logger = Logger::new(&configs);
+        let executor = globetrotter::Executor {
+            overrides: globetrotter::config::SettingsLayer {
+                dry_run: Some(true),
+                ..self.options.settings_layer()
+            },
+            global_base_dir_for_display: self.global_base_dir_for_display,
+            logger,
+            diagnostic_printer: self.diagnostic_printer,
+            handlebars: handlebars::Handlebars::default(),
+            max_keys: self.options.max_keys,
+        };
+
+        match &options.command {
+            TriageCommand::List(list) => {
+                list_findings(&executor, &configs, &list.params(&cache_dir)?, list).await
+            }
+            TriageCommand::Dismiss(decision) => {
+                record_decision(&executor, &configs, decision, Status::Dismissed, dry_run).await
+            }
+            TriageCommand::Confirm(decision) => {
+                record_decision(&executor, &configs, decision, Status::Confirmed, dry_run).await
+            }
+        }
+    }
+}
+
+/// Prints the findings without a decision, or every finding with `--all`.
+async fn list_findings(
+    executor: &Executor,
+    configs: &Configs<FileId>,
+    params: &LlmJudgeParams,
+    options: &TriageListOptions,
+) -> eyre::Result<()> {
+    let findings = executor.llm_findings(configs, params).await?;
+    let open = findings
+        .iter()
+        .filter(|finding| finding.decision.is_none())
+        .count();
+    for finding in &findings {
+        if options.all || finding.decision.is_none() {
+            println!("{}", render(finding));
+        }
+    }
+    tracing::info!(open, total = findings.len(), "listed llm judge findings");
+    Ok(())
+}
+
+/// Records `status` for a finding in the triage file of the config defining
+/// its key, or prints the difference in a dry run.
+async fn record_decision(
+    executor: &Executor,
+    configs: &Configs<FileId>,
+    decision: &TriageDecisionOptions,
+    status: Status,
+    dry_run: bool,
+) -> eyre::Result<()> {
+    let Some((config_file, hash)) = executor
+        .llm_content_hash(configs, &decision.key)
+        .await?
+        .and_then(|(index, hash)| Some((configs.get(index)?, hash)))
+    else {
+        eyre::bail!("translation key `{}` is not defined", decision.key);
+    };
+    let Some(path) = globetrotter::llm_judge::triage_path(config_file) else {
+        eyre::bail!(
+            "config `{}` has no triage file; set `lint.llm_triage` to record decisions",
+            config_file.config.name
+        );
+    };
+    let original = match tokio::fs::read_to_string(&path).await {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => {
+            return Err(error).wrap_err_with(|| eyre::eyre!("failed to read: {path:?}"));
+        }
+    };
+    let mut doc: DocumentMut = original
+        .parse()
+        .wrap_err_with(|| eyre::eyre!("failed to parse: {path:?}"))?;
+    record(&mut doc, decision, &hash, status)?;
+    rename::save(&path, &original, &doc.to_string(), dry_run).await?;
+    tracing::info!(
+        key = decision.key,
+        language = decision.language.code(),
+        %status,
+        path = %path.display(),
+        "recorded decision"
+    );
+    Ok(())
+}
+
+/// One finding as a line, followed by its suggestion and decision.
+fn render(finding: &ReviewFinding) -> String {
+    let mut lines = vec![format!(
+        "{}: {} ({}, {:.0}% confident): {}",
+        finding.path.display(),
+        finding.key,
+        finding.language,
+        finding.confidence * 100.0,
+        finding.problem
+    )];
+    if let Some(suggestion) = &finding.suggestion {
+        lines.push(format!("  suggested: {suggestion:?}"));
+    }
+    if let Some(entry) = &finding.decision {
+        lines.push(match &entry.note {
+            Some(note) => format!("  {}: {note}", entry.status),
+            None => format!("  {}", entry.status),
+        });
+    }
+    lines.join("\n")
+}
+
+/// Records `status` for the finding of `decision` in the triage document,
+/// replacing an earlier decision about the same key and language.
+fn record(
+    doc: &mut DocumentMut,
+    decision: &TriageDecisionOptions,
+    hash: &str,
+    status: Status,
+) -> eyre::Result<()> {
+    let findings = doc
+        .entry("finding")
+        .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .ok_or_else(|| eyre::eyre!("`finding` must be an array of tables"))?;
+    let language = decision.language.code();
+    let existing = findings.iter_mut().find(|table| {
+        table.get("key").and_then(Item::as_str) == Some(decision.key.as_str())
+            && table.get("language").and_then(Item::as_str) == Some(language)
+    });
+    let table = if let Some(table) = existing {
+        table
+    } else {
+        let mut table = Table::new();
+        table.insert("key", value(decision.key.as_str()));
+        table.insert("language", value(language));
+        findings.push(table);
+        findings
+            .iter_mut()
+            .last()
+            .ok_or_else(|| eyre::eyre!("failed to add the finding"))?
+    };
+    table.insert("hash", value(hash));
+    table.insert("status", value(<&'static str>::from(status)));
+    match &decision.note {
+        Some(note) => {
+            table.insert("note", value(note.as_str()));
+        }
+        None => {
+            table.remove("note");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::record;
+    use crate::options::TriageDecisionOptions;
+    use globetrotter::model::Language;
+    use globetrotter::triage::{Status, Triage};
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use toml_edit::DocumentMut;
+
+    #[test_util::test]
+    fn records_and_replaces_decisions() {
+        let mut doc: DocumentMut = indoc! {r#"
+            # Reviewed judge findings.
+            [[finding]]
+            key = "cart.title"
+            language = "fr"
+            hash = "aaaa"
+            status = "confirmed"
+        "#}
+        .parse()?;
+        let decision = |key: &str, note: Option<&str>| TriageDecisionOptions {
+            key: key.to_string(),
+            language: Language::Fr,
+            note: note.map(str::to_string),
+        };
+        record(
+            &mut doc,
+            &decision("cart.title", Some("Panier is the usual term")),
+            "bbbb",
+            Status::Dismissed,
+        )?;
+        record(
+            &mut doc,
+            &decision("cart.empty", None),
+            "cccc",
+            Status::Confirmed,
+        )?;
+        sim_assert_eq!(
+            have: doc.to_string(),
+            want: indoc! {r#"
+                # Reviewed judge findings.
+                [[finding]]
+                key = "cart.title"
+                language = "fr"
+                hash = "bbbb"
+                status = "dismissed"
+                note = "Panier is the usual term"
+
+                [[finding]]
+                key = "cart.empty"
+                language = "fr"
+                hash = "cccc"
+                status = "confirmed"
+            "#}
+        );
+        let triage = Triage::parse(&doc.to_string()).map_err(color_eyre::eyre::Report::msg)?;
+        assert_eq!(triage.entries.len(), 2);
+    }
+}
@@ -0,0 +1,141 @@
+//! Reverse lookup from translation keys to their usages in source code.
+
+use crate::options::{UsagesFormat, UsagesOptions};
+use color_eyre::eyre;
+use globetrotter::executor::UsagesParams;
+use globetrotter::progress::Logger;
+use globetrotter::usages::{KeyUsages, UsageKind};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+impl crate::Globetrotter {
+    /// Lists every file and line referencing the requested keys.
+    ///
+    /// Keys may be globs such as `app.cart.*`, which select defined keys only;
+    /// a key named without wildcards is looked up even if it is not defined. A
+    /// usage is a string literal holding the key, a generated identifier of
+    /// the key, or a dynamic prefix the key starts with.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a translation file cannot be read, a pattern is
+    /// invalid, or no key matches any pattern.
+    pub async fn usages(self, options: &UsagesOptions) -> eyre::Result<ExitCode> {
+        let mut configs = self.configs;
+        if let Some(config) = crate::lint::ad_hoc_translation_config(&self.options.translations) {
+            configs.push(config);
+        }
+
+        let logger = Logger::new(&configs);
+        let executor = globetrotter::Executor {
+            overrides: globetrotter::config::SettingsLayer {
+                dry_run: Some(true),
+                ..self.options.settings_layer()
+            },
+            global_base_dir_for_display: self.global_base_dir_for_display,
+            logger,
+            diagnostic_printer: self.diagnostic_printer,
+            handlebars: handlebars::Handlebars::default(),
+            max_keys: self.options.max_keys,
+        };
+
+        let dirs = if options.dirs.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            options.dirs.clone()
+        };
+        let params = UsagesParams {
+            keys: options.keys.clone(),
+            dirs,
+            calls: options.calls.clone(),
+        };
+        let keys = executor.find_usages(&configs, &params).await?;
+        if keys.is_empty() {
+            eyre::bail!("no translation key matches {}", options.keys.join(", "));
+        }
+
+        match options.format {
+            UsagesFormat::Text => {
+                for line in render_text(&keys) {
+                    println!("{line}");
+                }
+            }
+            UsagesFormat::Json => println!("{}", serde_json::to_string_pretty(&keys)?),
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// One block of lines per key: the key, then the location and kind of each
+/// usage.
+fn render_text(keys: &[KeyUsages]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for key in keys {
+        if key.defined {
+            lines.push(key.key.clone());
+        } else {
+            lines.push(format!("{} (not defined)", key.key));
+        }
+        if key.usages.is_empty() {
+            lines.push("  no usages".to_string());
+        }
+        lines.extend(key.usages.iter().map(|usage| match usage.kind {
+            // The matched text says which identifier or prefix was found.
+            UsageKind::Identifier | UsageKind::Prefix => {
+                format!("  {usage} ({} `{}`)", usage.kind, usage.text)
+            }
+            UsageKind::Literal | UsageKind::Text | UsageKind::Call => {
+                format!("  {usage} ({})", usage.kind)
+            }
+        }));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_text;
+    use globetrotter::usages::{KeyUsages, Usage, UsageKind};
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::PathBuf;
+
+    fn usage(line: usize, kind: UsageKind, text: &str) -> Usage {
+        Usage {
+            path: PathBuf::from("src/Cart.tsx"),
+            line,
+            column: 5,
+            range: 0..0,
+            kind,
+            text: text.to_string(),
+        }
+    }
+
+    #[test_util::test]
+    fn renders_usages_per_key() {
+        let keys = [
+            KeyUsages {
+                key: "app.cart.legacy".to_string(),
+                defined: false,
+                usages: vec![],
+            },
+            KeyUsages {
+                key: "app.cart.title".to_string(),
+                defined: true,
+                usages: vec![
+                    usage(3, UsageKind::Call, "app.cart.title"),
+                    usage(9, UsageKind::Prefix, "app.cart."),
+                ],
+            },
+        ];
+        sim_assert_eq!(
+            have: render_text(&keys),
+            want: vec![
+                "app.cart.legacy (not defined)",
+                "  no usages",
+                "app.cart.title",
+                "  src/Cart.tsx:3:5 (call)",
+                "  src/Cart.tsx:9:5 (prefix `app.cart.`)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Packing several keys into one judge request.
+//!
+//! Every single-key request repeats the long judging rules, which dominate the
+//! prompt for short UI strings. In batched mode ([`Options::batch_tokens`]),
+//! keys are packed in input order into requests whose estimated prompt size
+//! stays under a token budget, and the model answers with one [`KeyVerdict`]
+//! per key. Verdicts are still cached per key, and a batch whose answer cannot
+//! be used is judged again one key at a time.
+//!
+//! [`Options::batch_tokens`]: crate::Options::batch_tokens
+
+use crate::{Issue, KeyInput, Verdict, prompt};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The model's answer for a batch. This struct *is* the batch response
+/// contract: its derived JSON schema is sent as the strict `response_format`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BatchVerdict {
+    /// One verdict per key of the batch.
+    pub verdicts: Vec<KeyVerdict>,
+}
+
+/// The verdict for one key of a batch.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct KeyVerdict {
+    /// The translation key the verdict is for, as given in the prompt.
+    pub key: String,
+    /// Whether every language tells the user the same thing.
+    pub consistent: bool,
+    /// The languages that do not. Empty when `consistent` is `true`.
+    pub issues: Vec<Issue>,
+}
+
+impl From<KeyVerdict> for Verdict {
+    fn from(verdict: KeyVerdict) -> Self {
+        Self {
+            consistent: verdict.consistent,
+            issues: verdict.issues,
+        }
+    }
+}
+
+/// A rough prompt-token estimate for `text`: four ASCII characters per token
+/// and one token per other character, which overestimates rather than
+/// underestimates for scripts such as Japanese or Thai.
+#[must_use]
+pub fn estimate_tokens(text: &str) -> usize {
+    let ascii = text.bytes().filter(u8::is_ascii).count();
+    let other = text.chars().filter(|c| !c.is_ascii()).count();
+    ascii.div_ceil(4) + other
+}
+
+/// Splits `keys` in order into batches whose rendered prompt is estimated to
+/// stay within `budget` tokens. A key too large for the budget on its own
+/// still gets a batch of its own.
+#[must_use]
+pub fn pack<'k, 'a>(keys: &[&'k KeyInput<'a>], budget: usize) -> Vec<Vec<&'k KeyInput<'a>>> {
+    let overhead = estimate_tokens(&prompt::BATCH_TEMPLATE.replace("{keys}", ""));
+    let mut batches: Vec<Vec<&KeyInput<'_>>> = Vec::new();
+    let mut current = Vec::new();
+    let mut used = overhead;
+    for &key in keys {
+        // The separating blank line is counted as one token.
+        let size = estimate_tokens(&prompt::render_block(key)) + 1;
+        if !current.is_empty() && used + size > budget {
+            batches.push(std::mem::take(&mut current));
+            used = overhead;
+        }
+        current.push(key);
+        used += size;
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{estimate_tokens, pack};
+    use crate::{KeyInput, LanguageText, prompt};
+
+    #[test_util::test]
+    fn packs_keys_in_order_under_the_budget() {
+        let key = |key| KeyInput {
+            key,
+            languages: vec![
+                LanguageText {
+                    language: "en",
+                    text: "Save your changes before leaving",
+                },
+                LanguageText {
+                    language: "ja",
+                    text: "移動する前に変更を保存してください",
+                },
+            ],
+        };
+        let keys = [key("a"), key("b"), key("c"), key("d"), key("e")];
+        let refs: Vec<_> = keys.iter().collect();
+        let overhead = estimate_tokens(&prompt::BATCH_TEMPLATE.replace("{keys}", ""));
+        let block = estimate_tokens(&prompt::render_block(&keys[0])) + 1;
+
+        let batches = pack(&refs, overhead + 2 * block);
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        let order: Vec<&str> = batches.iter().flatten().map(|key| key.key).collect();
+        assert_eq!(order, vec!["a", "b", "c", "d", "e"]);
+
+        // A budget below a single key still judges every key, one per batch.
+        assert_eq!(pack(&refs, 0).len(), 5);
+    }
+
+    #[test_util::test]
+    fn estimates_non_ascii_text_per_character() {
+        assert_eq!(estimate_tokens("Save"), 1);
+        assert_eq!(estimate_tokens("保存"), 2);
+    }
+}
@@ -9,9 +9,16 @@
 //! The cache is bounded by entry count, evicted least-recently-used: a hit
 //! bumps the file's modification time, and [`Cache::enforce_capacity`] removes
 //! the oldest entries once per run when over capacity.
+//!
+//! A cache can be moved between machines as one JSON Lines file
+//! ([`Cache::export`], [`Cache::import`]), so that CI can publish the verdicts
+//! it paid for and developers can seed their cache from them.
 
 use crate::Verdict;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 /// Default [`Cache`] capacity, in verdicts.
 ///
@@ -20,6 +27,73 @@ use std::path::{Path, PathBuf};
 /// and prompt variants without evicting anything in practice.
 pub const DEFAULT_CAPACITY: usize = 100_000;
 
+/// The file in the cache root counting lookups across runs.
+const LOOKUPS_FILE: &str = "lookups.json";
+
+/// One verdict of an exported cache, as a line of JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+    /// The cache key, a hex BLAKE3 hash.
+    pub key: String,
+    /// When the verdict was last used, in seconds since the Unix epoch.
+    pub used: u64,
+    /// The cached verdict.
+    pub verdict: Verdict,
+}
+
+/// Cache hits and misses counted across runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lookups {
+    /// Keys answered from the cache.
+    pub hits: u64,
+    /// Keys that needed a request.
+    pub misses: u64,
+}
+
+impl Lookups {
+    /// The share of lookups answered from the cache, or `None` before the
+    /// first lookup.
+    #[must_use]
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "lookup counts are far below 2^52"
+        )]
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// What a cache holds, as reported by [`Cache::summary`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Cached verdicts.
+    pub entries: usize,
+    /// Their total size in bytes.
+    pub bytes: u64,
+    /// When the least recently used verdict was last used.
+    pub oldest: Option<SystemTime>,
+    /// Hits and misses since the cache was created.
+    pub lookups: Lookups,
+}
+
+/// The outcome of [`Cache::import`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Imported {
+    /// Verdicts added to the cache.
+    pub added: usize,
+    /// Verdicts the cache already held, which are kept as they are.
+    pub present: usize,
+}
+
+/// A stored verdict file.
+struct Stored {
+    path: PathBuf,
+    modified: SystemTime,
+    len: u64,
+}
+
 /// A bounded, content-addressed verdict cache rooted at one directory.
 pub struct Cache {
     root: PathBuf,
@@ -65,6 +139,12 @@ impl Cache {
         hasher.finalize().to_hex().to_string()
     }
 
+    /// Whether `key` has the form of a key made by [`Self::key`]. Imported keys
+    /// become file names, so nothing else is accepted.
+    fn is_key(key: &str) -> bool {
+        key.len() == 64 && key.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    }
+
     /// The sharded path for a cache `key`.
     fn entry_path(&self, key: &str) -> PathBuf {
         let shard = key.get(..2).unwrap_or("00");
@@ -121,7 +201,22 @@ impl Cache {
     ///
     /// Returns an error if the cache directory cannot be listed.
     pub fn enforce_capacity(&self) -> Result<(), std::io::Error> {
-        let mut entries: Vec<(std::time::SystemTime, PathBuf)> = Vec::new();
+        let mut entries = self.stored()?;
+        if entries.len() <= self.capacity {
+            return Ok(());
+        }
+        entries.sort_by_key(|entry| entry.modified);
+        let excess = entries.len() - self.capacity;
+        for entry in entries.into_iter().take(excess) {
+            // Racing runs may have removed it already; eviction is best-effort.
+            let _ = std::fs::remove_file(entry.path);
+        }
+        Ok(())
+    }
+
+    /// Every file in the shard directories.
+    fn stored(&self) -> Result<Vec<Stored>, std::io::Error> {
+        let mut entries = Vec::new();
         for shard in std::fs::read_dir(&self.root)? {
             let shard = shard?;
             if !shard.file_type()?.is_dir() {
@@ -131,31 +226,164 @@ impl Cache {
                 let entry = entry?;
                 let metadata = entry.metadata()?;
                 if metadata.is_file() {
-                    let modified = metadata
-                        .modified()
-                        .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                    entries.push((modified, entry.path()));
+                    entries.push(Stored {
+                        path: entry.path(),
+                        modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                        len: metadata.len(),
+                    });
                 }
             }
         }
-        if entries.len() <= self.capacity {
-            return Ok(());
+        Ok(entries)
+    }
+
+    /// Adds `hits` and `misses` to the lookups counted across runs.
+    ///
+    /// Concurrent runs may lose each other's counts; the hit rate is a guide,
+    /// not an audit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the counts cannot be written.
+    pub fn record_lookups(&self, hits: u64, misses: u64) -> Result<(), std::io::Error> {
+        let mut lookups = self.lookups();
+        lookups.hits += hits;
+        lookups.misses += misses;
+        let path = self.root.join(LOOKUPS_FILE);
+        let tmp = path.with_extension(format!("{}.part", std::process::id()));
+        std::fs::write(&tmp, serde_json::to_vec(&lookups)?)?;
+        std::fs::rename(&tmp, &path)
+    }
+
+    /// The lookups counted so far; none when the counts are missing or
+    /// unreadable.
+    fn lookups(&self) -> Lookups {
+        std::fs::read(self.root.join(LOOKUPS_FILE))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    /// Counts the cached verdicts, their size, and the lookups so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be listed.
+    pub fn summary(&self) -> Result<Summary, std::io::Error> {
+        let entries = self.stored()?;
+        Ok(Summary {
+            entries: entries.len(),
+            bytes: entries.iter().map(|entry| entry.len).sum(),
+            oldest: entries.iter().map(|entry| entry.modified).min(),
+            lookups: self.lookups(),
+        })
+    }
+
+    /// Removes the verdicts not used within `age`, returning how many were
+    /// removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be listed or an entry
+    /// cannot be removed.
+    pub fn prune(&self, age: Duration) -> Result<usize, std::io::Error> {
+        let cutoff = SystemTime::now()
+            .checked_sub(age)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut removed = 0;
+        for entry in self.stored()? {
+            if entry.modified < cutoff {
+                match std::fs::remove_file(&entry.path) {
+                    Ok(()) => removed += 1,
+                    Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(error) => return Err(error),
+                }
+            }
         }
-        entries.sort_by_key(|(modified, _)| *modified);
-        let excess = entries.len() - self.capacity;
-        for (_, path) in entries.into_iter().take(excess) {
-            // Racing runs may have removed it already; eviction is best-effort.
-            let _ = std::fs::remove_file(path);
+        Ok(removed)
+    }
+
+    /// Writes every readable verdict to `output` as one [`Entry`] per line,
+    /// ordered by key, and returns how many were written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be listed or `output`
+    /// cannot be written.
+    pub fn export(&self, output: &mut dyn Write) -> Result<usize, std::io::Error> {
+        let mut entries: Vec<Entry> = self
+            .stored()?
+            .into_iter()
+            .filter_map(|stored| {
+                let key = stored.path.file_stem()?.to_str()?.to_string();
+                if stored.path.extension()? != "json" || !Self::is_key(&key) {
+                    return None;
+                }
+                let verdict = serde_json::from_slice(&std::fs::read(&stored.path).ok()?).ok()?;
+                let used = stored
+                    .modified
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                Some(Entry { key, used, verdict })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        for entry in &entries {
+            serde_json::to_writer(&mut *output, entry)?;
+            output.write_all(b"\n")?;
         }
-        Ok(())
+        output.flush()?;
+        Ok(entries.len())
+    }
+
+    /// Adds the verdicts of an export to the cache, keeping their last use.
+    /// Verdicts the cache already holds are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line is not a valid [`Entry`], in which case
+    /// nothing after it is imported, or an entry cannot be written.
+    pub fn import(&self, input: &mut dyn BufRead) -> Result<Imported, std::io::Error> {
+        let invalid = |line: usize, message: String| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("line {line}: {message}"),
+            )
+        };
+        let mut imported = Imported::default();
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: Entry = serde_json::from_str(&line)
+                .map_err(|error| invalid(index + 1, error.to_string()))?;
+            if !Self::is_key(&entry.key) {
+                return Err(invalid(index + 1, format!("invalid key `{}`", entry.key)));
+            }
+            if self.entry_path(&entry.key).is_file() {
+                imported.present += 1;
+                continue;
+            }
+            self.store(&entry.key, &entry.verdict)?;
+            let used = SystemTime::UNIX_EPOCH + Duration::from_secs(entry.used);
+            std::fs::File::options()
+                .write(true)
+                .open(self.entry_path(&entry.key))?
+                .set_modified(used)?;
+            imported.added += 1;
+        }
+        Ok(imported)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Cache;
+    use super::{Cache, Imported, Lookups};
     use crate::Verdict;
     use color_eyre::eyre::{self, OptionExt, WrapErr};
+    use std::time::{Duration, SystemTime};
 
     fn temp_cache(capacity: usize) -> eyre::Result<(tempfile::TempDir, Cache)> {
         let dir = tempfile::tempdir()?;
@@ -214,6 +442,80 @@ mod tests {
         assert_eq!(remaining, 2);
     }
 
+    /// An export carries verdicts and their last use into another cache,
+    /// which keeps the verdicts it already holds.
+    #[test_util::test]
+    fn exports_and_imports_verdicts() {
+        let (_dir, source) = temp_cache(10)?;
+        let verdict = Verdict {
+            consistent: true,
+            issues: vec![],
+        };
+        let old = source.key(&["old"]);
+        source.store(&old, &verdict)?;
+        let used = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(source.entry_path(&old))?
+            .set_modified(used)?;
+        source.store(&source.key(&["new"]), &verdict)?;
+
+        let mut archive = Vec::new();
+        assert_eq!(source.export(&mut archive)?, 2);
+        let archive = String::from_utf8(archive)?;
+        assert!(archive.contains("\"used\":1700000000"), "{archive}");
+
+        let (_dir, target) = temp_cache(10)?;
+        target.store(&old, &verdict)?;
+        let imported = target.import(&mut archive.as_bytes())?;
+        assert_eq!(
+            imported,
+            Imported {
+                added: 1,
+                present: 1
+            }
+        );
+        assert!(target.lookup(&source.key(&["new"])).is_some());
+
+        // Keys become file names, so anything but a hash is rejected.
+        let forged = archive.replace(&old, "../../escape");
+        let error = target
+            .import(&mut forged.as_bytes())
+            .err()
+            .ok_or_eyre("forged key was imported")?;
+        assert!(
+            error.to_string().contains("invalid key `../../escape`"),
+            "{error}"
+        );
+    }
+
+    /// Pruning removes the verdicts not used recently, and the summary counts
+    /// what is left along with the recorded lookups.
+    #[test_util::test]
+    fn prunes_and_summarizes() {
+        let (_dir, cache) = temp_cache(10)?;
+        let verdict = Verdict {
+            consistent: true,
+            issues: vec![],
+        };
+        let stale = cache.key(&["stale"]);
+        cache.store(&stale, &verdict)?;
+        std::fs::File::options()
+            .write(true)
+            .open(cache.entry_path(&stale))?
+            .set_modified(SystemTime::now() - Duration::from_hours(40 * 24))?;
+        cache.store(&cache.key(&["fresh"]), &verdict)?;
+        cache.record_lookups(3, 1)?;
+        cache.record_lookups(0, 1)?;
+
+        assert_eq!(cache.prune(Duration::from_hours(30 * 24))?, 1);
+        let summary = cache.summary()?;
+        assert_eq!(summary.entries, 1);
+        assert!(summary.bytes > 0);
+        assert_eq!(summary.lookups, Lookups { hits: 3, misses: 2 });
+        assert_eq!(summary.lookups.hit_rate(), Some(0.6));
+    }
+
     /// Counts regular files under `root` recursively.
     fn walkdir_count(root: &std::path::Path) -> eyre::Result<usize> {
         let mut count = 0;
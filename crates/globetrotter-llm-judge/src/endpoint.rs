@@ -0,0 +1,296 @@
+//! Rate limits, retries, and the token budget of the endpoint.
+//!
+//! Every request of a [`Judge`](crate::Judge) or
+//! [`Translator`](crate::translate::Translator) goes through one [`Endpoint`].
+//! Before a request starts, it waits for the requests-per-minute and
+//! tokens-per-minute limits of [`Options`], and it refuses to start once the
+//! total token budget is spent, counting the estimated prompt tokens of the
+//! requests in flight. A rate-limited (`429`) or failed (`5xx`)
+//! request is retried with exponential backoff, honoring `Retry-After`.
+
+use crate::{Error, Options, batch};
+use async_openai::middleware::ReqwestService;
+use async_openai::middleware::retry::OpenAIRetryLayer;
+use async_openai::types::chat::CompletionUsage;
+use async_openai::{Client, config::OpenAIConfig};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// The client of an endpoint together with its limits and usage counters.
+pub(crate) struct Endpoint {
+    pub(crate) client: Client<OpenAIConfig>,
+    requests: Option<Mutex<Bucket>>,
+    tokens: Option<Mutex<Bucket>>,
+    budget: Option<u64>,
+    /// Tokens used by completed requests.
+    used: AtomicU64,
+    /// Tokens used by completed requests plus the estimates reserved by those
+    /// in flight, which the budget is checked against.
+    committed: AtomicU64,
+    /// Requests started, not counting retries.
+    sent: AtomicUsize,
+    /// HTTP attempts made, including retries.
+    attempts: Arc<AtomicUsize>,
+}
+
+/// Tokens used and retries made by an [`Endpoint`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Usage {
+    pub(crate) tokens: u64,
+    pub(crate) retries: usize,
+}
+
+impl Usage {
+    /// The usage since an `earlier` snapshot.
+    pub(crate) fn since(self, earlier: Self) -> Self {
+        Self {
+            tokens: self.tokens.saturating_sub(earlier.tokens),
+            retries: self.retries.saturating_sub(earlier.retries),
+        }
+    }
+}
+
+impl Endpoint {
+    /// Creates the endpoint of `options`.
+    ///
+    /// The API key is read from [`Options::api_key_env`] here; local servers
+    /// ignore it, so an unset variable sends an empty one.
+    pub(crate) fn new(options: &Options) -> Self {
+        let api_key = std::env::var(&options.api_key_env).unwrap_or_default();
+        let config = OpenAIConfig::new()
+            .with_api_base(options.base_url.trim_end_matches('/'))
+            .with_api_key(api_key);
+
+        // Count every attempt below the retry layer; the attempts beyond the
+        // requests sent are retries.
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        let transport = ReqwestService::default();
+        let service = tower::ServiceBuilder::new()
+            .layer(OpenAIRetryLayer::new(options.max_retries))
+            .service_fn(move |request| {
+                use tower::Service;
+                counter.fetch_add(1, Ordering::Relaxed);
+                transport.clone().call(request)
+            });
+
+        let now = Instant::now();
+        Self {
+            client: Client::with_config(config).with_http_service(service),
+            requests: options
+                .requests_per_minute
+                .map(|limit| Mutex::new(Bucket::new(limit, now))),
+            tokens: options
+                .tokens_per_minute
+                .map(|limit| Mutex::new(Bucket::new(limit, now))),
+            budget: options.token_budget,
+            used: AtomicU64::new(0),
+            committed: AtomicU64::new(0),
+            sent: AtomicUsize::new(0),
+            attempts,
+        }
+    }
+
+    /// Waits until a request with `prompt` may start, returning its estimated
+    /// prompt tokens.
+    ///
+    /// The estimate is reserved against the token budget right away, so that
+    /// concurrent requests cannot all start on the same remaining budget. It
+    /// is settled by [`Endpoint::record`] or returned by
+    /// [`Endpoint::release`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BudgetExhausted`] once the token budget is spent.
+    pub(crate) async fn acquire(&self, prompt: &str) -> Result<u64, Error> {
+        let estimate = batch::estimate_tokens(prompt) as u64;
+        self.committed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |committed| {
+                self.budget
+                    .is_none_or(|budget| committed < budget)
+                    .then(|| committed.saturating_add(estimate))
+            })
+            .map_err(|used| Error::BudgetExhausted {
+                used,
+                budget: self.budget.unwrap_or_default(),
+            })?;
+        let mut wait = Duration::ZERO;
+        if let Some(bucket) = &self.requests {
+            wait = wait.max(bucket.lock().await.reserve(1, Instant::now()));
+        }
+        if let Some(bucket) = &self.tokens {
+            wait = wait.max(bucket.lock().await.reserve(estimate, Instant::now()));
+        }
+        if !wait.is_zero() {
+            tracing::debug!(?wait, "waiting for the endpoint rate limit");
+            tokio::time::sleep(wait).await;
+        }
+        self.sent.fetch_add(1, Ordering::Relaxed);
+        Ok(estimate)
+    }
+
+    /// Records the tokens of a completed request that was estimated at
+    /// `estimate` tokens. Endpoints that report no usage are charged the
+    /// estimate.
+    pub(crate) async fn record(&self, estimate: u64, usage: Option<&CompletionUsage>) {
+        let tokens = usage.map_or(estimate, |usage| u64::from(usage.total_tokens));
+        self.used.fetch_add(tokens, Ordering::Relaxed);
+        self.settle(estimate, tokens);
+        // Settle the difference to the estimate taken by `acquire`.
+        if let Some(bucket) = &self.tokens {
+            let mut bucket = bucket.lock().await;
+            if tokens > estimate {
+                bucket.take(tokens - estimate, Instant::now());
+            } else {
+                bucket.give(estimate - tokens, Instant::now());
+            }
+        }
+    }
+
+    /// Returns the budget reserved for a request estimated at `estimate`
+    /// tokens that failed.
+    pub(crate) fn release(&self, estimate: u64) {
+        self.settle(estimate, 0);
+    }
+
+    /// Replaces the `estimate` reserved against the budget by the `tokens`
+    /// actually used.
+    fn settle(&self, estimate: u64, tokens: u64) {
+        // The closure always returns `Some`, so the update cannot fail.
+        let _ = self
+            .committed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |committed| {
+                Some(committed.saturating_sub(estimate).saturating_add(tokens))
+            });
+    }
+
+    /// The tokens used and retries made so far.
+    pub(crate) fn usage(&self) -> Usage {
+        let attempts = self.attempts.load(Ordering::Relaxed);
+        let sent = self.sent.load(Ordering::Relaxed);
+        Usage {
+            tokens: self.used.load(Ordering::Relaxed),
+            retries: attempts.saturating_sub(sent),
+        }
+    }
+}
+
+/// A bucket refilled continuously with `per_minute` units a minute, and
+/// holding at most that many.
+///
+/// A reservation takes its units right away, which may leave the bucket in
+/// debt; it then waits until the debt would have been refilled. Concurrent
+/// requests therefore queue up behind each other instead of all waking at
+/// once.
+#[derive(Debug)]
+struct Bucket {
+    per_minute: f64,
+    available: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// A full bucket.
+    fn new(per_minute: u32, now: Instant) -> Self {
+        let per_minute = f64::from(per_minute.max(1));
+        Self {
+            per_minute,
+            available: per_minute,
+            updated: now,
+        }
+    }
+
+    /// Takes `amount` units and returns how long to wait before using them.
+    fn reserve(&mut self, amount: u64, now: Instant) -> Duration {
+        self.take(amount, now);
+        if self.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available * 60.0 / self.per_minute)
+        }
+    }
+
+    /// Takes `amount` units without waiting, capped at a minute's worth so
+    /// that an oversized request still goes once the bucket is full.
+    fn take(&mut self, amount: u64, now: Instant) {
+        self.refill(now);
+        self.available -= self.units(amount);
+    }
+
+    /// Returns `amount` units.
+    fn give(&mut self, amount: u64, now: Instant) {
+        self.refill(now);
+        self.available = (self.available + self.units(amount)).min(self.per_minute);
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.updated = now;
+        self.available = (self.available + elapsed * self.per_minute / 60.0).min(self.per_minute);
+    }
+
+    fn units(&self, amount: u64) -> f64 {
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "token counts are far below 2^52"
+        )]
+        let amount = amount as f64;
+        amount.min(self.per_minute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bucket, Endpoint};
+    use crate::{Error, Options};
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    /// Requests in flight reserve their estimate, so a second request cannot
+    /// start on a budget the first one may already spend.
+    #[test_util::test]
+    async fn reserves_the_token_budget_of_requests_in_flight() {
+        let endpoint = Endpoint::new(&Options {
+            token_budget: Some(10),
+            ..Options::default()
+        });
+        let prompt = "x".repeat(64);
+        let estimate = endpoint.acquire(&prompt).await?;
+        assert!(estimate >= 10, "{estimate}");
+        assert!(matches!(
+            endpoint.acquire(&prompt).await,
+            Err(Error::BudgetExhausted { budget: 10, .. })
+        ));
+
+        // A failed request returns its reservation.
+        endpoint.release(estimate);
+        let estimate = endpoint.acquire(&prompt).await?;
+        endpoint.record(estimate, None).await;
+        assert_eq!(endpoint.usage().tokens, estimate);
+        assert!(endpoint.acquire("x").await.is_err());
+    }
+
+    #[test_util::test]
+    fn queues_reservations_beyond_the_limit() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(60, start);
+        for _ in 0..60 {
+            assert_eq!(bucket.reserve(1, start), Duration::ZERO);
+        }
+        // One unit refills every second; reservations queue up behind each
+        // other.
+        assert_eq!(bucket.reserve(1, start), Duration::from_secs(1));
+        assert_eq!(bucket.reserve(1, start), Duration::from_secs(2));
+        let later = start + Duration::from_secs(5);
+        assert_eq!(bucket.reserve(1, later), Duration::ZERO);
+
+        // A request larger than a minute's worth waits for a full bucket
+        // rather than forever.
+        let mut bucket = Bucket::new(100, start);
+        assert_eq!(bucket.reserve(1_000, start), Duration::ZERO);
+        assert_eq!(bucket.reserve(50, start), Duration::from_secs(30));
+    }
+}
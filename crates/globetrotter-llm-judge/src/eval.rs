@@ -0,0 +1,530 @@
+//! Offline evaluation of a judge configuration against labelled keys.
+//!
+//! Prompt wording and model choice change which findings the judge reports,
+//! and the best combination differs per catalog. A dataset of keys whose
+//! drifted languages are known turns that into numbers: [`run`] judges every
+//! case and the [`Report`] gives precision and recall per finding, a
+//! confidence threshold sweep, and how well the reported confidence matches
+//! the observed precision.
+//!
+//! A dataset is JSON Lines, one [`Case`] per line:
+//!
+//! ```json
+//! {"key": "actions.save", "languages": {"en": "Save", "fr": "Supprimer"}, "drifted": ["fr"]}
+//! {"key": "actions.close", "languages": {"en": "Close", "fr": "Fermer"}, "drifted": []}
+//! ```
+
+use crate::{Error, Judge, KeyInput, LanguageText, Progress};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// One labelled key.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Case {
+    /// The dotted key path.
+    pub key: String,
+    /// The key's strings by language code, in prompt order.
+    pub languages: IndexMap<String, String>,
+    /// The languages that tell users something different. Empty for a
+    /// consistent key.
+    #[serde(default)]
+    pub drifted: Vec<String>,
+}
+
+/// Parses a JSON Lines dataset. Blank lines are skipped.
+///
+/// # Errors
+///
+/// Returns [`Error::Dataset`] for a line that is not a valid case, a key that
+/// appears twice, or a drifted language the case has no string for.
+pub fn parse(content: &str) -> Result<Vec<Case>, Error> {
+    let mut keys = HashSet::new();
+    let mut cases = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let invalid = |message: String| Error::Dataset {
+            line: index + 1,
+            message,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let case: Case = serde_json::from_str(line).map_err(|error| invalid(error.to_string()))?;
+        if !keys.insert(case.key.clone()) {
+            return Err(invalid(format!("duplicate key `{}`", case.key)));
+        }
+        if let Some(language) = case
+            .drifted
+            .iter()
+            .find(|language| !case.languages.contains_key(*language))
+        {
+            return Err(invalid(format!(
+                "drifted language `{language}` has no string"
+            )));
+        }
+        cases.push(case);
+    }
+    Ok(cases)
+}
+
+/// A finding of the evaluation run, scored against the labels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scored {
+    /// The dotted key path.
+    pub key: String,
+    /// The flagged language code.
+    pub language: String,
+    /// The model's explanation.
+    pub problem: String,
+    /// The model's confidence.
+    pub confidence: f64,
+    /// Whether the language is labelled as drifted.
+    pub correct: bool,
+}
+
+/// A drifted language the judge did not flag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Missed {
+    /// The dotted key path.
+    pub key: String,
+    /// The drifted language code.
+    pub language: String,
+}
+
+/// Findings whose confidence falls into one range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bin {
+    /// The lowest confidence of the range.
+    pub lower: f64,
+    /// The highest confidence of the range, exclusive except for 1.0.
+    pub upper: f64,
+    /// The number of findings in the range.
+    pub findings: usize,
+    /// The number of those that are labelled as drifted.
+    pub correct: usize,
+    /// The mean confidence of the findings.
+    pub mean_confidence: f64,
+}
+
+impl Bin {
+    /// The observed precision of the findings, or `None` for an empty bin.
+    #[must_use]
+    pub fn observed(&self) -> Option<f64> {
+        ratio(self.correct, self.findings)
+    }
+}
+
+/// The result of an evaluation run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    /// The number of cases.
+    pub cases: usize,
+    /// The number of labelled drifted languages.
+    pub drifted: usize,
+    /// Cases the judge failed to answer or skipped; their drifted languages
+    /// count as missed.
+    pub failed: usize,
+    /// Every finding, one per key and language, scored against the labels.
+    pub findings: Vec<Scored>,
+    /// The drifted languages no finding flagged.
+    pub missed: Vec<Missed>,
+}
+
+/// Judges every case with `judge` and scores the findings.
+///
+/// Findings below the judge's
+/// [`min_confidence`](crate::Options::min_confidence) are never seen, so a
+/// judge for evaluation should keep it at `0.0` and leave thresholds to
+/// [`Report::at_threshold`].
+///
+/// # Errors
+///
+/// Returns an error if judging aborts, see [`Judge::judge`].
+pub async fn run(judge: &Judge, cases: &[Case], progress: &dyn Progress) -> Result<Report, Error> {
+    let inputs: Vec<KeyInput<'_>> = cases
+        .iter()
+        .map(|case| KeyInput {
+            key: &case.key,
+            languages: case
+                .languages
+                .iter()
+                .map(|(language, text)| LanguageText { language, text })
+                .collect(),
+        })
+        .collect();
+
+    // A model may flag a language twice; keep its most confident finding.
+    let mut flagged: HashMap<(String, String), crate::Finding> = HashMap::new();
+    let stats = judge
+        .judge(&inputs, progress, &mut |finding| {
+            let id = (finding.key.clone(), finding.language.clone());
+            match flagged.get(&id) {
+                Some(existing) if existing.confidence >= finding.confidence => {}
+                _ => {
+                    flagged.insert(id, finding);
+                }
+            }
+        })
+        .await?;
+
+    let mut report = Report {
+        cases: cases.len(),
+        failed: stats.failed + stats.skipped,
+        ..Report::default()
+    };
+    for case in cases {
+        report.drifted += case.drifted.len();
+        for language in &case.drifted {
+            if !flagged.contains_key(&(case.key.clone(), language.clone())) {
+                report.missed.push(Missed {
+                    key: case.key.clone(),
+                    language: language.clone(),
+                });
+            }
+        }
+    }
+    let labelled: HashMap<&str, &Case> =
+        cases.iter().map(|case| (case.key.as_str(), case)).collect();
+    report.findings = flagged
+        .into_values()
+        .map(|finding| Scored {
+            correct: labelled
+                .get(finding.key.as_str())
+                .is_some_and(|case| case.drifted.contains(&finding.language)),
+            key: finding.key,
+            language: finding.language,
+            problem: finding.problem,
+            confidence: finding.confidence,
+        })
+        .collect();
+    report.findings.sort_by(|a, b| {
+        (a.key.as_str(), a.language.as_str()).cmp(&(b.key.as_str(), b.language.as_str()))
+    });
+    Ok(report)
+}
+
+/// Precision and recall at one confidence threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scores {
+    /// The threshold: findings below it are dropped.
+    pub min_confidence: f64,
+    /// Findings kept.
+    pub findings: usize,
+    /// Findings kept that are labelled as drifted.
+    pub correct: usize,
+    /// The fraction of kept findings that are correct, or `None` without
+    /// findings.
+    pub precision: Option<f64>,
+    /// The fraction of drifted languages found, or `None` without any.
+    pub recall: Option<f64>,
+}
+
+impl Scores {
+    /// The harmonic mean of precision and recall, or `None` if either is
+    /// undefined or both are zero.
+    #[must_use]
+    pub fn f1(&self) -> Option<f64> {
+        let (precision, recall) = (self.precision?, self.recall?);
+        (precision + recall > 0.0).then(|| 2.0 * precision * recall / (precision + recall))
+    }
+}
+
+impl Report {
+    /// The scores when findings below `min_confidence` are dropped.
+    #[must_use]
+    pub fn at_threshold(&self, min_confidence: f64) -> Scores {
+        let kept = self
+            .findings
+            .iter()
+            .filter(|finding| finding.confidence >= min_confidence);
+        let (findings, correct) = kept.fold((0, 0), |(findings, correct), finding| {
+            (findings + 1, correct + usize::from(finding.correct))
+        });
+        Scores {
+            min_confidence,
+            findings,
+            correct,
+            precision: ratio(correct, findings),
+            recall: ratio(correct, self.drifted),
+        }
+    }
+
+    /// The findings grouped into `bins` equal confidence ranges.
+    #[must_use]
+    pub fn calibration(&self, bins: usize) -> Vec<Bin> {
+        let bins = bins.max(1);
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "bin counts are tiny compared to f64 precision"
+        )]
+        let width = 1.0 / bins as f64;
+        let mut result: Vec<Bin> = (0..bins)
+            .map(|index| {
+                #[expect(
+                    clippy::cast_precision_loss,
+                    reason = "bin counts are tiny compared to f64 precision"
+                )]
+                let lower = index as f64 * width;
+                Bin {
+                    lower,
+                    upper: lower + width,
+                    findings: 0,
+                    correct: 0,
+                    mean_confidence: 0.0,
+                }
+            })
+            .collect();
+        for finding in &self.findings {
+            #[expect(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                reason = "confidence is clamped to 0–1, so the index is a small non-negative number"
+            )]
+            let index = ((finding.confidence / width) as usize).min(bins - 1);
+            if let Some(bin) = result.get_mut(index) {
+                bin.findings += 1;
+                bin.correct += usize::from(finding.correct);
+                bin.mean_confidence += finding.confidence;
+            }
+        }
+        for bin in &mut result {
+            if bin.findings > 0 {
+                #[expect(
+                    clippy::cast_precision_loss,
+                    reason = "finding counts are far below f64 precision"
+                )]
+                let findings = bin.findings as f64;
+                bin.mean_confidence /= findings;
+            }
+        }
+        result
+    }
+
+    /// The expected calibration error over `bins` ranges: the mean gap between
+    /// confidence and observed precision, weighted by findings. `None` without
+    /// findings.
+    #[must_use]
+    pub fn calibration_error(&self, bins: usize) -> Option<f64> {
+        if self.findings.is_empty() {
+            return None;
+        }
+        let error: f64 = self
+            .calibration(bins)
+            .iter()
+            .filter_map(|bin| {
+                let observed = bin.observed()?;
+                #[expect(
+                    clippy::cast_precision_loss,
+                    reason = "finding counts are far below f64 precision"
+                )]
+                let findings = bin.findings as f64;
+                Some(findings * (bin.mean_confidence - observed).abs())
+            })
+            .sum();
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "finding counts are far below f64 precision"
+        )]
+        let total = self.findings.len() as f64;
+        Some(error / total)
+    }
+}
+
+/// `part / whole`, or `None` when `whole` is zero.
+fn ratio(part: usize, whole: usize) -> Option<f64> {
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "dataset sizes are far below f64 precision"
+    )]
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+/// Formats an optional ratio with two decimals, or `-` when undefined.
+fn decimal(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| format!("{value:.2}"))
+}
+
+/// The confidence thresholds of the sweep in the report summary.
+const THRESHOLDS: [f64; 6] = [0.0, 0.5, 0.6, 0.7, 0.8, 0.9];
+
+/// The number of confidence ranges in the report summary.
+const BINS: usize = 5;
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let all = self.at_threshold(0.0);
+        writeln!(
+            f,
+            "cases: {} ({} failed), drifted languages: {}, findings: {}",
+            self.cases,
+            self.failed,
+            self.drifted,
+            self.findings.len()
+        )?;
+        writeln!(
+            f,
+            "precision: {} ({}/{})",
+            decimal(all.precision),
+            all.correct,
+            all.findings
+        )?;
+        writeln!(
+            f,
+            "recall:    {} ({}/{})",
+            decimal(all.recall),
+            all.correct,
+            self.drifted
+        )?;
+        writeln!(f, "f1:        {}", decimal(all.f1()))?;
+
+        writeln!(f, "\nmin confidence  findings  precision  recall  f1")?;
+        for threshold in THRESHOLDS {
+            let scores = self.at_threshold(threshold);
+            writeln!(
+                f,
+                "{threshold:>14.1}  {:>8}  {:>9}  {:>6}  {:>4}",
+                scores.findings,
+                decimal(scores.precision),
+                decimal(scores.recall),
+                decimal(scores.f1())
+            )?;
+        }
+
+        writeln!(f, "\nconfidence  findings  mean  observed precision")?;
+        for bin in self.calibration(BINS) {
+            let mean = (bin.findings > 0).then_some(bin.mean_confidence);
+            writeln!(
+                f,
+                "{:.1}–{:.1}     {:>8}  {:>4}  {:>17}",
+                bin.lower,
+                bin.upper,
+                bin.findings,
+                decimal(mean),
+                decimal(bin.observed())
+            )?;
+        }
+        write!(
+            f,
+            "expected calibration error: {}",
+            decimal(self.calibration_error(BINS))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Report, Scored, parse, run};
+    use indoc::indoc;
+    use mock_llm::{MockServer, Rule};
+
+    fn scored(key: &str, confidence: f64, correct: bool) -> Scored {
+        Scored {
+            key: key.to_string(),
+            language: "fr".to_string(),
+            problem: String::new(),
+            confidence,
+            correct,
+        }
+    }
+
+    #[test_util::test]
+    fn parses_labelled_cases() {
+        let cases = parse(indoc! {r#"
+            {"key": "a", "languages": {"fr": "Supprimer", "en": "Save"}, "drifted": ["fr"]}
+
+            {"key": "b", "languages": {"en": "Close", "fr": "Fermer"}}
+        "#})?;
+        assert_eq!(cases.len(), 2);
+        // Languages keep their order in the line.
+        assert_eq!(
+            cases[0].languages.keys().collect::<Vec<_>>(),
+            vec!["fr", "en"]
+        );
+        assert!(cases[1].drifted.is_empty());
+
+        let error = parse(r#"{"key": "a", "languages": {"en": "Save"}, "drifted": ["fr"]}"#)
+            .err()
+            .map(|error| error.to_string());
+        assert_eq!(
+            error.as_deref(),
+            Some("dataset line 1: drifted language `fr` has no string")
+        );
+        assert!(
+            parse("{\"key\": \"a\", \"languages\": {}}\n{\"key\": \"a\", \"languages\": {}}")
+                .is_err()
+        );
+    }
+
+    #[test_util::test]
+    fn scores_thresholds_and_calibration() {
+        let report = Report {
+            cases: 4,
+            drifted: 3,
+            failed: 0,
+            findings: vec![
+                scored("a", 0.95, true),
+                scored("b", 0.9, true),
+                scored("c", 0.3, false),
+                scored("d", 0.35, true),
+            ],
+            missed: Vec::new(),
+        };
+        let all = report.at_threshold(0.0);
+        assert_eq!((all.findings, all.correct), (4, 3));
+        assert_eq!(all.precision, Some(0.75));
+        assert_eq!(all.recall, Some(1.0));
+        let strict = report.at_threshold(0.5);
+        assert_eq!(strict.precision, Some(1.0));
+        assert_eq!(strict.recall, Some(2.0 / 3.0));
+
+        let bins = report.calibration(5);
+        let counts: Vec<usize> = bins.iter().map(|bin| bin.findings).collect();
+        assert_eq!(counts, vec![0, 2, 0, 0, 2]);
+        assert_eq!(bins[1].observed(), Some(0.5));
+        // |0.325 - 0.5| and |0.925 - 1.0|, weighted equally.
+        let error = report.calibration_error(5).unwrap_or_default();
+        assert!((error - 0.125).abs() < 1e-9, "error: {error}");
+    }
+
+    #[test_util::test]
+    async fn evaluates_against_a_mock_server() {
+        let cases = parse(indoc! {r#"
+            {"key": "save", "languages": {"en": "Save", "fr": "Supprimer"}, "drifted": ["fr"]}
+            {"key": "close", "languages": {"en": "Close", "fr": "Fermer"}}
+            {"key": "open", "languages": {"en": "Open", "de": "Öffnen"}, "drifted": ["de"]}
+        "#})?;
+        let issue = |confidence: f64| {
+            Rule::json(serde_json::json!({"consistent": false, "issues": [{
+                "language": "fr", "problem": "different action",
+                "confidence": confidence, "suggestion": null,
+            }]}))
+        };
+        let server = MockServer::start(vec![
+            issue(0.9).containing("Supprimer"),
+            issue(0.4).containing("Fermer"),
+            Rule::json(serde_json::json!({"consistent": true, "issues": []})),
+        ])
+        .await?;
+        let judge = crate::Judge::new(crate::Options {
+            base_url: server.base_url(),
+            cache_capacity: 0,
+            ..crate::Options::default()
+        })?;
+        let report = run(&judge, &cases, &()).await?;
+
+        assert_eq!((report.cases, report.drifted, report.failed), (3, 2, 0));
+        let findings: Vec<(&str, bool)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.key.as_str(), finding.correct))
+            .collect();
+        assert_eq!(findings, vec![("close", false), ("save", true)]);
+        assert_eq!(report.missed.len(), 1);
+        assert_eq!(report.at_threshold(0.0).precision, Some(0.5));
+        assert_eq!(report.at_threshold(0.5).precision, Some(1.0));
+        assert_eq!(report.at_threshold(0.5).recall, Some(0.5));
+        assert!(report.to_string().contains("precision: 0.50 (1/2)"));
+    }
+}
@@ -13,14 +13,24 @@
 //!
 //! Verdicts are cached content-addressed on disk (see [`cache`]), so re-runs
 //! only pay for keys whose text — or judge configuration — actually changed.
+//! For large catalogs, several keys can share one request (see [`batch`]).
+//! Requests can be rate limited and capped by a total token budget, and
+//! rate-limited or failed requests are retried with backoff.
+//!
+//! The same endpoint can also fill in missing languages (see [`translate`]),
+//! and a prompt or model can be measured against labelled keys (see [`eval`]).
 //!
 //! The public API is pure data in, pure data out; it has no knowledge of
 //! globetrotter's model, diagnostics, or configuration types.
 
+pub mod batch;
 pub mod cache;
+mod endpoint;
+pub mod eval;
 pub mod prompt;
+pub mod translate;
 
-use async_openai::{Client, config::OpenAIConfig};
+use endpoint::Endpoint;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -53,6 +63,16 @@ pub enum Error {
         last: String,
     },
 
+    /// The token budget of [`Options::token_budget`] is spent, so no further
+    /// request is started.
+    #[error("token budget of {budget} exhausted ({used} tokens used)")]
+    BudgetExhausted {
+        /// Tokens used so far, including the estimates of requests in flight.
+        used: u64,
+        /// The configured budget.
+        budget: u64,
+    },
+
     /// A custom prompt template is missing a required placeholder.
     ///
     /// Without both placeholders every key would render the same prompt — and
@@ -65,6 +85,15 @@ pub enum Error {
         /// The placeholder that was not found.
         missing: &'static str,
     },
+
+    /// A line of an evaluation dataset is not a valid case.
+    #[error("dataset line {line}: {message}")]
+    Dataset {
+        /// The 1-based line number.
+        line: usize,
+        /// What is wrong with the line.
+        message: String,
+    },
 }
 
 /// Reasoning effort requested from the model, for models that support it.
@@ -120,6 +149,26 @@ pub struct Options {
     pub cache_dir: Option<std::path::PathBuf>,
     /// Maximum number of cached verdicts kept on disk; `0` disables caching.
     pub cache_capacity: usize,
+    /// Estimated prompt-token budget of a batched request, packing several
+    /// keys into one (see [`batch`]); `0` sends one request per key.
+    ///
+    /// Ignored with a custom [`template`](Self::template), which describes a
+    /// single key.
+    pub batch_tokens: usize,
+    /// Retries of a rate-limited (`429`) or failed (`5xx`) request, with
+    /// exponential backoff that honors a `Retry-After` header.
+    pub max_retries: usize,
+    /// Requests started per minute at most; `None` for no limit.
+    pub requests_per_minute: Option<u32>,
+    /// Tokens per minute at most, counting the estimated prompt before a
+    /// request and the reported usage after it; `None` for no limit.
+    pub tokens_per_minute: Option<u32>,
+    /// Total tokens, as reported by the endpoint, after which no further
+    /// request is started; `None` for no limit. The keys left are skipped and
+    /// counted rather than failing the run. Requests in flight count with
+    /// their estimated prompt tokens, so the budget is overshot by at most
+    /// the responses of requests already started.
+    pub token_budget: Option<u64>,
 }
 
 impl Default for Options {
@@ -135,6 +184,11 @@ impl Default for Options {
             min_confidence: 0.0,
             cache_dir: None,
             cache_capacity: cache::DEFAULT_CAPACITY,
+            batch_tokens: 0,
+            max_retries: 3,
+            requests_per_minute: None,
+            tokens_per_minute: None,
+            token_budget: None,
         }
     }
 }
@@ -158,6 +212,30 @@ pub struct KeyInput<'a> {
     pub languages: Vec<LanguageText<'a>>,
 }
 
+impl KeyInput<'_> {
+    /// A short hash of the key's strings in every language, independent of
+    /// their order. Any edit to one of them changes it, so a decision recorded
+    /// against the hash lapses when the strings change.
+    #[must_use]
+    pub fn content_hash(&self) -> String {
+        let mut languages = self.languages.clone();
+        languages.sort_by_key(|language| language.language);
+        let mut hasher = blake3::Hasher::new();
+        for language in languages {
+            for part in [language.language, language.text] {
+                hasher.update(&(part.len() as u64).to_le_bytes());
+                hasher.update(part.as_bytes());
+            }
+        }
+        hasher
+            .finalize()
+            .to_hex()
+            .get(..16)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
 /// The model's verdict for one key. This struct *is* the response contract:
 /// its derived JSON schema is sent as the strict `response_format`.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -172,11 +250,12 @@ pub struct Verdict {
 
 /// One language flagged by the model.
 ///
-/// The schema extension keeps `confidence` required for strict structured
-/// output even though serde accepts a missing value on the recovery path.
+/// The schema extension keeps `confidence` and `suggestion` required for strict
+/// structured output even though serde accepts missing values on the recovery
+/// path.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-#[schemars(extend("required" = ["language", "problem", "confidence"]))]
+#[schemars(extend("required" = ["language", "problem", "confidence", "suggestion"]))]
 pub struct Issue {
     /// The language code the model flagged.
     pub language: String,
@@ -190,6 +269,11 @@ pub struct Issue {
     /// finding is retained rather than silently filtered out.
     #[serde(default = "full_confidence")]
     pub confidence: f64,
+    /// A corrected text for the flagged language that tells users what the
+    /// other languages do, or `None` when the model proposes none. Verdicts
+    /// cached before suggestions existed parse with `None`.
+    #[serde(default)]
+    pub suggestion: Option<String>,
 }
 
 /// The parse-time default for [`Issue::confidence`]; full confidence, so
@@ -210,6 +294,8 @@ pub struct Finding {
     /// The model's self-reported certainty, clamped to 0.0–1.0. See
     /// [`Issue::confidence`] for how (little) to trust it.
     pub confidence: f64,
+    /// The model's corrected text for the flagged language, if any.
+    pub suggestion: Option<String>,
 }
 
 /// Counters describing a completed [`Judge::judge`] run.
@@ -219,8 +305,16 @@ pub struct Stats {
     pub judged: usize,
     /// Keys answered from the verdict cache without a request.
     pub cached: usize,
+    /// Keys answered by a batched request rather than one of their own.
+    pub batched: usize,
     /// Keys skipped because both the request and its recovery attempt failed.
     pub failed: usize,
+    /// Keys skipped because the [`Options::token_budget`] was spent.
+    pub skipped: usize,
+    /// Tokens used by the run's requests, as reported by the endpoint.
+    pub tokens: u64,
+    /// Requests retried after a rate limit or server error.
+    pub retries: usize,
     /// Findings emitted.
     pub flagged: usize,
     /// Findings dropped because their confidence was below
@@ -246,12 +340,27 @@ impl Progress for () {}
 /// A configured judge holding the HTTP client, the response schema, and the
 /// verdict cache.
 pub struct Judge {
-    client: Client<OpenAIConfig>,
+    endpoint: Endpoint,
     options: Options,
     schema: serde_json::Value,
+    batch_schema: serde_json::Value,
     cache: Option<cache::Cache>,
 }
 
+/// Where a key's verdict came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    /// The verdict cache.
+    Cache,
+    /// A request for this key alone.
+    Single,
+    /// A batched request.
+    Batch,
+}
+
+/// The outcome of judging one key.
+type Outcome = Result<(Verdict, Source), Error>;
+
 impl Judge {
     /// Creates a judge from `options`.
     ///
@@ -273,16 +382,17 @@ impl Judge {
                 }
             }
         }
-        let api_key = std::env::var(&options.api_key_env).unwrap_or_default();
-        let config = OpenAIConfig::new()
-            .with_api_base(options.base_url.trim_end_matches('/'))
-            .with_api_key(api_key);
+        if options.template.is_some() && options.batch_tokens > 0 {
+            tracing::warn!("batching is disabled with a custom prompt template");
+        }
         let schema = schemars::schema_for!(Verdict).to_value();
+        let batch_schema = schemars::schema_for!(batch::BatchVerdict).to_value();
         let cache = cache::Cache::open(options.cache_dir.as_deref(), options.cache_capacity)?;
         Ok(Self {
-            client: Client::with_config(config),
+            endpoint: Endpoint::new(&options),
             options,
             schema,
+            batch_schema,
             cache,
         })
     }
@@ -292,7 +402,11 @@ impl Judge {
     ///
     /// Up to [`Options::concurrency`] requests are in flight at a time. A key
     /// whose request *and* recovery attempt fail is warned about and skipped;
-    /// the run only aborts when several keys fail consecutively.
+    /// the run only aborts when several keys fail consecutively. Once the
+    /// [`Options::token_budget`] is spent, the keys left are skipped.
+    ///
+    /// With [`Options::batch_tokens`], the keys missing from the cache are
+    /// packed into batched requests first (see [`batch`]).
     ///
     /// # Errors
     ///
@@ -308,59 +422,47 @@ impl Judge {
 
         progress.set_length(keys.len() as u64);
 
-        let mut stats = Stats::default();
-        let mut consecutive_failures = 0usize;
-        // Clamped so an out-of-range threshold cannot silently suppress even
-        // full-confidence findings.
-        let min_confidence = self.options.min_confidence.clamp(0.0, 1.0);
+        let usage = self.endpoint.usage();
+        let mut tally = Tally {
+            stats: Stats::default(),
+            consecutive_failures: 0,
+            // Clamped so an out-of-range threshold cannot silently suppress
+            // even full-confidence findings.
+            min_confidence: self.options.min_confidence.clamp(0.0, 1.0),
+        };
 
-        // Judge keys concurrently while preserving completion-order delivery.
-        let mut verdicts = futures::stream::iter(keys.iter().map(|key| async move {
-            let outcome = self.judge_key(key).await;
-            (key, outcome)
+        let groups: Vec<Vec<&KeyInput<'_>>> = if self.batching() {
+            // Answer cached keys up front so batches are packed with misses
+            // only.
+            let mut misses = Vec::new();
+            for key in keys {
+                match self.cached(key, &self.render(key)) {
+                    Some(verdict) => {
+                        progress.inc(1);
+                        tally.record(key, Ok((verdict, Source::Cache)), sink)?;
+                    }
+                    None => misses.push(key),
+                }
+            }
+            batch::pack(&misses, self.options.batch_tokens)
+        } else {
+            keys.iter().map(|key| vec![key]).collect()
+        };
+
+        // Judge groups concurrently while preserving completion-order delivery.
+        let mut verdicts = futures::stream::iter(groups.into_iter().map(|group| async move {
+            match group.as_slice() {
+                [key] => vec![(*key, self.judge_key(key).await)],
+                _ => self.judge_batch(group).await,
+            }
         }))
         .buffer_unordered(self.options.concurrency.max(1));
 
         // Stream successful findings and stop after repeated endpoint failures.
-        while let Some((key, outcome)) = verdicts.next().await {
-            progress.inc(1);
-            match outcome {
-                Ok((verdict, from_cache)) => {
-                    consecutive_failures = 0;
-                    stats.judged += 1;
-                    if from_cache {
-                        stats.cached += 1;
-                    }
-                    if !verdict.consistent {
-                        for issue in verdict.issues {
-                            // Clamp before comparing: the schema documents the
-                            // 0–1 range but cannot enforce it server-side.
-                            let confidence = issue.confidence.clamp(0.0, 1.0);
-                            if confidence < min_confidence {
-                                stats.suppressed += 1;
-                                continue;
-                            }
-                            stats.flagged += 1;
-                            sink(Finding {
-                                key: key.key.to_string(),
-                                language: issue.language,
-                                problem: issue.problem,
-                                confidence,
-                            });
-                        }
-                    }
-                }
-                Err(error) => {
-                    stats.failed += 1;
-                    consecutive_failures += 1;
-                    tracing::warn!(key = key.key, %error, "llm judge request failed; skipping key");
-                    if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
-                        return Err(Error::EndpointUnusable {
-                            failures: consecutive_failures,
-                            last: error.to_string(),
-                        });
-                    }
-                }
+        while let Some(outcomes) = verdicts.next().await {
+            for (key, outcome) in outcomes {
+                progress.inc(1);
+                tally.record(key, outcome, sink)?;
             }
         }
         drop(verdicts);
@@ -368,41 +470,90 @@ impl Judge {
         // Defer eviction until all cache reads and writes have finished.
         if let Some(cache) = &self.cache {
             cache.enforce_capacity()?;
+            let hits = tally.stats.cached as u64;
+            // The counts only feed the reported hit rate; losing them must not fail a run
+            // whose verdicts are already in hand.
+            if let Err(error) = cache.record_lookups(hits, keys.len() as u64 - hits) {
+                tracing::warn!(%error, "failed to record llm judge cache lookups");
+            }
         }
-        Ok(stats)
+        let usage = self.endpoint.usage().since(usage);
+        tally.stats.tokens = usage.tokens;
+        tally.stats.retries = usage.retries;
+        Ok(tally.stats)
     }
 
-    /// Judges one key using the cache, a strict-schema request, and one
-    /// recovery attempt without structured output. Returns the verdict and
-    /// whether it came from the cache.
-    async fn judge_key(&self, key: &KeyInput<'_>) -> Result<(Verdict, bool), Error> {
-        let rendered = prompt::render(
+    /// Whether keys are packed into batched requests.
+    fn batching(&self) -> bool {
+        self.options.batch_tokens > 0 && self.options.template.is_none()
+    }
+
+    /// Renders the single-key prompt for `key`.
+    fn render(&self, key: &KeyInput<'_>) -> String {
+        prompt::render(
             self.options
                 .template
                 .as_deref()
                 .unwrap_or(prompt::DEFAULT_TEMPLATE),
             key,
-        );
+        )
+    }
 
-        // The hash covers everything that can change the verdict: the rendered
-        // prompt (template + key + all language strings) and the generation
-        // configuration. The endpoint URL is included because the same model
-        // name can resolve to different weights/quantizations per server.
-        // `min_confidence` is deliberately excluded: it filters at emit time,
-        // so changing the threshold re-filters cached verdicts for free.
-        let cache_key = self.cache.as_ref().map(|cache| {
-            cache.key(&[
-                &self.options.base_url,
-                &self.options.model,
-                &format!("{:?}", self.options.effort),
-                &format!("{}", self.options.temperature),
-                &rendered,
-            ])
-        });
-        if let (Some(cache), Some(cache_key)) = (&self.cache, &cache_key)
-            && let Some(verdict) = cache.lookup(cache_key)
-        {
-            return Ok((verdict, true));
+    /// The cache key of a verdict obtained with `prompt`, which identifies the
+    /// prompt the verdict was given for.
+    ///
+    /// The hash covers everything that can change the verdict: the prompt
+    /// (template + key + all language strings) and the generation
+    /// configuration. The endpoint URL is included because the same model name
+    /// can resolve to different weights/quantizations per server.
+    /// `min_confidence` is deliberately excluded: it filters at emit time, so
+    /// changing the threshold re-filters cached verdicts for free.
+    fn cache_key(&self, prompt: &[&str]) -> Option<String> {
+        let cache = self.cache.as_ref()?;
+        let effort = format!("{:?}", self.options.effort);
+        let temperature = format!("{}", self.options.temperature);
+        let mut parts = vec![
+            self.options.base_url.as_str(),
+            self.options.model.as_str(),
+            &effort,
+            &temperature,
+        ];
+        parts.extend_from_slice(prompt);
+        Some(cache.key(&parts))
+    }
+
+    /// The cache key of a verdict for `key` from a batched request. It does
+    /// not depend on the other keys of the batch, so the verdict is found
+    /// again however the keys are packed next time.
+    fn batch_cache_key(&self, key: &KeyInput<'_>) -> Option<String> {
+        self.cache_key(&["batch", prompt::BATCH_TEMPLATE, &prompt::render_block(key)])
+    }
+
+    /// A cached verdict for `key`, whose single-key prompt is `rendered`.
+    /// Verdicts of single-key requests are preferred over batched ones, and
+    /// both are used in either mode.
+    fn cached(&self, key: &KeyInput<'_>, rendered: &str) -> Option<Verdict> {
+        let cache = self.cache.as_ref()?;
+        [self.cache_key(&[rendered]), self.batch_cache_key(key)]
+            .into_iter()
+            .flatten()
+            .find_map(|cache_key| cache.lookup(&cache_key))
+    }
+
+    /// Stores `verdict` under `cache_key`, if caching is enabled.
+    fn store(&self, cache_key: Option<String>, verdict: &Verdict) -> Result<(), Error> {
+        if let (Some(cache), Some(cache_key)) = (&self.cache, cache_key) {
+            cache.store(&cache_key, verdict)?;
+        }
+        Ok(())
+    }
+
+    /// Judges one key using the cache, a strict-schema request, and one
+    /// recovery attempt without structured output.
+    async fn judge_key(&self, key: &KeyInput<'_>) -> Outcome {
+        let rendered = self.render(key);
+        if let Some(verdict) = self.cached(key, &rendered) {
+            return Ok((verdict, Source::Cache));
         }
 
         // Strict structured output first; one recovery attempt without it for
@@ -411,6 +562,7 @@ impl Judge {
         // enough to reject `response_format` tends to reject that too.
         let verdict = match self.request(&rendered, true).await {
             Ok(verdict) => verdict,
+            Err(error @ Error::BudgetExhausted { .. }) => return Err(error),
             Err(first_error) => {
                 tracing::debug!(
                     key = key.key,
@@ -421,62 +573,217 @@ impl Judge {
             }
         };
 
-        if let (Some(cache), Some(cache_key)) = (&self.cache, &cache_key) {
-            cache.store(cache_key, &verdict)?;
+        self.store(self.cache_key(&[&rendered]), &verdict)?;
+        Ok((verdict, Source::Single))
+    }
+
+    /// Judges several keys in one strict-schema request. Keys the answer does
+    /// not cover — all of them when the request fails or its answer does not
+    /// parse — are judged one at a time instead.
+    async fn judge_batch<'k, 'a>(
+        &self,
+        keys: Vec<&'k KeyInput<'a>>,
+    ) -> Vec<(&'k KeyInput<'a>, Outcome)> {
+        let rendered = prompt::render_batch(&keys);
+        let schema = ResponseSchema {
+            name: "verdicts",
+            description: "Consistency verdicts for several translation keys",
+            value: &self.batch_schema,
+        };
+        let mut verdicts: std::collections::HashMap<String, Verdict> =
+            match complete::<batch::BatchVerdict>(
+                &self.endpoint,
+                &self.options,
+                &rendered,
+                Some(schema),
+            )
+            .await
+            {
+                Ok(answer) => answer
+                    .verdicts
+                    .into_iter()
+                    .map(|verdict| (verdict.key.clone(), verdict.into()))
+                    .collect(),
+                Err(error) => {
+                    tracing::debug!(
+                        keys = keys.len(),
+                        %error,
+                        "batched request failed; judging its keys one at a time"
+                    );
+                    std::collections::HashMap::new()
+                }
+            };
+
+        let mut outcomes = Vec::with_capacity(keys.len());
+        for key in keys {
+            let outcome = match verdicts.remove(key.key) {
+                Some(verdict) => self
+                    .store(self.batch_cache_key(key), &verdict)
+                    .map(|()| (verdict, Source::Batch)),
+                None => self.judge_key(key).await,
+            };
+            outcomes.push((key, outcome));
         }
-        Ok((verdict, false))
+        outcomes
     }
 
     /// Sends one chat completion and parses the verdict. With `strict`, the
     /// derived JSON schema is enforced server-side; without it, the response is
     /// parsed leniently (the prompt itself already demands JSON).
     async fn request(&self, rendered_prompt: &str, strict: bool) -> Result<Verdict, Error> {
-        use async_openai::types::chat::{
-            ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs, ResponseFormat,
-            ResponseFormatJsonSchema,
-        };
+        let schema = strict.then_some(ResponseSchema {
+            name: "verdict",
+            description: "Consistency verdict for one translation key",
+            value: &self.schema,
+        });
+        complete(&self.endpoint, &self.options, rendered_prompt, schema).await
+    }
+}
 
-        let message = ChatCompletionRequestUserMessageArgs::default()
-            .content(rendered_prompt)
-            .build()?;
-
-        let mut request = CreateChatCompletionRequestArgs::default();
-        request
-            .model(&self.options.model)
-            .temperature(self.options.temperature)
-            .messages(vec![message.into()]);
-        if strict {
-            request.response_format(ResponseFormat::JsonSchema {
-                json_schema: ResponseFormatJsonSchema {
-                    name: "verdict".to_string(),
-                    description: Some("Consistency verdict for one translation key".to_string()),
-                    schema: self.schema.clone(),
-                    strict: Some(true),
-                },
-            });
-            if let Some(effort) = self.options.effort {
-                request.reasoning_effort(effort.to_api());
+/// The running counters of [`Judge::judge`].
+struct Tally {
+    stats: Stats,
+    consecutive_failures: usize,
+    min_confidence: f64,
+}
+
+impl Tally {
+    /// Counts the outcome for `key` and emits its findings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EndpointUnusable`] once several keys in a row failed.
+    fn record(
+        &mut self,
+        key: &KeyInput<'_>,
+        outcome: Outcome,
+        sink: &mut dyn FnMut(Finding),
+    ) -> Result<(), Error> {
+        let (verdict, source) = match outcome {
+            Ok(outcome) => outcome,
+            Err(error @ Error::BudgetExhausted { .. }) => {
+                if self.stats.skipped == 0 {
+                    tracing::warn!(%error, "skipping the keys left");
+                }
+                self.stats.skipped += 1;
+                return Ok(());
+            }
+            Err(error) => {
+                self.stats.failed += 1;
+                self.consecutive_failures += 1;
+                tracing::warn!(key = key.key, %error, "llm judge request failed; skipping key");
+                if self.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                    return Err(Error::EndpointUnusable {
+                        failures: self.consecutive_failures,
+                        last: error.to_string(),
+                    });
+                }
+                return Ok(());
             }
+        };
+        self.consecutive_failures = 0;
+        self.stats.judged += 1;
+        match source {
+            Source::Cache => self.stats.cached += 1,
+            Source::Batch => self.stats.batched += 1,
+            Source::Single => {}
+        }
+        if verdict.consistent {
+            return Ok(());
+        }
+        for issue in verdict.issues {
+            // Clamp before comparing: the schema documents the 0–1 range but
+            // cannot enforce it server-side.
+            let confidence = issue.confidence.clamp(0.0, 1.0);
+            if confidence < self.min_confidence {
+                self.stats.suppressed += 1;
+                continue;
+            }
+            self.stats.flagged += 1;
+            sink(Finding {
+                key: key.key.to_string(),
+                language: issue.language,
+                problem: issue.problem,
+                confidence,
+                suggestion: issue.suggestion,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A JSON schema enforced as strict structured output.
+struct ResponseSchema<'a> {
+    name: &'static str,
+    description: &'static str,
+    value: &'a serde_json::Value,
+}
+
+/// Sends one chat completion and parses its JSON answer. With a `schema`, the
+/// schema and the reasoning effort are sent along; without, the response is
+/// parsed leniently.
+///
+/// The request waits for the rate limits of the endpoint and fails with
+/// [`Error::BudgetExhausted`] once its token budget is spent.
+async fn complete<T: serde::de::DeserializeOwned>(
+    endpoint: &Endpoint,
+    options: &Options,
+    rendered_prompt: &str,
+    schema: Option<ResponseSchema<'_>>,
+) -> Result<T, Error> {
+    use async_openai::types::chat::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs, ResponseFormat,
+        ResponseFormatJsonSchema,
+    };
+
+    let message = ChatCompletionRequestUserMessageArgs::default()
+        .content(rendered_prompt)
+        .build()?;
+
+    let mut request = CreateChatCompletionRequestArgs::default();
+    request
+        .model(&options.model)
+        .temperature(options.temperature)
+        .messages(vec![message.into()]);
+    if let Some(schema) = schema {
+        request.response_format(ResponseFormat::JsonSchema {
+            json_schema: ResponseFormatJsonSchema {
+                name: schema.name.to_string(),
+                description: Some(schema.description.to_string()),
+                schema: schema.value.clone(),
+                strict: Some(true),
+            },
+        });
+        if let Some(effort) = options.effort {
+            request.reasoning_effort(effort.to_api());
         }
-        let request = request.build()?;
-
-        let response = self.client.chat().create(request).await?;
-        let content = response
-            .choices
-            .first()
-            .and_then(|choice| choice.message.content.as_deref())
-            .unwrap_or_default();
-        parse_verdict(content).map_err(|error| {
-            async_openai::error::OpenAIError::JSONDeserialize(error, content.to_string()).into()
-        })
     }
+    let request = request.build()?;
+
+    let estimate = endpoint.acquire(rendered_prompt).await?;
+    let response = match endpoint.client.chat().create(request).await {
+        Ok(response) => response,
+        Err(error) => {
+            endpoint.release(estimate);
+            return Err(error.into());
+        }
+    };
+    endpoint.record(estimate, response.usage.as_ref()).await;
+    let content = response
+        .choices
+        .first()
+        .and_then(|choice| choice.message.content.as_deref())
+        .unwrap_or_default();
+    parse_json(content).map_err(|error| {
+        async_openai::error::OpenAIError::JSONDeserialize(error, content.to_string()).into()
+    })
 }
 
-/// Parses a verdict from model output, tolerating surrounding prose or a
+/// Parses a JSON answer from model output, tolerating surrounding prose or a
 /// ```` ```json ```` fence by falling back to the first balanced `{…}` block.
-fn parse_verdict(content: &str) -> Result<Verdict, serde_json::Error> {
+fn parse_json<T: serde::de::DeserializeOwned>(content: &str) -> Result<T, serde_json::Error> {
     match serde_json::from_str(content) {
-        Ok(verdict) => Ok(verdict),
+        Ok(value) => Ok(value),
         Err(error) => match first_json_object(content) {
             Some(block) => serde_json::from_str(block),
             None => Err(error),
@@ -511,13 +818,16 @@ fn first_json_object(text: &str) -> Option<&str> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Verdict, first_json_object, parse_verdict};
+    use super::{Finding, Judge, KeyInput, LanguageText, Options, Stats, Verdict};
+    use super::{first_json_object, parse_json};
     use color_eyre::eyre::{self, OptionExt};
+    use mock_llm::{MockServer, Rule};
+    use std::time::Duration;
 
     /// A bare structured response parses directly.
     #[test_util::test]
     fn parses_a_bare_verdict() {
-        let verdict: Verdict = parse_verdict(r#"{"consistent": true, "issues": []}"#)?;
+        let verdict: Verdict = parse_json(r#"{"consistent": true, "issues": []}"#)?;
         assert!(verdict.consistent);
         assert!(verdict.issues.is_empty());
     }
@@ -528,13 +838,14 @@ mod tests {
     fn parses_a_fenced_verdict() {
         let content = "Here is my verdict:\n```json\n{\"consistent\": false, \
                        \"issues\": [{\"language\": \"de\", \"problem\": \"says {x}\"}]}\n```";
-        let verdict = parse_verdict(content)?;
+        let verdict: Verdict = parse_json(content)?;
         assert!(!verdict.consistent);
         assert_eq!(verdict.issues[0].language, "de");
         // Braces inside JSON strings do not affect balanced-block extraction.
         assert_eq!(verdict.issues[0].problem, "says {x}");
         // Missing confidence defaults to 1.0 so the finding is retained.
         assert!((verdict.issues[0].confidence - 1.0).abs() < f64::EPSILON);
+        assert_eq!(verdict.issues[0].suggestion, None);
     }
 
     /// A template without the placeholders would render one identical prompt
@@ -558,10 +869,30 @@ mod tests {
     fn parses_an_explicit_confidence() {
         let content = r#"{"consistent": false, "issues": [{"language": "fr",
             "problem": "different action", "confidence": 0.4}]}"#;
-        let verdict = parse_verdict(content)?;
+        let verdict: Verdict = parse_json(content)?;
         assert!((verdict.issues[0].confidence - 0.4).abs() < f64::EPSILON);
     }
 
+    /// The content hash ignores language order but not the strings.
+    #[test_util::test]
+    fn hashes_content_independent_of_order() {
+        let text = |language, text| crate::LanguageText { language, text };
+        let key = |languages| crate::KeyInput {
+            key: "actions.save",
+            languages,
+        };
+        let hash = key(vec![text("en", "Save"), text("fr", "Enregistrer")]).content_hash();
+        assert_eq!(hash.len(), 16);
+        assert_eq!(
+            key(vec![text("fr", "Enregistrer"), text("en", "Save")]).content_hash(),
+            hash
+        );
+        assert_ne!(
+            key(vec![text("en", "Save"), text("fr", "Supprimer")]).content_hash(),
+            hash
+        );
+    }
+
     /// Balanced-object extraction ignores braces inside JSON strings.
     #[test_util::test]
     fn finds_balanced_object_with_braces_in_strings() {
@@ -590,5 +921,207 @@ mod tests {
             .filter_map(serde_json::Value::as_str)
             .collect();
         assert!(required.contains(&"confidence"), "required: {required:?}");
+        assert!(required.contains(&"suggestion"), "required: {required:?}");
+    }
+
+    /// Options for judging against `server`, without a cache.
+    fn mock_options(server: &MockServer) -> Options {
+        Options {
+            base_url: server.base_url(),
+            cache_capacity: 0,
+            ..Options::default()
+        }
+    }
+
+    /// A key whose `fr` text is `fr`.
+    fn key(key: &'static str, fr: &'static str) -> KeyInput<'static> {
+        KeyInput {
+            key,
+            languages: vec![
+                LanguageText {
+                    language: "en",
+                    text: "Save",
+                },
+                LanguageText {
+                    language: "fr",
+                    text: fr,
+                },
+            ],
+        }
+    }
+
+    /// A verdict flagging `fr`.
+    fn drift() -> serde_json::Value {
+        serde_json::json!({"consistent": false, "issues": [{
+            "language": "fr", "problem": "different action",
+            "confidence": 0.9, "suggestion": "Enregistrer",
+        }]})
+    }
+
+    /// Judges `keys` and collects the findings.
+    async fn run(
+        judge: &Judge,
+        keys: &[KeyInput<'_>],
+    ) -> Result<(Stats, Vec<Finding>), crate::Error> {
+        let mut findings = Vec::new();
+        let stats = judge
+            .judge(keys, &(), &mut |finding| findings.push(finding))
+            .await?;
+        Ok((stats, findings))
+    }
+
+    /// An unparsable strict answer is recovered by one request without the
+    /// schema or effort, whose fenced answer still parses.
+    #[test_util::test]
+    async fn recovers_from_an_unparsable_strict_answer() {
+        let server = MockServer::start(vec![
+            Rule::raw("I think {").with_strict(true),
+            Rule::raw(format!("```json\n{}\n```", drift())).with_strict(false),
+        ])
+        .await?;
+        let judge = Judge::new(mock_options(&server))?;
+        let (stats, findings) = run(&judge, &[key("actions.save", "Supprimer")]).await?;
+
+        assert_eq!((stats.judged, stats.failed, stats.flagged), (1, 0, 1));
+        assert_eq!(
+            findings
+                .first()
+                .and_then(|finding| finding.suggestion.as_deref()),
+            Some("Enregistrer")
+        );
+        let requests = server.requests();
+        let strict: Vec<bool> = requests.iter().map(|request| request.strict).collect();
+        assert_eq!(strict, vec![true, false]);
+        assert!(
+            requests
+                .last()
+                .is_some_and(|request| request.body.get("reasoning_effort").is_none())
+        );
+    }
+
+    /// A second run answers every key from the cache without requests.
+    #[test_util::test]
+    async fn answers_repeated_runs_from_the_cache() {
+        let server = MockServer::start(vec![
+            Rule::json(drift()).for_key("actions.save"),
+            Rule::json(serde_json::json!({"consistent": true, "issues": []})),
+        ])
+        .await?;
+        let cache = tempfile::tempdir()?;
+        let options = Options {
+            cache_dir: Some(cache.path().to_path_buf()),
+            cache_capacity: 10,
+            ..mock_options(&server)
+        };
+        let keys = [
+            key("actions.save", "Supprimer"),
+            key("actions.keep", "Garder"),
+        ];
+
+        let (first, _) = run(&Judge::new(options.clone())?, &keys).await?;
+        let (second, findings) = run(&Judge::new(options)?, &keys).await?;
+        assert_eq!((first.cached, second.cached), (0, 2));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    /// No more than [`Options::concurrency`] requests are in flight.
+    #[test_util::test]
+    async fn limits_requests_in_flight() {
+        let server = MockServer::start(vec![
+            Rule::json(serde_json::json!({"consistent": true, "issues": []}))
+                .with_delay(Duration::from_millis(30)),
+        ])
+        .await?;
+        let judge = Judge::new(Options {
+            concurrency: 2,
+            ..mock_options(&server)
+        })?;
+        let keys = ["a", "b", "c", "d", "e", "f"].map(|name| key(name, "Enregistrer"));
+        let (stats, _) = run(&judge, &keys).await?;
+        assert_eq!(stats.judged, 6);
+        assert_eq!(server.max_in_flight(), 2);
+    }
+
+    /// Keys still rate limited after the client's own retries are skipped
+    /// until the endpoint counts as unusable.
+    #[test_util::test]
+    async fn aborts_after_consecutive_rate_limits() {
+        // `Retry-After: 0` keeps the client's retries immediate.
+        let server = MockServer::start(vec![Rule::rate_limited(Some(0))]).await?;
+        let judge = Judge::new(Options {
+            concurrency: 1,
+            ..mock_options(&server)
+        })?;
+        let keys = ["a", "b", "c", "d", "e", "f"].map(|name| key(name, "Enregistrer"));
+        match run(&judge, &keys).await {
+            Err(crate::Error::EndpointUnusable { failures, .. }) => assert_eq!(failures, 5),
+            other => eyre::bail!("expected the endpoint to be unusable, got {other:?}"),
+        }
+    }
+
+    /// A key the batch answer leaves out is judged again on its own.
+    #[test_util::test]
+    async fn judges_keys_left_out_of_a_batch_alone() {
+        let mut batch = drift();
+        batch["key"] = serde_json::json!("actions.save");
+        let server = MockServer::start(vec![
+            Rule::json(serde_json::json!({"verdicts": [batch]})).containing("\"verdicts\""),
+            Rule::json(serde_json::json!({"consistent": true, "issues": []}))
+                .for_key("actions.keep"),
+        ])
+        .await?;
+        let judge = Judge::new(Options {
+            batch_tokens: 10_000,
+            ..mock_options(&server)
+        })?;
+        let keys = [
+            key("actions.save", "Supprimer"),
+            key("actions.keep", "Garder"),
+        ];
+        let (stats, findings) = run(&judge, &keys).await?;
+
+        assert_eq!((stats.judged, stats.batched, stats.flagged), (2, 1, 1));
+        assert_eq!(
+            findings.first().map(|finding| finding.key.as_str()),
+            Some("actions.save")
+        );
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    /// Rate-limited requests are retried as `Retry-After` asks, and the
+    /// summary counts the retries and the tokens used.
+    #[test_util::test]
+    async fn retries_rate_limited_requests() {
+        let server = MockServer::start(vec![
+            Rule::rate_limited(Some(0)).with_times(2),
+            Rule::json(drift()),
+        ])
+        .await?;
+        let judge = Judge::new(mock_options(&server))?;
+        let (stats, findings) = run(&judge, &[key("actions.save", "Supprimer")]).await?;
+
+        assert_eq!((stats.judged, stats.flagged, stats.retries), (1, 1, 2));
+        assert!(stats.tokens > 0, "{stats:?}");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    /// Once the token budget is spent, the keys left are skipped without a
+    /// request instead of failing the run.
+    #[test_util::test]
+    async fn skips_keys_once_the_token_budget_is_spent() {
+        let server = MockServer::start(vec![Rule::json(drift())]).await?;
+        let judge = Judge::new(Options {
+            concurrency: 1,
+            token_budget: Some(1),
+            ..mock_options(&server)
+        })?;
+        let keys = ["a", "b", "c"].map(|name| key(name, "Supprimer"));
+        let (stats, findings) = run(&judge, &keys).await?;
+
+        assert_eq!((stats.judged, stats.failed, stats.skipped), (1, 0, 2));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(server.requests().len(), 1);
     }
 }
@@ -9,19 +9,11 @@
 
 use crate::KeyInput;
 
-/// The default judge prompt template.
-///
-/// Placeholders: `{key}` is replaced with the dotted key path, `{languages}`
-/// with one `code: text` line per language. All other braces are passed through
-/// verbatim, so JSON examples and `{{placeholder}}` samples need no escaping.
-pub const DEFAULT_TEMPLATE: &str = "\
-These are the translations of ONE string of an application, shown to users in \
-their own language:
-
-Translation key: {key}
-
-{languages}
-
+/// The judging rules shared by [`DEFAULT_TEMPLATE`] and [`BATCH_TEMPLATE`], so
+/// both modes report the same kinds of issues.
+macro_rules! rules {
+    () => {
+        "\
 Report a language ONLY if its users are told a genuinely different fact or \
 action than users of the other languages — you must be able to complete the \
 sentence \"users of <lang> are told <A>, everyone else is told <B>\" where A \
@@ -49,12 +41,68 @@ domains, currencies
 If in doubt, or your concern is about HOW something is phrased rather than \
 WHAT the user is told, answer consistent: true.
 
+"
+    };
+}
+
+/// How `confidence` and `suggestion` are meant, shared by both templates.
+macro_rules! fields {
+    () => {
+        "confidence is how certain you are that the difference changes \
+what the user is told: 1.0 only for unmistakable contradictions, lower when the \
+texts could still mean the same thing. suggestion is a corrected xx text that \
+tells users what the other languages do, keeping its placeholders and markup \
+unchanged, or null if you cannot write one."
+    };
+}
+
+/// The default judge prompt template.
+///
+/// Placeholders: `{key}` is replaced with the dotted key path, `{languages}`
+/// with one `code: text` line per language. All other braces are passed through
+/// verbatim, so JSON examples and `{{placeholder}}` samples need no escaping.
+pub const DEFAULT_TEMPLATE: &str = concat!(
+    "\
+These are the translations of ONE string of an application, shown to users in \
+their own language:
+
+Translation key: {key}
+
+{languages}
+
+",
+    rules!(),
+    "\
 Answer with JSON: {\"consistent\": true/false, \"issues\": [{\"language\": \
 \"xx\", \"problem\": \"users of xx are told ... while the others say ...\", \
-\"confidence\": 0.0-1.0}]} — issues empty when consistent. confidence is how \
-certain you are that the difference changes what the user is told: 1.0 only \
-for unmistakable contradictions, lower when the texts could still mean the \
-same thing.";
+\"confidence\": 0.0-1.0, \"suggestion\": \"...\" or null}]} — issues empty \
+when consistent. ",
+    fields!()
+);
+
+/// The prompt judging several keys in one request.
+///
+/// Placeholder: `{keys}` is replaced with one block per key, as rendered by
+/// [`render_batch`]. The rules are those of [`DEFAULT_TEMPLATE`]; only the
+/// framing and the answer format differ.
+pub const BATCH_TEMPLATE: &str = concat!(
+    "\
+Below are several strings of an application. Each block holds the translations \
+of ONE string, shown to users in their own language. Judge every block on its \
+own; the blocks are unrelated.
+
+{keys}
+
+",
+    rules!(),
+    "\
+Answer with JSON: {\"verdicts\": [{\"key\": \"...\", \"consistent\": \
+true/false, \"issues\": [{\"language\": \"xx\", \"problem\": \"users of xx \
+are told ... while the others say ...\", \"confidence\": 0.0-1.0, \
+\"suggestion\": \"...\" or null}]}] — exactly one verdict per block, with the \
+block's translation key, and issues empty when consistent. ",
+    fields!()
+);
 
 /// Renders `template` for one key, substituting the `{key}` and `{languages}`
 /// placeholders.
@@ -64,20 +112,40 @@ same thing.";
 /// translations themselves) passes through untouched.
 #[must_use]
 pub fn render(template: &str, key: &KeyInput<'_>) -> String {
-    let languages = key
-        .languages
+    template
+        .replace("{key}", key.key)
+        .replace("{languages}", &languages(key))
+}
+
+/// The block [`render_batch`] emits for one key.
+#[must_use]
+pub fn render_block(key: &KeyInput<'_>) -> String {
+    format!("Translation key: {}\n{}", key.key, languages(key))
+}
+
+/// Renders [`BATCH_TEMPLATE`] for several keys, one block per key.
+#[must_use]
+pub fn render_batch(keys: &[&KeyInput<'_>]) -> String {
+    let blocks = keys
+        .iter()
+        .map(|key| render_block(key))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    BATCH_TEMPLATE.replace("{keys}", &blocks)
+}
+
+/// One `code: text` line per language of `key`.
+fn languages(key: &KeyInput<'_>) -> String {
+    key.languages
         .iter()
         .map(|language| format!("{}: {}", language.language, language.text))
         .collect::<Vec<_>>()
-        .join("\n");
-    template
-        .replace("{key}", key.key)
-        .replace("{languages}", &languages)
+        .join("\n")
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{DEFAULT_TEMPLATE, render};
+    use super::{DEFAULT_TEMPLATE, render, render_batch};
     use crate::{KeyInput, LanguageText};
 
     #[test_util::test]
@@ -102,4 +170,22 @@ mod tests {
         assert!(rendered.contains("de: {{count}} Dateien speichern"));
         assert!(rendered.contains(r#"{"consistent": true/false"#));
     }
+
+    #[test_util::test]
+    fn renders_one_block_per_batched_key() {
+        let key = |key, text| KeyInput {
+            key,
+            languages: vec![LanguageText {
+                language: "en",
+                text,
+            }],
+        };
+        let (save, close) = (key("actions.save", "Save"), key("actions.close", "Close"));
+        let rendered = render_batch(&[&save, &close]);
+        assert!(rendered.contains(
+            "Translation key: actions.save\nen: Save\n\nTranslation key: actions.close\nen: Close"
+        ));
+        assert!(rendered.contains(r#"{"verdicts": [{"key": "...""#));
+        assert!(!rendered.contains("{keys}"));
+    }
 }
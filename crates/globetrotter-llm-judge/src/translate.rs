@@ -0,0 +1,367 @@
+//! Machine translation of missing languages through the judge's endpoint.
+//!
+//! Each key is translated into all its missing languages in one request
+//! ([`TranslationInput`] → [`Translated`]). Template placeholders such as
+//! `{{count}}` are replaced by numbered `<ph id="0"/>` tags before the text is
+//! sent — the tag form translation models are trained to keep verbatim — and
+//! restored afterwards. A translation that loses, duplicates, or invents a tag
+//! is rejected rather than returned.
+
+use crate::endpoint::Endpoint;
+use crate::{Error, LanguageText, MAX_CONSECUTIVE_FAILURES, Options, Progress, ResponseSchema};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The translation prompt. `{key}`, `{source}`, `{targets}`, and `{text}` are
+/// replaced with the key, the source language code, the target language codes,
+/// and the protected source text.
+pub const TEMPLATE: &str = "\
+Translate one string of an application's user interface from the language \
+`{source}` into each of these languages: {targets}.
+
+Translation key: {key}
+Text: {text}
+
+Write what a professional translator would ship in the product: natural, \
+idiomatic, and in the register usual for software in each language. The key \
+name hints at where the text is shown.
+
+Tags like <ph id=\"0\"/> stand for values inserted at runtime. Keep every tag \
+exactly once and unchanged, moved to wherever the grammar of the language puts \
+it. Keep any other markup as it is.
+
+Answer with JSON: {\"translations\": [{\"language\": \"xx\", \"text\": \"...\"}]} \
+— one entry per requested language.";
+
+/// A key to translate into the languages it lacks.
+#[derive(Debug, Clone)]
+pub struct TranslationInput<'a> {
+    /// The dotted key path, a hint for the model about where the text is shown.
+    pub key: &'a str,
+    /// The source-language text.
+    pub source: LanguageText<'a>,
+    /// The language codes to translate into.
+    pub targets: Vec<&'a str>,
+}
+
+/// The model's answer for one key. This struct *is* the response contract.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Answer {
+    /// One translation per requested language.
+    pub translations: Vec<TargetText>,
+}
+
+/// One translated text in the model's answer.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TargetText {
+    /// The language code.
+    pub language: String,
+    /// The translated text, with placeholders still protected.
+    pub text: String,
+}
+
+/// A translation with its placeholders restored, ready to be written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translated {
+    /// The dotted key path.
+    pub key: String,
+    /// The language code.
+    pub language: String,
+    /// The translated text.
+    pub text: String,
+}
+
+/// Counters describing a completed [`Translator::translate`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Translations returned.
+    pub translated: usize,
+    /// Translations dropped because the model did not keep the placeholders,
+    /// or answered for a language that was not requested.
+    pub rejected: usize,
+    /// Keys skipped because both the request and its recovery attempt failed.
+    pub failed: usize,
+    /// Keys skipped because the [`Options::token_budget`] was spent.
+    pub skipped: usize,
+    /// Tokens used by the run's requests, as reported by the endpoint.
+    pub tokens: u64,
+    /// Requests retried after a rate limit or server error.
+    pub retries: usize,
+}
+
+/// Text whose template placeholders were replaced by numbered tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Protected {
+    /// The text with tags in place of placeholders.
+    pub text: String,
+    /// The replaced placeholders, indexed by tag id.
+    pub placeholders: Vec<String>,
+}
+
+/// Replaces every `{{…}}` (and `{{{…}}}`) expression of `text` by a numbered
+/// `<ph id="N"/>` tag.
+#[must_use]
+pub fn protect(text: &str) -> Protected {
+    let mut protected = String::with_capacity(text.len());
+    let mut placeholders = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest
+            .get(start..)
+            .and_then(|tail| tail.find("}}"))
+            .map(|offset| start + offset + 2)
+        else {
+            break;
+        };
+        // A triple-stash expression ends with one more brace.
+        let end = if rest.get(start..start + 3) == Some("{{{") && rest.get(end..=end) == Some("}") {
+            end + 1
+        } else {
+            end
+        };
+        protected.push_str(rest.get(..start).unwrap_or_default());
+        protected.push_str(&tag(placeholders.len()));
+        placeholders.push(rest.get(start..end).unwrap_or_default().to_string());
+        rest = rest.get(end..).unwrap_or_default();
+    }
+    protected.push_str(rest);
+    Protected {
+        text: protected,
+        placeholders,
+    }
+}
+
+/// The tag standing for the placeholder `id`.
+fn tag(id: usize) -> String {
+    format!("<ph id=\"{id}\"/>")
+}
+
+impl Protected {
+    /// Restores the placeholders in a translation of the protected text.
+    ///
+    /// Returns `None` unless every tag appears exactly once and no other tag
+    /// does.
+    #[must_use]
+    pub fn restore(&self, translation: &str) -> Option<String> {
+        let mut restored = translation.to_string();
+        for (id, placeholder) in self.placeholders.iter().enumerate() {
+            let tag = tag(id);
+            if restored.matches(&tag).count() != 1 {
+                return None;
+            }
+            restored = restored.replacen(&tag, placeholder, 1);
+        }
+        (!restored.contains("<ph id=")).then_some(restored)
+    }
+}
+
+/// Renders [`TEMPLATE`] for one key with its protected text.
+#[must_use]
+pub fn render(input: &TranslationInput<'_>, protected: &Protected) -> String {
+    TEMPLATE
+        .replace("{key}", input.key)
+        .replace("{source}", input.source.language)
+        .replace("{targets}", &input.targets.join(", "))
+        .replace("{text}", &protected.text)
+}
+
+/// A configured translator holding the HTTP client and the response schema.
+pub struct Translator {
+    endpoint: Endpoint,
+    options: Options,
+    schema: serde_json::Value,
+}
+
+impl Translator {
+    /// Creates a translator from the endpoint settings of `options`; the
+    /// judge-only settings (template, confidence, cache) are ignored.
+    #[must_use]
+    pub fn new(options: Options) -> Self {
+        Self {
+            endpoint: Endpoint::new(&options),
+            schema: schemars::schema_for!(Answer).to_value(),
+            options,
+        }
+    }
+
+    /// Translates every key, calling `sink` with each [`Translated`] text as
+    /// its answer arrives (completion order, not input order).
+    ///
+    /// Failures are handled like [`Judge::judge`](crate::Judge::judge): a key
+    /// whose request and recovery attempt fail is warned about and skipped,
+    /// the run aborts when several keys fail consecutively, and the keys left
+    /// once the [`Options::token_budget`] is spent are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EndpointUnusable`] when the endpoint keeps failing.
+    pub async fn translate(
+        &self,
+        keys: &[TranslationInput<'_>],
+        progress: &dyn Progress,
+        sink: &mut dyn FnMut(Translated),
+    ) -> Result<Stats, Error> {
+        use futures::StreamExt;
+
+        progress.set_length(keys.len() as u64);
+
+        let usage = self.endpoint.usage();
+        let mut stats = Stats::default();
+        let mut consecutive_failures = 0usize;
+        let mut answers = futures::stream::iter(keys.iter().map(|key| async move {
+            let protected = protect(key.source.text);
+            let outcome = self.translate_key(key, &protected).await;
+            (key, protected, outcome)
+        }))
+        .buffer_unordered(self.options.concurrency.max(1));
+
+        while let Some((key, protected, outcome)) = answers.next().await {
+            progress.inc(1);
+            match outcome {
+                Ok(answer) => {
+                    consecutive_failures = 0;
+                    for target in answer.translations {
+                        let requested = key.targets.contains(&target.language.as_str());
+                        let Some(text) = protected.restore(&target.text).filter(|_| requested)
+                        else {
+                            tracing::warn!(
+                                key = key.key,
+                                language = target.language,
+                                text = target.text,
+                                "rejected machine translation that does not keep the placeholders"
+                            );
+                            stats.rejected += 1;
+                            continue;
+                        };
+                        stats.translated += 1;
+                        sink(Translated {
+                            key: key.key.to_string(),
+                            language: target.language,
+                            text,
+                        });
+                    }
+                }
+                Err(error @ Error::BudgetExhausted { .. }) => {
+                    if stats.skipped == 0 {
+                        tracing::warn!(%error, "skipping the keys left");
+                    }
+                    stats.skipped += 1;
+                }
+                Err(error) => {
+                    stats.failed += 1;
+                    consecutive_failures += 1;
+                    tracing::warn!(key = key.key, %error, "translation request failed; skipping key");
+                    if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                        return Err(Error::EndpointUnusable {
+                            failures: consecutive_failures,
+                            last: error.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        let usage = self.endpoint.usage().since(usage);
+        stats.tokens = usage.tokens;
+        stats.retries = usage.retries;
+        Ok(stats)
+    }
+
+    /// Translates one key with a strict-schema request and one recovery
+    /// attempt without structured output.
+    async fn translate_key(
+        &self,
+        key: &TranslationInput<'_>,
+        protected: &Protected,
+    ) -> Result<Answer, Error> {
+        let rendered = render(key, protected);
+        let schema = ResponseSchema {
+            name: "translations",
+            description: "Translations of one user interface string",
+            value: &self.schema,
+        };
+        match crate::complete(&self.endpoint, &self.options, &rendered, Some(schema)).await {
+            Ok(answer) => Ok(answer),
+            Err(error @ Error::BudgetExhausted { .. }) => Err(error),
+            Err(first_error) => {
+                tracing::debug!(
+                    key = key.key,
+                    %first_error,
+                    "strict structured output failed; retrying without response schema"
+                );
+                crate::complete(&self.endpoint, &self.options, &rendered, None).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Translated, TranslationInput, Translator, protect};
+    use crate::{LanguageText, Options};
+    use mock_llm::{MockServer, Rule};
+
+    #[test_util::test]
+    fn protects_and_restores_placeholders() {
+        let protected = protect("Hi {{name}}, you have {{{count}}} new {{#if one}}message{{/if}}");
+        assert_eq!(
+            protected.text,
+            r#"Hi <ph id="0"/>, you have <ph id="1"/> new <ph id="2"/>message<ph id="3"/>"#
+        );
+        assert_eq!(
+            protected.restore(
+                r#"<ph id="1"/> nouveaux <ph id="2"/>message<ph id="3"/> pour <ph id="0"/>"#
+            ),
+            Some("{{{count}}} nouveaux {{#if one}}message{{/if}} pour {{name}}".to_string())
+        );
+        // A lost, duplicated, or invented tag rejects the translation.
+        assert_eq!(protected.restore(r#"<ph id="0"/> <ph id="1"/>"#), None);
+        assert_eq!(protect("Hi").restore(r#"Hi <ph id="0"/>"#), None);
+    }
+
+    /// Placeholders survive the round trip; a translation losing one is
+    /// rejected.
+    #[test_util::test]
+    async fn translates_through_a_mock_server() {
+        let server = MockServer::start(vec![Rule::json(serde_json::json!({"translations": [
+            {"language": "fr", "text": "Bonjour <ph id=\"0\"/>"},
+            {"language": "de", "text": "Hallo"},
+        ]}))])
+        .await?;
+        let translator = Translator::new(Options {
+            base_url: server.base_url(),
+            ..Options::default()
+        });
+        let input = TranslationInput {
+            key: "greeting",
+            source: LanguageText {
+                language: "en",
+                text: "Hello {{name}}",
+            },
+            targets: vec!["fr", "de"],
+        };
+        let mut translated = Vec::new();
+        let stats = translator
+            .translate(&[input], &(), &mut |translation| {
+                translated.push(translation);
+            })
+            .await?;
+
+        assert_eq!((stats.translated, stats.rejected, stats.failed), (1, 1, 0));
+        assert_eq!(
+            translated,
+            vec![Translated {
+                key: "greeting".to_string(),
+                language: "fr".to_string(),
+                text: "Bonjour {{name}}".to_string(),
+            }]
+        );
+        assert!(
+            server
+                .requests()
+                .first()
+                .is_some_and(|request| request.prompt.contains(r#"Text: Hello <ph id="0"/>"#))
+        );
+    }
+}
@@ -134,6 +134,24 @@ impl<T> Spanned<T> {
     }
 }
 
+impl Spanned<String> {
+    /// Maps a byte range of the string value to a span in its source file.
+    ///
+    /// The mapping is exact when the source spells the value literally. When
+    /// escape sequences or line-ending normalization make the source and the
+    /// value differ in length, the span of the whole value is returned instead.
+    #[must_use]
+    pub fn sub_span(&self, range: Span) -> Span {
+        if self.span.len() != self.inner.len()
+            || range.end > self.inner.len()
+            || range.start > range.end
+        {
+            return self.span.clone();
+        }
+        self.span.start + range.start..self.span.start + range.end
+    }
+}
+
 impl<T> std::fmt::Display for Spanned<T>
 where
     T: std::fmt::Display,
@@ -1,6 +1,6 @@
 //! JSON representations of translations for one language.
 
-use crate::{Language, TemplateEngine, diagnostics::Spanned};
+use crate::{Language, TemplateEngine, diagnostics::Spanned, pseudo};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
@@ -52,6 +52,10 @@ pub struct Translations {
     pub template_engine: Option<TemplateEngine>,
     /// The language these translations are for.
     pub language: Language,
+    /// The pseudo-locale, such as `en-XA`, when these are pseudo-localized
+    /// translations of `language`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
     /// The translations, keyed by their dotted key path.
     pub translations: IndexMap<String, Translation>,
 }
@@ -78,6 +82,28 @@ impl crate::Translations {
         Ok(translations)
     }
 
+    /// Writes the pseudo-locale `locale` of the `source` language as
+    /// pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON serialization fails or if a source-language
+    /// translation is missing while `strict` is enabled.
+    pub fn write_pseudo_translations_json(
+        &self,
+        source: Language,
+        locale: &str,
+        options: &pseudo::Options,
+        template_engine: Option<TemplateEngine>,
+        strict: bool,
+        writer: impl std::io::Write,
+    ) -> Result<Translations, Error> {
+        let translations =
+            self.pseudo_translations_json(source, locale, options, strict, template_engine)?;
+        serde_json::to_writer_pretty(writer, &translations)?;
+        Ok(translations)
+    }
+
     /// Builds the JSON representation for one language.
     ///
     /// When `strict` is `false`, a missing value is replaced with a descriptive
@@ -123,13 +149,70 @@ impl crate::Translations {
             template_engine,
             translations,
             language,
+            locale: None,
         })
     }
+
+    /// Builds the JSON representation of `locale`, pseudo-localized from the
+    /// `source` language.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a source-language translation is missing while
+    /// `strict` is enabled.
+    pub fn pseudo_translations_json(
+        &self,
+        source: Language,
+        locale: &str,
+        options: &pseudo::Options,
+        strict: bool,
+        template_engine: Option<TemplateEngine>,
+    ) -> Result<Translations, Error> {
+        let mut translations = self.translations_json(source, strict, template_engine)?;
+        for translation in translations.translations.values_mut() {
+            let (Translation::Literal(text) | Translation::Template(text)) = translation;
+            *text = pseudo::pseudolocalize(text, options);
+        }
+        translations.locale = Some(locale.to_string());
+        Ok(translations)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Version;
+    use super::{Translation, Version};
+    use crate::{Language, Translations, pseudo};
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn pseudo_localizes_the_source_language() -> color_eyre::eyre::Result<()> {
+        let raw = concat!(
+            "\n[greeting]\nen = \"Hello {{name}}\"\nde = \"Hallo {{name}}\"\n",
+            "arguments = { name = \"string\" }\n",
+            "\n[save]\nen = \"Save\"\n",
+        );
+        let mut diagnostics = vec![];
+        let translations = Translations::from_str(raw, 0, false, &mut diagnostics)?;
+        let options = pseudo::Options {
+            expansion: None,
+            ..pseudo::Options::default()
+        };
+        let json =
+            translations.pseudo_translations_json(Language::En, "en-XA", &options, true, None)?;
+        sim_assert_eq!(have: json.language, want: Language::En);
+        sim_assert_eq!(have: json.locale.as_deref(), want: Some("en-XA"));
+        sim_assert_eq!(
+            have: json.translations.into_iter().collect::<Vec<_>>(),
+            want: vec![
+                (
+                    "greeting".to_string(),
+                    Translation::Template("[Ħḗŀŀǿ {{name}}]".to_string())
+                ),
+                ("save".to_string(), Translation::Literal("[Şȧṽḗ]".to_string())),
+            ]
+        );
+        Ok(())
+    }
 
     /// The moving `latest` input alias resolves to the only supported schema
     /// while serialization remains pinned to its stable version number.
@@ -974,6 +974,25 @@ impl Language {
             Language::Zu => "Zulu",
         }
     }
+
+    /// Returns `true` if this language is written right to left, such as
+    /// Arabic or Hebrew.
+    #[must_use]
+    pub fn is_right_to_left(&self) -> bool {
+        matches!(
+            self,
+            Language::Ar
+                | Language::Dv
+                | Language::Fa
+                | Language::He
+                | Language::Ks
+                | Language::Ps
+                | Language::Sd
+                | Language::Ug
+                | Language::Ur
+                | Language::Yi
+        )
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,162 @@
+//! Length limits for translations.
+//!
+//! A key may declare a `max_length` for UI surfaces with limited room (buttons,
+//! tabs, push notification titles). Lengths are measured in grapheme clusters
+//! by default, or in terminal display columns for scripts where the visual
+//! width matters more than the character count.
+
+use crate::{Language, diagnostics::Spanned};
+use indexmap::IndexMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The unit a [`MaxLength`] is measured in.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    strum::Display,
+    strum::EnumString,
+    strum::VariantNames,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum LengthUnit {
+    /// User-perceived characters (extended grapheme clusters).
+    #[default]
+    Graphemes,
+    /// Display columns, counting wide (e.g. CJK) characters as two.
+    Width,
+}
+
+impl LengthUnit {
+    /// Measures `text` in this unit.
+    #[must_use]
+    pub fn measure(self, text: &str) -> usize {
+        match self {
+            Self::Graphemes => text.graphemes(true).count(),
+            Self::Width => unicode_width::UnicodeWidthStr::width(text),
+        }
+    }
+
+    /// The plural noun used when describing a length in this unit.
+    #[must_use]
+    pub fn noun(self) -> &'static str {
+        match self {
+            Self::Graphemes => "graphemes",
+            Self::Width => "columns",
+        }
+    }
+}
+
+/// The maximum length declared for a translation key.
+///
+/// Declared either as a single number that applies to every language
+/// (`max_length = 24`) or as a table with an optional `default`, per-language
+/// overrides, and a `unit` (`max_length = { default = 24, de = 30 }`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MaxLength {
+    /// The limit applied to languages without an explicit override.
+    pub default: Option<Spanned<usize>>,
+    /// Per-language limits that take precedence over [`MaxLength::default`].
+    pub languages: IndexMap<Language, Spanned<usize>>,
+    /// The unit lengths are measured in.
+    pub unit: LengthUnit,
+}
+
+impl MaxLength {
+    /// Returns the limit that applies to `language`, if any.
+    #[must_use]
+    pub fn limit(&self, language: Language) -> Option<&Spanned<usize>> {
+        self.languages.get(&language).or(self.default.as_ref())
+    }
+
+    /// Describes the limits for use in generated documentation, e.g.
+    /// `24 graphemes (de: 30)`.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let overrides = self
+            .languages
+            .iter()
+            .map(|(language, limit)| format!("{}: {}", language.code(), limit.as_ref()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match (&self.default, overrides.is_empty()) {
+            (Some(default), true) => format!("{} {}", default.as_ref(), self.unit.noun()),
+            (Some(default), false) => {
+                format!("{} {} ({overrides})", default.as_ref(), self.unit.noun())
+            }
+            (None, _) => format!("{overrides} {}", self.unit.noun()),
+        }
+    }
+}
+
+/// The maximum ratio between the length of a translation and the length of
+/// its source-language text, e.g. `1.5` for "at most 50% longer".
+///
+/// Wraps an `f64` that is known to be finite and positive, so it can be
+/// compared and hashed like the rest of the configuration.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct ExpansionRatio(f64);
+
+impl ExpansionRatio {
+    /// Creates a ratio, returning `None` unless `ratio` is finite and positive.
+    #[must_use]
+    pub fn new(ratio: f64) -> Option<Self> {
+        (ratio.is_finite() && ratio > 0.0).then_some(Self(ratio))
+    }
+
+    /// Returns the ratio as a float.
+    #[must_use]
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl Eq for ExpansionRatio {}
+
+impl std::hash::Hash for ExpansionRatio {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl std::fmt::Display for ExpansionRatio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}×", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LengthUnit, MaxLength};
+    use crate::{Language, diagnostics::Spanned};
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn measures_graphemes_and_width() {
+        // A combining accent and a flag are single graphemes.
+        sim_assert_eq!(have: LengthUnit::Graphemes.measure("e\u{301}🇩🇪"), want: 2);
+        sim_assert_eq!(have: LengthUnit::Graphemes.measure("保存"), want: 2);
+        sim_assert_eq!(have: LengthUnit::Width.measure("保存"), want: 4);
+    }
+
+    #[test_util::test]
+    fn language_limit_overrides_default() {
+        let max_length = MaxLength {
+            default: Some(Spanned::dummy(12)),
+            languages: [(Language::De, Spanned::dummy(16))].into_iter().collect(),
+            unit: LengthUnit::Graphemes,
+        };
+        sim_assert_eq!(have: max_length.limit(Language::De).map(|l| l.inner), want: Some(16));
+        sim_assert_eq!(have: max_length.limit(Language::En).map(|l| l.inner), want: Some(12));
+        sim_assert_eq!(have: max_length.describe(), want: "12 graphemes (de: 16)");
+    }
+}
@@ -12,10 +12,28 @@ pub mod ext;
 pub mod json;
 /// Supported languages.
 pub mod language;
+/// Length limits of translations.
+pub mod length;
 /// Linting of translation files.
 pub mod lint;
+/// Numbers, URLs, e-mail addresses, and currency amounts in translations.
+pub mod literals;
+/// Inline markup in translations.
+pub mod markup;
+/// Naming conventions for translation keys.
+pub mod naming;
+/// Pseudo-localization of source-language text.
+pub mod pseudo;
+/// Formal and informal address.
+pub mod register;
+/// Review status of translations.
+pub mod status;
 /// TOML parsing of translation files.
 pub mod toml;
+/// Locale-specific typographic conventions.
+pub mod typography;
+/// Unicode hygiene: normalization, invisible characters, and bidi controls.
+pub mod unicode;
 /// Validation of translations against a set of options.
 pub mod validation;
 
@@ -157,6 +175,13 @@ pub struct Translation {
     /// declared via an `allow` key in the translation file.
     #[serde(skip)]
     pub allow: std::collections::BTreeSet<lint::AllowEntry>,
+    /// The maximum length of this translation, declared via a `max_length` key.
+    #[serde(skip)]
+    pub max_length: Option<length::MaxLength>,
+    /// The review status of languages of this translation, declared via a
+    /// `status` key.
+    #[serde(skip)]
+    pub status: status::Statuses,
 }
 
 impl std::fmt::Display for Translation {
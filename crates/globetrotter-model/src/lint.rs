@@ -2,7 +2,13 @@
 //!
 //! These checks go beyond what code generation strictly requires: they look for
 //! missing or empty translations, stray whitespace, broken templates,
-//! inconsistent or undeclared template arguments, and duplicated strings.
+//! inconsistent or undeclared template arguments, text over its `max_length` or
+//! expanding beyond the source language, differing or misplaced typography,
+//! unbalanced or inconsistent markup, differing numbers and addresses, Unicode
+//! issues, text in the wrong register, key naming violations, and duplicated
+//! strings. Spelling, unused and missing keys, generated identifiers, stale
+//! translations, and LLM drift are checked elsewhere but share the same
+//! [`crate::lint::LintCode`]s.
 //!
 //! Every diagnostic carries a stable [`crate::lint::LintCode`]; a translation key can
 //! suppress a code by listing its kebab-case name in an `allow` key, e.g.
@@ -10,7 +16,14 @@
 
 use crate::{
     Language, TemplateEngine, Translation, Translations,
-    diagnostics::{DiagnosticExt, FileId, Spanned},
+    diagnostics::{DiagnosticExt, FileId, Span, Spanned},
+    length::ExpansionRatio,
+    literals::{self, Literal, LiteralKind},
+    markup::{self, Attribute, Element, MarkupError},
+    naming::{KeyNaming, Violation},
+    register::{self, Register},
+    typography::{self, Terminal},
+    unicode::{self, IssueKind},
 };
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use handlebars::template::{BlockParam, HelperTemplate, Parameter, Template, TemplateElement};
@@ -65,6 +78,41 @@ pub enum LintCode {
     /// A language of one key tells the user something different than the
     /// others, as judged by an LLM (see `--llm-judge`).
     LlmDrift,
+    /// A translation exceeds the key's declared `max_length`.
+    MaxLength,
+    /// A translation is much longer than its source-language text (see
+    /// `lint.max_expansion` in the configuration).
+    Expansion,
+    /// A word is not in the language's spelling dictionary (see
+    /// `lint.spelling` in the configuration).
+    Spelling,
+    /// Punctuation, quotation marks, or line breaks are inconsistent across
+    /// languages or break a language's typographic conventions.
+    Typography,
+    /// Markup tags are unbalanced, or differ from the other languages of the
+    /// key in name, nesting, or attributes.
+    Markup,
+    /// Numbers, URLs, e-mail addresses, or currency amounts differ between
+    /// the languages of a key.
+    Literal,
+    /// A translation is not NFC-normalized, contains invisible characters or
+    /// stray bidi controls, or mixes scripts within a word.
+    Unicode,
+    /// A key breaks the configured naming rules (see `lint.key_naming` in
+    /// the configuration).
+    KeyNaming,
+    /// Keys or arguments generate the same identifier in a typed output
+    /// target, or an identifier that is not valid there, such as a keyword.
+    Identifier,
+    /// A translation call in source code uses a key that is not defined.
+    MissingKey,
+    /// A translation addresses the reader formally where the language is
+    /// configured to be informal, or the other way around (see
+    /// `lint.register` in the configuration).
+    Register,
+    /// A translation was reviewed against a source-language text that has
+    /// changed since (see `lint.source_lock` in the configuration).
+    Stale,
 }
 
 /// Options controlling how translations are linted.
@@ -80,8 +128,27 @@ pub struct LintOptions<'a> {
     /// Whether to report keys that share an identical translation, and keys
     /// whose languages are identical to each other.
     pub detect_duplicates: bool,
+    /// The language translations are written in first, against which the
+    /// length of other languages is compared.
+    pub source_language: Option<Language>,
+    /// The maximum ratio between a translation's length and the length of
+    /// its source-language text. When `None`, expansion is not checked.
+    pub max_expansion: Option<ExpansionRatio>,
+    /// Naming rules every key must follow. When `None`, keys are not checked.
+    pub key_naming: Option<&'a KeyNaming>,
+    /// The register each language is expected to address the reader in.
+    /// Languages without an entry are not checked.
+    pub registers: &'a BTreeMap<Language, Register>,
+    /// Whether translations are checked against the typographic conventions
+    /// of their language, such as its quotation marks and the spacing of
+    /// French punctuation, beyond the comparison with the reference language.
+    pub typography_conventions: bool,
 }
 
+/// Source texts shorter than this are exempt from the expansion check: short
+/// labels routinely double in length across languages ("OK" → "Aceptar").
+pub const EXPANSION_MIN_SOURCE_LENGTH: usize = 10;
+
 /// An entry in a translation key's `allow` list: a specific [`LintCode`] to
 /// suppress, or the catch-all `all` that suppresses every lint for the key.
 ///
@@ -147,6 +214,65 @@ pub fn handlebars_variables(source: &str) -> Option<BTreeSet<String>> {
     Some(variables)
 }
 
+/// Byte ranges of the literal text of a Handlebars template, outside its
+/// expressions, partials, and comments, in document order.
+///
+/// Uses the same parser as [`handlebars_variables`], so that both agree on
+/// what is a placeholder; the text inside `{{#if}}…{{/if}}` and other blocks is
+/// included. Returns `None` if the template does not compile.
+#[must_use]
+pub fn handlebars_text(source: &str) -> Option<Vec<Span>> {
+    let template = Template::compile(source).ok()?;
+    let mut texts = Vec::new();
+    collect_text(&template.elements, &mut texts);
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    for text in texts.into_iter().filter(|text| !text.is_empty()) {
+        // Text the parser unescaped, as in `\{{`, cannot be located and is
+        // left out.
+        if let Some(start) = locate_text(source, cursor, text) {
+            cursor = start + text.len();
+            spans.push(start..cursor);
+        }
+    }
+    Some(spans)
+}
+
+fn collect_text<'a>(elements: &'a [TemplateElement], texts: &mut Vec<&'a str>) {
+    for element in elements {
+        match element {
+            TemplateElement::RawString(text) => texts.push(text),
+            TemplateElement::HelperBlock(helper) => {
+                for template in [&helper.template, &helper.inverse].into_iter().flatten() {
+                    collect_text(&template.elements, texts);
+                }
+            }
+            TemplateElement::DecoratorBlock(block) | TemplateElement::PartialBlock(block) => {
+                if let Some(template) = &block.template {
+                    collect_text(&template.elements, texts);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Finds `text` in `source` at or after `from` where only template syntax
+/// precedes it, skipping occurrences inside an expression such as the `if` of
+/// `{{#if x}}`.
+fn locate_text(source: &str, from: usize, text: &str) -> Option<usize> {
+    let mut index = from;
+    while let Some(offset) = source.get(index..)?.find(text) {
+        let start = index + offset;
+        let skipped = source.get(from..start)?.trim_end();
+        if skipped.is_empty() || skipped.ends_with("}}") {
+            return Some(start);
+        }
+        index = start + text.chars().next()?.len_utf8();
+    }
+    None
+}
+
 fn collect_elements(
     elements: &[TemplateElement],
     variables: &mut BTreeSet<String>,
@@ -239,10 +365,13 @@ impl Translations {
     /// Checks for missing and empty translations, surrounding whitespace,
     /// templates that fail to compile, placeholders that are inconsistent across
     /// languages, template arguments that are used but not declared (or declared
-    /// but never used), and — when [`LintOptions::detect_duplicates`] is enabled
-    /// — keys that share an identical translation. Existing diagnostics are
-    /// retained. Issues are warnings unless [`LintOptions::strict`] promotes
-    /// them to errors.
+    /// but never used), text over its `max_length` or beyond
+    /// [`LintOptions::max_expansion`], typography, markup, numbers and addresses
+    /// that differ between languages, Unicode issues, the register of address,
+    /// keys that break [`LintOptions::key_naming`], and — when
+    /// [`LintOptions::detect_duplicates`] is enabled — keys or languages that
+    /// share an identical translation. Existing diagnostics are retained. Issues
+    /// are warnings unless [`LintOptions::strict`] promotes them to errors.
     pub fn lint(&self, diagnostics: &mut Vec<Diagnostic<FileId>>, options: &LintOptions<'_>) {
         // Determine the language set against which every key is checked.
         let required: BTreeSet<Language> = options
@@ -276,6 +405,16 @@ impl Translations {
                 options.strict,
                 diagnostics,
             );
+            lint_length(translation, options, diagnostics);
+            lint_typography(translation, options, diagnostics);
+            lint_markup(translation, options, diagnostics);
+            lint_literals(translation, options, diagnostics);
+            lint_unicode(translation, options.strict, diagnostics);
+            lint_register(translation, options, diagnostics);
+        }
+
+        if let Some(naming) = options.key_naming {
+            self.lint_key_naming(naming, options.strict, diagnostics);
         }
 
         // Run catalog-wide duplicate checks only when requested.
@@ -286,6 +425,875 @@ impl Translations {
             lint_duplicates(self, options.strict, diagnostics);
         }
     }
+
+    /// Checks every key against `naming` and appends any violations to
+    /// `diagnostics`.
+    ///
+    /// Runs as part of [`Translations::lint`], and on its own before code
+    /// generation so that naming problems surface before generators reject
+    /// colliding identifiers.
+    pub fn lint_key_naming(
+        &self,
+        naming: &KeyNaming,
+        strict: bool,
+        diagnostics: &mut Vec<Diagnostic<FileId>>,
+    ) {
+        for (key, translation) in &self.0 {
+            if is_allowed(&translation.allow, LintCode::KeyNaming) {
+                continue;
+            }
+            for violation in naming.check(key.as_ref()) {
+                let (message, label) = match violation {
+                    Violation::Case {
+                        segment,
+                        expected,
+                        style,
+                    } => (
+                        format!("key segment `{segment}` is not {style}"),
+                        format!("expected `{expected}`"),
+                    ),
+                    Violation::Depth { depth, max } => (
+                        format!("key is nested {depth} levels deep"),
+                        format!("at most {max} levels are allowed"),
+                    ),
+                    Violation::Reserved { segment } => (
+                        format!("key segment `{segment}` is a reserved word"),
+                        "reserved by `lint.key_naming`".to_string(),
+                    ),
+                    Violation::Character { segment, character } => (
+                        format!("key segment `{segment}` contains `{character}`"),
+                        naming
+                            .allowed_characters
+                            .as_ref()
+                            .map(|allowed| format!("only `{allowed}` are allowed"))
+                            .unwrap_or_default(),
+                    ),
+                };
+                diagnostics.push(
+                    Diagnostic::warning_or_error(strict)
+                        .with_code(LintCode::KeyNaming)
+                        .with_message(message)
+                        .with_labels(vec![
+                            Label::primary(translation.file_id, key.span.clone())
+                                .with_message(format!("in `{}`: {label}", key.as_ref())),
+                        ]),
+                );
+            }
+        }
+    }
+}
+
+/// Reports translations that exceed the key's `max_length`, and translations
+/// that expand beyond [`LintOptions::max_expansion`] relative to the source
+/// language.
+fn lint_length(
+    translation: &Translation,
+    options: &LintOptions<'_>,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    let file_id = translation.file_id;
+    let allow = &translation.allow;
+    let unit = translation
+        .max_length
+        .as_ref()
+        .map(|max_length| max_length.unit)
+        .unwrap_or_default();
+
+    if let Some(max_length) = &translation.max_length {
+        for (language, value) in &translation.language {
+            let Some(limit) = max_length.limit(*language) else {
+                continue;
+            };
+            let length = unit.measure(value.as_ref());
+            if length <= *limit.as_ref() {
+                continue;
+            }
+            emit(
+                diagnostics,
+                allow,
+                LintCode::MaxLength,
+                Diagnostic::warning_or_error(options.strict)
+                    .with_message(format!(
+                        "`{}` translation exceeds the maximum length of {} {}",
+                        language.code(),
+                        limit.as_ref(),
+                        unit.noun()
+                    ))
+                    .with_labels(vec![
+                        Label::primary(file_id, value.span.clone())
+                            .with_message(format!("{length} {} long", unit.noun())),
+                        Label::secondary(file_id, limit.span.clone())
+                            .with_message("limit declared here"),
+                    ]),
+            );
+        }
+    }
+
+    let (Some(source_language), Some(max_expansion)) =
+        (options.source_language, options.max_expansion)
+    else {
+        return;
+    };
+    let Some(source) = translation.language.get(&source_language) else {
+        return;
+    };
+    // Placeholders stand for text of unknown length, so only the text around
+    // them is compared.
+    let source_length = unit.measure(without_expressions(source.as_ref()).trim());
+    if source_length < EXPANSION_MIN_SOURCE_LENGTH {
+        return;
+    }
+    for (language, value) in &translation.language {
+        if *language == source_language {
+            continue;
+        }
+        let length = unit.measure(without_expressions(value.as_ref()).trim());
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "translation lengths are far below 2^52"
+        )]
+        let ratio = length as f64 / source_length as f64;
+        if ratio <= max_expansion.get() {
+            continue;
+        }
+        emit(
+            diagnostics,
+            allow,
+            LintCode::Expansion,
+            Diagnostic::warning_or_error(options.strict)
+                .with_message(format!(
+                    "`{}` translation is {ratio:.1}× as long as the `{}` source (limit {max_expansion})",
+                    language.code(),
+                    source_language.code(),
+                ))
+                .with_labels(vec![
+                    Label::primary(file_id, value.span.clone())
+                        .with_message(format!("{length} {}", unit.noun())),
+                    Label::secondary(file_id, source.span.clone())
+                        .with_message(format!("{source_length} {}", unit.noun())),
+                ]),
+        );
+    }
+}
+
+/// `text` with its template expressions (`{{…}}`) removed.
+fn without_expressions(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for expression in markup::expressions(text) {
+        out.push_str(text.get(cursor..expression.start).unwrap_or_default());
+        cursor = expression.end;
+    }
+    out.push_str(text.get(cursor..).unwrap_or_default());
+    out
+}
+
+/// The language other languages of a key are compared against: the source
+/// language when the key has it, otherwise the key's first language.
+fn reference_language(translation: &Translation, options: &LintOptions<'_>) -> Option<Language> {
+    options
+        .source_language
+        .filter(|language| translation.language.contains_key(language))
+        .or_else(|| translation.language.keys().next().copied())
+}
+
+/// A finding within one translation: the message, the byte range of the
+/// offending text, and the label shown at it.
+type Finding = (String, Span, String);
+
+/// Reports punctuation and line breaks that differ from the reference language
+/// of the key and, if enabled, text that breaks a language's conventions.
+fn lint_typography(
+    translation: &Translation,
+    options: &LintOptions<'_>,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    if is_allowed(&translation.allow, LintCode::Typography) {
+        return;
+    }
+    let reference = reference_language(translation, options).and_then(|language| {
+        let value = translation.language.get(&language)?;
+        Some((language, value.as_ref().as_str()))
+    });
+
+    for (language, value) in &translation.language {
+        let text = value.as_ref();
+        if text.trim().is_empty() {
+            continue;
+        }
+        let mut findings = Vec::new();
+        if let Some((reference, reference_text)) = reference
+            && reference != *language
+            && !reference_text.trim().is_empty()
+        {
+            findings.extend(compare_typography(
+                text,
+                *language,
+                reference_text,
+                reference,
+            ));
+        }
+        if options.typography_conventions {
+            findings.extend(convention_findings(text, *language));
+        }
+
+        for (message, range, label) in findings {
+            diagnostics.push(
+                Diagnostic::warning_or_error(options.strict)
+                    .with_code(LintCode::Typography)
+                    .with_message(message)
+                    .with_labels(vec![
+                        Label::primary(translation.file_id, value.sub_span(range))
+                            .with_message(label),
+                    ]),
+            );
+        }
+    }
+}
+
+/// Compares the final punctuation, ellipsis style, and line breaks of `text`
+/// with the reference translation of the same key.
+fn compare_typography(
+    text: &str,
+    language: Language,
+    reference_text: &str,
+    reference: Language,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let code = language.code();
+    let reference_code = reference.code();
+    let (terminal, range) = typography::terminal(text, language);
+    let (expected, _) = typography::terminal(reference_text, reference);
+    if terminal != expected {
+        findings.push((
+            format!(
+                "`{code}` translation ends with {terminal}, but `{reference_code}` ends with {expected}"
+            ),
+            range,
+            format!("expected {expected}"),
+        ));
+    } else if terminal == Terminal::Ellipsis
+        && text.trim_end().ends_with("...") != reference_text.trim_end().ends_with("...")
+    {
+        let (used, other) = if text.trim_end().ends_with("...") {
+            ("...", "…")
+        } else {
+            ("…", "...")
+        };
+        findings.push((
+            format!(
+                "`{code}` translation writes the ellipsis as `{used}`, but `{reference_code}` uses `{other}`"
+            ),
+            range,
+            format!("use `{other}`"),
+        ));
+    }
+
+    let line_breaks = text.trim_end_matches('\n').matches('\n').count();
+    let expected_line_breaks = reference_text.trim_end_matches('\n').matches('\n').count();
+    if line_breaks != expected_line_breaks {
+        findings.push((
+            format!(
+                "`{code}` translation has {line_breaks} line break(s), but `{reference_code}` has {expected_line_breaks}"
+            ),
+            0..text.len(),
+            "line breaks differ".to_string(),
+        ));
+    }
+    findings
+}
+
+/// Checks `text` against the typographic conventions of `language`.
+fn convention_findings(text: &str, language: Language) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let conventions = typography::conventions(language);
+    let code = language.code();
+
+    let (terminal, range) = typography::terminal(text, language);
+    let accepted = match terminal {
+        Terminal::FullStop => conventions.full_stops,
+        Terminal::QuestionMark => conventions.question_marks,
+        Terminal::ExclamationMark => conventions.exclamation_marks,
+        Terminal::None | Terminal::Ellipsis | Terminal::Colon => &[],
+    };
+    if let Some(found) = text.get(range.clone()).and_then(|mark| mark.chars().next())
+        && let Some(expected) = accepted.first()
+        && !accepted.contains(&found)
+    {
+        findings.push((
+            format!("`{code}` translation should end with `{expected}` rather than `{found}`"),
+            range,
+            format!("use `{expected}`"),
+        ));
+    }
+
+    if let Some((index, mark)) = text.char_indices().find(|(_, mark)| {
+        typography::QUOTATION_MARKS.contains(mark) && !conventions.accepts_quote(*mark)
+    }) {
+        findings.push((
+            format!("`{mark}` is not a `{code}` quotation mark"),
+            index..index + mark.len_utf8(),
+            format!("use {}", conventions.describe_quotes()),
+        ));
+    }
+
+    if conventions.spaced_punctuation {
+        for (range, mark, found) in unspaced_punctuation(text) {
+            let expected = if matches!(mark, ':' | '«' | '»') {
+                "no-break space"
+            } else {
+                "narrow no-break space"
+            };
+            let message = if found {
+                format!("use a {expected} next to `{mark}` in the `{code}` translation")
+            } else {
+                format!("missing {expected} next to `{mark}` in the `{code}` translation")
+            };
+            findings.push((message, range, format!("expected a {expected}")));
+        }
+    }
+
+    if conventions.inverted_marks {
+        let mut prose = mask_non_text(text);
+        for literal in literals::literals(text, language) {
+            if matches!(literal.kind, LiteralKind::Url | LiteralKind::Email) {
+                literals::blank(&mut prose, literal.range);
+            }
+        }
+        for (index, closing, opening) in missing_inverted_marks(&prose) {
+            findings.push((
+                format!("`{code}` translation is missing the opening `{opening}`"),
+                index..index + closing.len_utf8(),
+                format!("`{closing}` has no matching `{opening}`"),
+            ));
+        }
+    }
+    findings
+}
+
+/// Finds the questions and exclamations of `prose` that do not open with `¿`
+/// or `¡`, sentence by sentence, yielding the offset of the closing mark, the
+/// mark, and the missing opening mark.
+///
+/// A run such as `?!` closes one sentence.
+fn missing_inverted_marks(prose: &str) -> Vec<(usize, char, char)> {
+    let mut found = Vec::new();
+    let mut start = 0;
+    let mut previous = None;
+    for (index, mark) in prose.char_indices() {
+        let after = index + mark.len_utf8();
+        match mark {
+            '?' | '!' => {
+                let opening = if mark == '?' { '¿' } else { '¡' };
+                let sentence = prose.get(start..index).unwrap_or_default();
+                if !matches!(previous, Some('?' | '!')) && !sentence.contains(opening) {
+                    found.push((index, mark, opening));
+                }
+                start = after;
+            }
+            '.' | '…'
+                if prose.get(after..).is_none_or(|rest| {
+                    rest.is_empty() || rest.starts_with(char::is_whitespace)
+                }) =>
+            {
+                start = after;
+            }
+            _ => {}
+        }
+        previous = Some(mark);
+    }
+    found
+}
+
+/// Blanks out template syntax and markup tags (`<…>`) so they are not
+/// mistaken for prose, keeping byte offsets intact.
+///
+/// Placeholders are found with [`handlebars_text`], so the text inside block
+/// helpers is kept. A template that does not compile, which the template check
+/// reports, has its `{{…}}` runs blanked as written.
+#[must_use]
+pub fn mask_non_text(text: &str) -> String {
+    let syntax: Vec<Span> = match handlebars_text(text) {
+        Some(spans) => {
+            let mut syntax = Vec::new();
+            let mut cursor = 0;
+            for span in spans {
+                if cursor < span.start {
+                    syntax.push(cursor..span.start);
+                }
+                cursor = span.end;
+            }
+            syntax.push(cursor..text.len());
+            syntax
+        }
+        None => markup::expressions(text),
+    };
+    let mut masked = text.to_string();
+    let tags = markup::tags(text).into_iter().map(|tag| tag.range);
+    for range in syntax.into_iter().chain(tags) {
+        literals::blank(&mut masked, range);
+    }
+    masked
+}
+
+/// Reports numbers, URLs, e-mail addresses, and currency amounts that differ
+/// from the reference language of the key.
+fn lint_literals(
+    translation: &Translation,
+    options: &LintOptions<'_>,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    if is_allowed(&translation.allow, LintCode::Literal) {
+        return;
+    }
+    let Some(reference) = reference_language(translation, options) else {
+        return;
+    };
+    let Some(reference_value) = translation.language.get(&reference) else {
+        return;
+    };
+    let file_id = translation.file_id;
+    let reference_code = reference.code();
+    let expected = literals::literals(reference_value.as_ref(), reference);
+
+    for (language, value) in &translation.language {
+        if *language == reference {
+            continue;
+        }
+        let code = language.code();
+        let found = literals::literals(value.as_ref(), *language);
+        for kind in [
+            LiteralKind::Number,
+            LiteralKind::Currency,
+            LiteralKind::Url,
+            LiteralKind::Email,
+        ] {
+            let (extra, missing) = literal_difference(&found, &expected, kind);
+            if extra.is_empty() && missing.is_empty() {
+                continue;
+            }
+            let written = |text: &Spanned<String>, literal: &Literal| {
+                text.as_ref()
+                    .get(literal.range.clone())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let mut labels: Vec<Label<FileId>> = extra
+                .iter()
+                .map(|literal| {
+                    Label::primary(file_id, value.sub_span(literal.range.clone())).with_message(
+                        format!("`{}` is not in `{reference_code}`", written(value, literal)),
+                    )
+                })
+                .collect();
+            if labels.is_empty() {
+                let missing = missing
+                    .iter()
+                    .map(|literal| format!("`{}`", written(reference_value, literal)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                labels.push(
+                    Label::primary(file_id, value.span.clone())
+                        .with_message(format!("missing {missing}")),
+                );
+            }
+            labels.extend(missing.iter().map(|literal| {
+                Label::secondary(file_id, reference_value.sub_span(literal.range.clone()))
+                    .with_message(format!("`{reference_code}` has this"))
+            }));
+            diagnostics.push(
+                Diagnostic::warning_or_error(options.strict)
+                    .with_code(LintCode::Literal)
+                    .with_message(format!(
+                        "{} in the `{code}` translation differ from `{reference_code}`",
+                        kind.plural()
+                    ))
+                    .with_labels(labels),
+            );
+        }
+    }
+}
+
+/// Reports text that is not NFC-normalized, invisible characters, bidi
+/// controls outside right-to-left languages, and mixed-script words.
+fn lint_unicode(
+    translation: &Translation,
+    strict: bool,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    if is_allowed(&translation.allow, LintCode::Unicode) {
+        return;
+    }
+    for (language, value) in &translation.language {
+        let code = language.code();
+        for issue in unicode::issues(value.as_ref(), *language) {
+            let (message, label, fixable) = match issue.kind {
+                IssueKind::NotNfc => (
+                    format!("`{code}` translation is not NFC-normalized"),
+                    "decomposed characters".to_string(),
+                    true,
+                ),
+                IssueKind::Invisible(c) => (
+                    format!(
+                        "invisible character U+{:04X} {} in the `{code}` translation",
+                        u32::from(c),
+                        unicode::char_name(c)
+                    ),
+                    "invisible character here".to_string(),
+                    true,
+                ),
+                IssueKind::Bidi(c) => (
+                    format!(
+                        "bidirectional control character U+{:04X} {} in the `{code}` translation",
+                        u32::from(c),
+                        unicode::char_name(c)
+                    ),
+                    "changes the display order of the text".to_string(),
+                    true,
+                ),
+                IssueKind::MixedScript(first, other) => (
+                    format!("word mixes {first} and {other} letters in the `{code}` translation"),
+                    "possible look-alike characters".to_string(),
+                    false,
+                ),
+            };
+            let mut diagnostic = Diagnostic::warning_or_error(strict)
+                .with_code(LintCode::Unicode)
+                .with_message(message)
+                .with_labels(vec![
+                    Label::primary(translation.file_id, value.sub_span(issue.range))
+                        .with_message(label),
+                ]);
+            if fixable {
+                diagnostic = diagnostic
+                    .with_notes(vec!["`globetrotter lint --fix` repairs this".to_string()]);
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+/// Reports each translation whose pronouns address the reader in another
+/// register than the one configured for its language.
+fn lint_register(
+    translation: &Translation,
+    options: &LintOptions<'_>,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    if is_allowed(&translation.allow, LintCode::Register) {
+        return;
+    }
+    for (language, value) in &translation.language {
+        let Some(expected) = options.registers.get(language) else {
+            continue;
+        };
+        let labels: Vec<_> = register::markers(value.as_ref(), *language)
+            .into_iter()
+            .filter(|marker| marker.register != *expected)
+            .map(|marker| {
+                Label::primary(translation.file_id, value.sub_span(marker.range))
+                    .with_message(format!("{}", marker.register))
+            })
+            .collect();
+        if labels.is_empty() {
+            continue;
+        }
+        let code = language.code();
+        diagnostics.push(
+            Diagnostic::warning_or_error(options.strict)
+                .with_code(LintCode::Register)
+                .with_message(format!(
+                    "`{code}` translation does not address the reader {expected}ly"
+                ))
+                .with_labels(labels)
+                .with_notes(vec![format!(
+                    "`lint.register` expects {expected} address in `{code}`"
+                )]),
+        );
+    }
+}
+
+/// Returns the literals of `kind` that occur more often in `found` than in
+/// `expected`, and those that occur more often in `expected` than in `found`.
+fn literal_difference<'a>(
+    found: &'a [Literal],
+    expected: &'a [Literal],
+    kind: LiteralKind,
+) -> (Vec<&'a Literal>, Vec<&'a Literal>) {
+    let unmatched = |from: &'a [Literal], other: &'a [Literal]| {
+        let mut remaining: Vec<&str> = other
+            .iter()
+            .filter(|literal| literal.kind == kind)
+            .map(|literal| literal.value.as_str())
+            .collect();
+        from.iter()
+            .filter(|literal| literal.kind == kind)
+            .filter(
+                |literal| match remaining.iter().position(|value| *value == literal.value) {
+                    Some(position) => {
+                        remaining.swap_remove(position);
+                        false
+                    }
+                    None => true,
+                },
+            )
+            .collect::<Vec<_>>()
+    };
+    (unmatched(found, expected), unmatched(expected, found))
+}
+
+/// The markup elements of one language of a key.
+type LanguageMarkup<'a> = (Language, &'a Spanned<String>, Vec<Element>);
+
+/// Reports unbalanced markup tags, and tags or attributes that differ from the
+/// reference language of the key.
+fn lint_markup(
+    translation: &Translation,
+    options: &LintOptions<'_>,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    if is_allowed(&translation.allow, LintCode::Markup) {
+        return;
+    }
+    let file_id = translation.file_id;
+    let mut parsed: Vec<LanguageMarkup<'_>> = Vec::new();
+    for (language, value) in &translation.language {
+        match markup::elements(value.as_ref()) {
+            Ok(elements) => parsed.push((*language, value, elements)),
+            Err(errors) => {
+                for error in errors {
+                    let (message, range, label) = describe_markup_error(&error, *language);
+                    diagnostics.push(
+                        Diagnostic::warning_or_error(options.strict)
+                            .with_code(LintCode::Markup)
+                            .with_message(message)
+                            .with_labels(vec![
+                                Label::primary(file_id, value.sub_span(range)).with_message(label),
+                            ]),
+                    );
+                }
+            }
+        }
+    }
+
+    // Languages whose tags are unbalanced were reported above and are not
+    // compared, and neither is anything when the reference is unbalanced.
+    let reference = reference_language(translation, options);
+    let Some(reference) = parsed
+        .iter()
+        .find(|(language, _, _)| Some(*language) == reference)
+    else {
+        return;
+    };
+    for current in &parsed {
+        if current.0 != reference.0 {
+            compare_markup(file_id, options.strict, current, reference, diagnostics);
+        }
+    }
+}
+
+fn describe_markup_error(error: &MarkupError, language: Language) -> Finding {
+    let code = language.code();
+    match error {
+        MarkupError::Unclosed { name, range } => (
+            format!("unclosed `<{name}>` tag in the `{code}` translation"),
+            range.clone(),
+            format!("`<{name}>` is never closed"),
+        ),
+        MarkupError::UnexpectedClose { name, range } => (
+            format!("unexpected closing tag `</{name}>` in the `{code}` translation"),
+            range.clone(),
+            format!("no matching `<{name}>`"),
+        ),
+        MarkupError::BadlyNested { name, open, range } => (
+            format!("badly nested tags in the `{code}` translation"),
+            range.clone(),
+            format!("`</{name}>` closes before `<{open}>` is closed"),
+        ),
+    }
+}
+
+/// Compares the elements of one language with those of the reference
+/// language, pairing elements of the same name in document order.
+fn compare_markup(
+    file_id: FileId,
+    strict: bool,
+    (language, value, elements): &LanguageMarkup<'_>,
+    (reference, reference_value, reference_elements): &LanguageMarkup<'_>,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    let code = language.code();
+    let reference_code = reference.code();
+    let names: BTreeSet<&str> = elements
+        .iter()
+        .chain(reference_elements.iter())
+        .map(|element| element.name.as_str())
+        .collect();
+    let mut push = |message: String, labels: Vec<Label<FileId>>| {
+        diagnostics.push(
+            Diagnostic::warning_or_error(strict)
+                .with_code(LintCode::Markup)
+                .with_message(message)
+                .with_labels(labels),
+        );
+    };
+    let here = |range: Span| Label::primary(file_id, value.sub_span(range));
+    let there = |range: Span| Label::secondary(file_id, reference_value.sub_span(range));
+
+    for name in names {
+        let ours = elements.iter().filter(|element| element.name == name);
+        let mut theirs = reference_elements
+            .iter()
+            .filter(|element| element.name == name);
+        for element in ours {
+            let Some(expected) = theirs.next() else {
+                push(
+                    format!("extra `<{name}>` tag in the `{code}` translation"),
+                    vec![
+                        here(element.range.clone())
+                            .with_message(format!("not in the `{reference_code}` translation")),
+                    ],
+                );
+                continue;
+            };
+            if element.ancestors != expected.ancestors {
+                push(
+                    format!(
+                        "`<{name}>` tag is nested differently in the `{code}` translation than in `{reference_code}`"
+                    ),
+                    vec![
+                        here(element.range.clone())
+                            .with_message(format!("inside {}", describe_ancestors(element))),
+                        there(expected.range.clone())
+                            .with_message(format!("inside {}", describe_ancestors(expected))),
+                    ],
+                );
+            }
+            for (attribute, ours, theirs) in differing_attributes(element, expected) {
+                let mut labels = vec![match theirs {
+                    Some(theirs) => {
+                        let written = reference_value
+                            .as_ref()
+                            .get(theirs.range.clone())
+                            .unwrap_or(attribute);
+                        here(ours.map_or(element.range.clone(), |ours| ours.range.clone()))
+                            .with_message(format!("`{reference_code}` has `{written}`"))
+                    }
+                    None => here(ours.map_or(element.range.clone(), |ours| ours.range.clone()))
+                        .with_message(format!("`{reference_code}` has no `{attribute}`")),
+                }];
+                if let Some(theirs) = theirs {
+                    labels.push(there(theirs.range.clone()));
+                }
+                push(
+                    format!(
+                        "`{attribute}` attribute of `<{name}>` in the `{code}` translation differs from `{reference_code}`"
+                    ),
+                    labels,
+                );
+            }
+        }
+        for missing in theirs {
+            push(
+                format!("`<{name}>` tag is missing from the `{code}` translation"),
+                vec![
+                    here(0..value.as_ref().len())
+                        .with_message(format!("expected `<{name}>` as in `{reference_code}`")),
+                    there(missing.range.clone())
+                        .with_message(format!("`{reference_code}` has it here")),
+                ],
+            );
+        }
+    }
+}
+
+fn describe_ancestors(element: &Element) -> String {
+    if element.ancestors.is_empty() {
+        "no other tag".to_string()
+    } else {
+        element
+            .ancestors
+            .iter()
+            .map(|name| format!("`<{name}>`"))
+            .collect::<Vec<_>>()
+            .join(" > ")
+    }
+}
+
+/// Finds the non-translatable attributes whose values differ between an
+/// element and its counterpart in the reference language.
+fn differing_attributes<'a>(
+    element: &'a Element,
+    expected: &'a Element,
+) -> Vec<(&'a str, Option<&'a Attribute>, Option<&'a Attribute>)> {
+    let names: BTreeSet<&str> = element
+        .attributes
+        .keys()
+        .chain(expected.attributes.keys())
+        .map(String::as_str)
+        .filter(|name| !markup::TRANSLATABLE_ATTRIBUTES.contains(name))
+        .collect();
+    names
+        .into_iter()
+        .map(|name| {
+            (
+                name,
+                element.attributes.get(name),
+                expected.attributes.get(name),
+            )
+        })
+        .filter(|(_, ours, theirs)| {
+            ours.map(|found| &found.value) != theirs.map(|found| &found.value)
+        })
+        .collect()
+}
+
+/// Finds high punctuation and guillemets that are not set off by a no-break
+/// space, as French typography requires.
+///
+/// Yields the range of the offending space (or of the mark itself when the
+/// space is missing), the mark, and whether a regular space was used instead.
+fn unspaced_punctuation(text: &str) -> Vec<(Span, char, bool)> {
+    const NO_BREAK_SPACES: &[char] = &['\u{a0}', '\u{202f}'];
+    let mut found = Vec::new();
+    for (index, mark) in text.char_indices() {
+        // The space goes before `?!;:»` and after `«`.
+        let (neighbour, neighbour_range) = match mark {
+            '?' | '!' | ';' | ':' | '»' => {
+                let Some(before) = text.get(..index).and_then(|head| head.chars().next_back())
+                else {
+                    continue;
+                };
+                (before, index - before.len_utf8()..index)
+            }
+            '«' => {
+                let after = index + mark.len_utf8();
+                let Some(next) = text.get(after..).and_then(|tail| tail.chars().next()) else {
+                    continue;
+                };
+                (next, after..after + next.len_utf8())
+            }
+            _ => continue,
+        };
+        // Skip doubled marks (`?!`), URLs (`https://`), and template syntax.
+        let in_url = mark == ':'
+            && text
+                .get(index + 1..)
+                .is_some_and(|tail| tail.starts_with('/'));
+        if NO_BREAK_SPACES.contains(&neighbour)
+            || matches!(neighbour, '?' | '!' | '{' | '}')
+            || in_url
+            || neighbour.is_ascii_digit() && mark == ':'
+        {
+            continue;
+        }
+        if neighbour == ' ' {
+            found.push((neighbour_range, mark, true));
+        } else {
+            found.push((index..index + mark.len_utf8(), mark, false));
+        }
+    }
+    found
 }
 
 /// Reports languages within one key that share an identical translation
@@ -602,10 +1610,12 @@ fn lint_duplicates(
 #[cfg(test)]
 mod tests {
     use super::{LintOptions, handlebars_variables};
+    use crate::length::ExpansionRatio;
+    use crate::register::Register;
     use crate::{Language, Translations, diagnostics::Spanned};
     use color_eyre::eyre::{self, OptionExt};
     use similar_asserts::assert_eq as sim_assert_eq;
-    use std::collections::BTreeSet;
+    use std::collections::{BTreeMap, BTreeSet};
 
     fn vars(source: &str) -> Vec<String> {
         handlebars_variables(source)
@@ -653,6 +1663,16 @@ mod tests {
         raw: &str,
         required: &[Language],
         detect_duplicates: bool,
+    ) -> eyre::Result<Vec<(Option<String>, String)>> {
+        lint_with(raw, required, detect_duplicates, None, false)
+    }
+
+    fn lint_with(
+        raw: &str,
+        required: &[Language],
+        detect_duplicates: bool,
+        max_expansion: Option<f64>,
+        typography_conventions: bool,
     ) -> eyre::Result<Vec<(Option<String>, String)>> {
         let mut parse_diagnostics = vec![];
         let translations = Translations::from_str(raw, 0, false, &mut parse_diagnostics)?;
@@ -663,6 +1683,11 @@ mod tests {
             template_engine: None,
             strict: false,
             detect_duplicates,
+            source_language: Some(Language::En),
+            max_expansion: max_expansion.and_then(ExpansionRatio::new),
+            key_naming: None,
+            registers: &BTreeMap::new(),
+            typography_conventions,
         };
         let mut diagnostics = vec![];
         translations.lint(&mut diagnostics, &options);
@@ -849,4 +1874,301 @@ mod tests {
             "{found:?}"
         );
     }
+
+    #[test_util::test]
+    fn flags_translations_over_max_length() {
+        let raw = "\n[save]\nen = \"Save\"\nde = \"Speichern\"\nmax_length = { default = 6, fr = 12 }\nfr = \"Enregistrer\"\n";
+        let msgs = messages(raw, &[])?;
+        sim_assert_eq!(
+            have: msgs,
+            want: vec!["`de` translation exceeds the maximum length of 6 graphemes".to_string()]
+        );
+    }
+
+    fn typography_codes(raw: &str, conventions: bool) -> eyre::Result<Vec<String>> {
+        Ok(lint_with(raw, &[], false, None, conventions)?
+            .into_iter()
+            .filter(|(code, _)| code.as_deref() == Some("typography"))
+            .map(|(_, message)| message)
+            .collect())
+    }
+
+    #[test_util::test]
+    fn flags_inconsistent_terminal_punctuation_and_line_breaks() {
+        let raw = "\n[ask]\nen = \"Delete?\"\nde = \"Löschen\"\nnl = \"Verwijderen…\"\n\n[lines]\nen = \"One\\nTwo\"\nde = \"Eins Zwei\"\n";
+        sim_assert_eq!(
+            have: typography_codes(raw, true)?,
+            want: vec![
+                "`de` translation ends with no punctuation, but `en` ends with a question mark".to_string(),
+                "`nl` translation ends with an ellipsis, but `en` ends with a question mark".to_string(),
+                "`de` translation has 0 line break(s), but `en` has 1".to_string(),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn flags_locale_conventions() {
+        let raw = concat!(
+            "\n[a]\nen = \"Save the “draft”?\"\n",
+            "fr = \"Enregistrer le « brouillon » ?\"\n",
+            "de = \"Den “Entwurf” speichern?\"\n",
+            "es = \"Guardar el borrador?\"\n",
+            "ja = \"下書きを保存しますか?\"\n",
+            "\n[b]\nen = \"Done.\"\nja = \"完了.\"\nfr = \"Terminé\u{202f}!\"\n",
+        );
+        sim_assert_eq!(
+            have: typography_codes(raw, false)?,
+            want: vec![
+                "`fr` translation ends with an exclamation mark, but `en` ends with a full stop".to_string(),
+            ]
+        );
+        let have = typography_codes(raw, true)?;
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                "`”` is not a `de` quotation mark".to_string(),
+                "`es` translation is missing the opening `¿`".to_string(),
+                "use a no-break space next to `«` in the `fr` translation".to_string(),
+                "use a no-break space next to `»` in the `fr` translation".to_string(),
+                "use a narrow no-break space next to `?` in the `fr` translation".to_string(),
+                "`fr` translation ends with an exclamation mark, but `en` ends with a full stop".to_string(),
+                "`ja` translation should end with `。` rather than `.`".to_string(),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn checks_inverted_marks_per_sentence() {
+        let raw = concat!(
+            "\n[a]\nes = \"¿Vienes? ¿Seguro?! Trae pan?\"\n",
+            "\n[b]\nes = \"Hola. Vienes?\"\n",
+            "\n[c]\nes = \"Ve a https://x.es/?q=1 o a <a title='¡Ya!'>ayuda</a>{{#if (eq n '!')}}{{/if}}.\"\n",
+        );
+        sim_assert_eq!(
+            have: typography_codes(raw, true)?,
+            want: vec![
+                "`es` translation is missing the opening `¿`".to_string(),
+                "`es` translation is missing the opening `¿`".to_string(),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn flags_inconsistent_markup() {
+        let raw = concat!(
+            "\n[terms]\n",
+            "en = \"Accept the <a href='/terms'>terms</a> and <b>privacy</b><br/>\"\n",
+            "de = \"Akzeptiere die <a href='/agb' title='AGB'>AGB</a> und <i>Datenschutz</i>\"\n",
+            "fr = \"Accepter les <b><a href='/terms'>conditions</a></b><br/>\"\n",
+            "es = \"Acepta los <0>términos</b></0>\"\n",
+        );
+        let found = lint(raw, &[], false)?;
+        let have: Vec<String> = found
+            .into_iter()
+            .filter(|(code, _)| code.as_deref() == Some("markup"))
+            .map(|(_, message)| message)
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                "unexpected closing tag `</b>` in the `es` translation".to_string(),
+                "`href` attribute of `<a>` in the `de` translation differs from `en`".to_string(),
+                "`<b>` tag is missing from the `de` translation".to_string(),
+                "`<br>` tag is missing from the `de` translation".to_string(),
+                "extra `<i>` tag in the `de` translation".to_string(),
+                "`<a>` tag is nested differently in the `fr` translation than in `en`".to_string(),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn flags_differing_literals() {
+        let raw = concat!(
+            "\n[trial]\n",
+            "en = \"Try it free for 30 days, then $9.99. Questions? help@example.com\"\n",
+            "de = \"30 Tage kostenlos testen, danach $9,99. Fragen? help@example.com\"\n",
+            "fr = \"Essai gratuit de 14 jours, puis 9,99 $. Questions ? aide@example.com\"\n",
+            "\n[ok]\nen = \"Version 2 of 1,000 files\"\nde = \"Version 2 von 1.000 Dateien\"\n",
+            "\n[allowed]\nen = \"24 hours\"\nde = \"1 Tag\"\nallow = [\"literal\"]\n",
+        );
+        let found = lint(raw, &[], false)?;
+        let have: Vec<String> = found
+            .into_iter()
+            .filter(|(code, _)| code.as_deref() == Some("literal"))
+            .map(|(_, message)| message)
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                "numbers in the `fr` translation differ from `en`".to_string(),
+                "e-mail addresses in the `fr` translation differ from `en`".to_string(),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn flags_unicode_issues() {
+        let raw = concat!(
+            "\n[greeting]\n",
+            "en = \"Hello\\u200b P\\u0430ypal\"\n",
+            "de = \"Cafe\\u0301\"\n",
+            "he = \"\\u200fשלום\"\n",
+        );
+        let found = lint(raw, &[], false)?;
+        let have: Vec<String> = found
+            .into_iter()
+            .filter(|(code, _)| code.as_deref() == Some("unicode"))
+            .map(|(_, message)| message)
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                "`de` translation is not NFC-normalized".to_string(),
+                "invisible character U+200B ZERO WIDTH SPACE in the `en` translation".to_string(),
+                "word mixes Latin and Cyrillic letters in the `en` translation".to_string(),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn flags_strings_in_the_wrong_register() {
+        let raw = concat!(
+            "\n[sign_in]\nen = \"Sign in to see your orders\"\n",
+            "de = \"Melde dich an, um deine Bestellungen zu sehen\"\n",
+            "es = \"Inicia sesión para ver tus pedidos\"\n",
+            "fr = \"Connecte-toi pour voir tes commandes\"\n",
+            "\n[saved]\nen = \"Your changes were saved\"\n",
+            "de = \"Ihre Änderungen wurden gespeichert, danke Ihnen\"\n",
+            "es = \"Usted ha guardado los cambios\"\n",
+            "\n[legacy]\nen = \"Thanks\"\nde = \"Danke dir\"\nallow = [\"register\"]\n",
+        );
+        let mut parse_diagnostics = vec![];
+        let translations = Translations::from_str(raw, 0, false, &mut parse_diagnostics)?;
+        let registers = BTreeMap::from([
+            (Language::De, Register::Formal),
+            (Language::Es, Register::Informal),
+        ]);
+        let options = LintOptions {
+            required_languages: &[],
+            template_engine: None,
+            strict: false,
+            detect_duplicates: false,
+            source_language: Some(Language::En),
+            max_expansion: None,
+            key_naming: None,
+            registers: &registers,
+            typography_conventions: false,
+        };
+        let mut diagnostics = vec![];
+        translations.lint(&mut diagnostics, &options);
+        let have: Vec<(String, Vec<String>)> = diagnostics
+            .into_iter()
+            .filter(|diagnostic| diagnostic.code.as_deref() == Some("register"))
+            .map(|diagnostic| {
+                let labels = diagnostic
+                    .labels
+                    .into_iter()
+                    .map(|label| label.message)
+                    .collect();
+                (diagnostic.message, labels)
+            })
+            .collect();
+        // Only the configured languages are checked, and a capitalized `Ihre`
+        // at the start of a sentence is ambiguous.
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                (
+                    "`es` translation does not address the reader informally".to_string(),
+                    vec!["formal".to_string()],
+                ),
+                (
+                    "`de` translation does not address the reader formally".to_string(),
+                    vec!["informal".to_string(), "informal".to_string()],
+                ),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn flags_key_naming_violations() {
+        let raw = concat!(
+            "\n[app.sign_out]\nen = \"Sign out\"\n",
+            "\n[app.signIn]\nen = \"Sign in\"\n",
+            "\n[app.a.b.c]\nen = \"Deep\"\n",
+            "\n[app.type]\nen = \"Type\"\n",
+            "\n[app.legacyKey]\nen = \"Old\"\nallow = [\"key-naming\"]\n",
+        );
+        let mut parse_diagnostics = vec![];
+        let translations = Translations::from_str(raw, 0, false, &mut parse_diagnostics)?;
+        let naming = crate::naming::KeyNaming {
+            case: Some(crate::naming::CaseStyle::Snake),
+            max_depth: Some(3),
+            reserved: vec!["type".to_string()],
+            allowed_characters: crate::naming::CharacterSet::parse("a-z_"),
+        };
+        let mut diagnostics = vec![];
+        translations.lint_key_naming(&naming, false, &mut diagnostics);
+        let have: Vec<(String, String)> = diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                let label = diagnostic
+                    .labels
+                    .first()
+                    .map(|label| label.message.clone())
+                    .unwrap_or_default();
+                (diagnostic.message, label)
+            })
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                (
+                    "key is nested 4 levels deep".to_string(),
+                    "in `app.a.b.c`: at most 3 levels are allowed".to_string(),
+                ),
+                (
+                    "key segment `signIn` contains `I`".to_string(),
+                    "in `app.signIn`: only `a-z_` are allowed".to_string(),
+                ),
+                (
+                    "key segment `signIn` is not snake_case".to_string(),
+                    "in `app.signIn`: expected `sign_in`".to_string(),
+                ),
+                (
+                    "key segment `type` is a reserved word".to_string(),
+                    "in `app.type`: reserved by `lint.key_naming`".to_string(),
+                ),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn flags_expansion_beyond_ratio() {
+        let raw = concat!(
+            "\n[hint]\nen = \"Upload your documents\"\n",
+            "de = \"Laden Sie jetzt bitte alle Ihre erforderlichen Unterlagen hoch\"\n",
+            "fr = \"Téléversez vos documents\"\n",
+            // Short source texts are exempt.
+            "\n[ok]\nen = \"OK\"\nes = \"Aceptar\"\n",
+            // Placeholders are not measured.
+            "\n[signin]\nen = \"Sign in as {{userDisplayName}}\"\n",
+            "de = \"Erneut anmelden als {{userDisplayName}}\"\narguments = [\"userDisplayName\"]\n",
+        );
+        let found = lint_with(raw, &[Language::En], false, Some(1.5), false)?;
+        sim_assert_eq!(
+            have: found,
+            want: vec![
+                (
+                    Some("expansion".to_string()),
+                    "`de` translation is 3.0× as long as the `en` source (limit 1.5×)".to_string()
+                ),
+                (
+                    Some("expansion".to_string()),
+                    "`de` translation is 1.9× as long as the `en` source (limit 1.5×)".to_string()
+                ),
+            ]
+        );
+        assert!(lint_with(raw, &[Language::En], false, None, false)?.is_empty());
+    }
 }
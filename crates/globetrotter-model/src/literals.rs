@@ -0,0 +1,409 @@
+//! Numbers, URLs, e-mail addresses, and currency amounts in translations.
+//!
+//! These literals carry facts that must survive translation unchanged ("30
+//! days" must not become "14 Tage"). They are extracted from each language
+//! and normalized — digits of other scripts become ASCII, and thousands and
+//! decimal separators are read in the conventions of the language — so that
+//! they can be compared across the languages of a key.
+
+use crate::{Language, diagnostics::Span, markup};
+
+/// The kind of a literal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LiteralKind {
+    /// A number, e.g. `30` or `1,5`.
+    Number,
+    /// A URL, e.g. `https://example.com/terms`.
+    Url,
+    /// An e-mail address.
+    Email,
+    /// A number with a currency symbol or code, e.g. `9,99 €`.
+    Currency,
+}
+
+impl LiteralKind {
+    /// Returns the plural noun used in diagnostics, e.g. `"numbers"`.
+    #[must_use]
+    pub fn plural(self) -> &'static str {
+        match self {
+            Self::Number => "numbers",
+            Self::Url => "URLs",
+            Self::Email => "e-mail addresses",
+            Self::Currency => "currency amounts",
+        }
+    }
+}
+
+/// A literal found in a text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Literal {
+    /// The kind of literal.
+    pub kind: LiteralKind,
+    /// The normalized value, e.g. `1234.5` for `1.234,50` or `EUR 9.99` for
+    /// `9,99 €`.
+    pub value: String,
+    /// The byte range of the literal within the text.
+    pub range: Span,
+}
+
+/// Languages that write a decimal point; all others are assumed to write a
+/// decimal comma.
+const DECIMAL_POINT_LANGUAGES: &[Language] = &[
+    Language::Am,
+    Language::Ar,
+    Language::Bn,
+    Language::Cy,
+    Language::En,
+    Language::Ga,
+    Language::Gu,
+    Language::Ha,
+    Language::He,
+    Language::Hi,
+    Language::Ig,
+    Language::Ja,
+    Language::Km,
+    Language::Kn,
+    Language::Ko,
+    Language::Lo,
+    Language::Ml,
+    Language::Mr,
+    Language::Ms,
+    Language::Mt,
+    Language::My,
+    Language::Ne,
+    Language::Pa,
+    Language::Si,
+    Language::Sw,
+    Language::Ta,
+    Language::Te,
+    Language::Th,
+    Language::Tl,
+    Language::Ur,
+    Language::Yo,
+    Language::Zh,
+];
+
+/// Currency symbols and the code they are normalized to.
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("₹", "INR"),
+    ("₽", "RUB"),
+    ("₩", "KRW"),
+    ("₺", "TRY"),
+    ("₴", "UAH"),
+    ("zł", "PLN"),
+    ("Kč", "CZK"),
+    ("$", "$"),
+];
+
+/// Currency codes recognized next to a number.
+const CURRENCY_CODES: &[&str] = &[
+    "AUD", "BRL", "CAD", "CHF", "CNY", "CZK", "DKK", "EUR", "GBP", "HUF", "INR", "JPY", "KRW",
+    "MXN", "NOK", "PLN", "RUB", "SEK", "TRY", "USD",
+];
+
+/// Spaces used to group the digits of a number (`1 000`, `1 000`).
+const GROUP_SPACES: &[char] = &[' ', '\u{a0}', '\u{202f}', '\u{2009}'];
+
+/// Returns the character `language` writes as its decimal separator.
+#[must_use]
+pub fn decimal_separator(language: Language) -> char {
+    if DECIMAL_POINT_LANGUAGES.contains(&language) {
+        '.'
+    } else {
+        ','
+    }
+}
+
+/// Returns the value of a decimal digit in ASCII, Arabic-Indic, Persian,
+/// Devanagari, or full-width form.
+fn digit_value(c: char) -> Option<u32> {
+    ['0', '\u{660}', '\u{6f0}', '\u{966}', '\u{ff10}']
+        .into_iter()
+        .find_map(|zero| {
+            let value = u32::from(c).checked_sub(u32::from(zero))?;
+            (value < 10).then_some(value)
+        })
+}
+
+/// Characters trimmed from the ends of a URL or e-mail address.
+const ENCLOSING: &[char] = &[
+    '.', ',', ';', ':', '!', '?', '(', ')', '[', ']', '"', '\'', '«', '»', '“', '”', '„', '‘', '’',
+    '<', '>',
+];
+
+/// Blanks `range` in `masked` with spaces, keeping byte offsets intact.
+pub(crate) fn blank(masked: &mut String, range: Span) {
+    if let Some(region) = masked.get(range.clone()) {
+        let spaces = " ".repeat(region.len());
+        masked.replace_range(range, &spaces);
+    }
+}
+
+/// Extracts URLs and e-mail addresses from `masked`, blanking them out.
+fn extract_addresses(masked: &mut String) -> Vec<Literal> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    let chunks: Vec<(usize, String)> = masked
+        .split_whitespace()
+        .filter_map(|chunk| {
+            let start = offset + masked.get(offset..)?.find(chunk)?;
+            offset = start + chunk.len();
+            Some((start, chunk.to_string()))
+        })
+        .collect();
+    for (start, chunk) in chunks {
+        let trimmed = chunk.trim_start_matches(ENCLOSING);
+        let start = start + chunk.len() - trimmed.len();
+        let trimmed = trimmed.trim_end_matches(ENCLOSING);
+        let range = start..start + trimmed.len();
+        let kind = if trimmed.contains("://") || trimmed.starts_with("www.") {
+            LiteralKind::Url
+        } else if let Some((local, domain)) = trimmed.split_once('@')
+            && !local.is_empty()
+            && domain.contains('.')
+            && !domain.contains('@')
+        {
+            LiteralKind::Email
+        } else {
+            continue;
+        };
+        let value = match kind {
+            LiteralKind::Url => trimmed.trim_end_matches('/').to_string(),
+            _ => trimmed.to_lowercase(),
+        };
+        found.push(Literal {
+            kind,
+            value,
+            range: range.clone(),
+        });
+        blank(masked, range);
+    }
+    found
+}
+
+/// Finds the end of the number starting at byte `start` of `text`.
+fn number_end(text: &str, start: usize) -> usize {
+    let mut end = start;
+    for (index, c) in text.get(start..).unwrap_or_default().char_indices() {
+        if digit_value(c).is_some() {
+            end = start + index + c.len_utf8();
+            continue;
+        }
+        let rest = text.get(start + index + c.len_utf8()..).unwrap_or_default();
+        let digits_after = rest
+            .chars()
+            .take_while(|c| digit_value(*c).is_some())
+            .count();
+        let joins = match c {
+            '.' | ',' => digits_after > 0,
+            // A space or apostrophe only groups exactly three digits, so that
+            // `3 4` stays two numbers.
+            '\'' | '’' => digits_after == 3,
+            c if GROUP_SPACES.contains(&c) => digits_after == 3,
+            _ => false,
+        };
+        if !joins {
+            break;
+        }
+    }
+    end
+}
+
+/// Normalizes a number written in the conventions of `language`: digits
+/// become ASCII, group separators are dropped, the decimal separator becomes
+/// `.`, and leading zeros of the integer part and trailing zeros of the
+/// fraction are removed.
+#[must_use]
+pub fn normalize_number(raw: &str, language: Language) -> String {
+    let separators: Vec<(usize, char)> = raw
+        .char_indices()
+        .filter(|(_, c)| matches!(c, '.' | ','))
+        .collect();
+    let last = separators.last().copied();
+    let decimal = last.and_then(|(index, separator)| {
+        let count = separators.iter().filter(|(_, c)| *c == separator).count();
+        let mixed = separators.iter().any(|(_, c)| *c != separator);
+        let digits_after = raw
+            .get(index + 1..)
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| digit_value(*c).is_some())
+            .count();
+        let is_decimal = if count > 1 {
+            false
+        } else if mixed || digits_after != 3 {
+            true
+        } else {
+            separator == decimal_separator(language)
+        };
+        is_decimal.then_some(index)
+    });
+
+    let mut integer = String::new();
+    let mut fraction = String::new();
+    for (index, c) in raw.char_indices() {
+        let Some(digit) = digit_value(c).and_then(|digit| char::from_digit(digit, 10)) else {
+            continue;
+        };
+        if decimal.is_some_and(|decimal| index > decimal) {
+            fraction.push(digit);
+        } else {
+            integer.push(digit);
+        }
+    }
+    let integer = integer.trim_start_matches('0');
+    let integer = if integer.is_empty() { "0" } else { integer };
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{fraction}")
+    }
+}
+
+/// Finds a currency symbol or code directly before or after a number,
+/// optionally separated by a space, returning its normalized code and
+/// extending `range` to cover it.
+fn currency(text: &str, range: &mut Span) -> Option<&'static str> {
+    let before = text.get(..range.start).unwrap_or_default();
+    let after = text.get(range.end..).unwrap_or_default();
+    let before_trimmed = before.trim_end_matches(GROUP_SPACES);
+    let after_trimmed = after.trim_start_matches(GROUP_SPACES);
+    let candidates = CURRENCY_SYMBOLS
+        .iter()
+        .copied()
+        .chain(CURRENCY_CODES.iter().map(|code| (*code, *code)));
+    for (symbol, code) in candidates {
+        let word_boundary = |rest: Option<char>| !rest.is_some_and(char::is_alphanumeric);
+        if before_trimmed.ends_with(symbol)
+            && word_boundary(before_trimmed.strip_suffix(symbol)?.chars().next_back())
+        {
+            range.start = before_trimmed.len() - symbol.len();
+            return Some(code);
+        }
+        if after_trimmed.starts_with(symbol)
+            && word_boundary(after_trimmed.strip_prefix(symbol)?.chars().next())
+        {
+            range.end += after.len() - after_trimmed.len() + symbol.len();
+            return Some(code);
+        }
+    }
+    None
+}
+
+/// Extracts the numbers, URLs, e-mail addresses, and currency amounts of a
+/// translation in `language`, in document order.
+///
+/// Template expressions (`{{…}}`) and markup tags are skipped, as are digits
+/// that are part of a word such as `mp3`.
+#[must_use]
+pub fn literals(text: &str, language: Language) -> Vec<Literal> {
+    let mut masked = text.to_string();
+    for expression in markup::expressions(text) {
+        blank(&mut masked, expression);
+    }
+    for tag in markup::tags(text) {
+        blank(&mut masked, tag.range);
+    }
+
+    let mut found = extract_addresses(&mut masked);
+    let mut index = 0;
+    while let Some((offset, c)) = masked
+        .get(index..)
+        .and_then(|tail| tail.char_indices().find(|(_, c)| digit_value(*c).is_some()))
+    {
+        let start = index + offset;
+        let end = number_end(&masked, start);
+        index = end.max(start + c.len_utf8());
+        let in_word = masked
+            .get(..start)
+            .and_then(|head| head.chars().next_back())
+            .is_some_and(char::is_alphabetic);
+        if in_word {
+            continue;
+        }
+        let value = normalize_number(masked.get(start..end).unwrap_or_default(), language);
+        let mut range = start..end;
+        let literal = match currency(&masked, &mut range) {
+            Some(code) => Literal {
+                kind: LiteralKind::Currency,
+                value: format!("{code} {value}"),
+                range,
+            },
+            None => Literal {
+                kind: LiteralKind::Number,
+                value,
+                range,
+            },
+        };
+        found.push(literal);
+    }
+    found.sort_by_key(|literal| literal.range.start);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LiteralKind, literals, normalize_number};
+    use crate::Language;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn normalizes_numbers_per_locale() {
+        let have: Vec<String> = [
+            ("1,234.5", Language::En),
+            ("1.234,50", Language::De),
+            ("1 234,5", Language::Fr),
+            ("1.000", Language::De),
+            ("1,000", Language::En),
+            ("1,5", Language::De),
+            ("0.75", Language::De),
+            ("٣٠", Language::Ar),
+            ("007", Language::En),
+        ]
+        .into_iter()
+        .map(|(raw, language)| normalize_number(raw, language))
+        .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec!["1234.5", "1234.5", "1234.5", "1000", "1000", "1.5", "0.75", "30", "7"]
+        );
+    }
+
+    #[test_util::test]
+    fn extracts_literals() {
+        let text = "Try {{count}} mp3s for 9,99 € in 30 Tagen: <a href=\"https://a.io/x\">Hilfe</a> \
+                    unter https://example.com/help/ oder Support@Example.com (USD 5).";
+        let have: Vec<_> = literals(text, Language::De)
+            .into_iter()
+            .map(|literal| {
+                (
+                    literal.kind,
+                    literal.value,
+                    text.get(literal.range).unwrap_or_default().to_string(),
+                )
+            })
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                (LiteralKind::Currency, "EUR 9.99".to_string(), "9,99 €".to_string()),
+                (LiteralKind::Number, "30".to_string(), "30".to_string()),
+                (
+                    LiteralKind::Url,
+                    "https://example.com/help".to_string(),
+                    "https://example.com/help/".to_string(),
+                ),
+                (
+                    LiteralKind::Email,
+                    "support@example.com".to_string(),
+                    "Support@Example.com".to_string(),
+                ),
+                (LiteralKind::Currency, "USD 5".to_string(), "USD 5".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,432 @@
+//! Inline markup in translations.
+//!
+//! Translations may contain HTML-like tags such as `<b>…</b>`, `<a href="…">`,
+//! or numbered component slots (`<0>…</0>`) that the application replaces at
+//! runtime. This module finds those tags and pairs them into elements so that
+//! their structure can be compared across languages.
+
+use crate::diagnostics::Span;
+use std::collections::BTreeMap;
+
+/// Elements that never have a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Attributes whose values are text for the reader and are expected to be
+/// translated, so they may differ between languages.
+pub const TRANSLATABLE_ATTRIBUTES: &[&str] = &["alt", "aria-label", "placeholder", "title"];
+
+/// Whether a tag opens, closes, or is an element on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagKind {
+    /// An opening tag, e.g. `<b>`.
+    Open,
+    /// A closing tag, e.g. `</b>`.
+    Close,
+    /// A self-closing or void tag, e.g. `<br/>` or `<br>`.
+    SelfClosing,
+}
+
+/// An attribute of a tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attribute {
+    /// The attribute value, or `None` for a bare attribute such as `disabled`.
+    pub value: Option<String>,
+    /// The byte range of the whole attribute within the text.
+    pub range: Span,
+}
+
+/// A single tag found in a text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tag {
+    /// The lower-cased tag name, e.g. `a` or `0`.
+    pub name: String,
+    /// Whether the tag opens, closes, or stands alone.
+    pub kind: TagKind,
+    /// The attributes of an opening or self-closing tag, by lower-cased name.
+    pub attributes: BTreeMap<String, Attribute>,
+    /// The byte range of the tag within the text.
+    pub range: Span,
+}
+
+/// An element: an opening tag and its closing tag, or a self-closing tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Element {
+    /// The lower-cased tag name.
+    pub name: String,
+    /// The attributes of the opening tag.
+    pub attributes: BTreeMap<String, Attribute>,
+    /// The names of the enclosing elements, outermost first.
+    pub ancestors: Vec<String>,
+    /// The byte range of the opening (or self-closing) tag.
+    pub range: Span,
+}
+
+/// A structural problem with the tags of one text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkupError {
+    /// An opening tag is never closed.
+    Unclosed {
+        /// The tag name.
+        name: String,
+        /// The byte range of the opening tag.
+        range: Span,
+    },
+    /// A closing tag has no matching opening tag.
+    UnexpectedClose {
+        /// The tag name.
+        name: String,
+        /// The byte range of the closing tag.
+        range: Span,
+    },
+    /// A closing tag closes an element before one of its children is closed,
+    /// e.g. `<b><i>…</b></i>`.
+    BadlyNested {
+        /// The name of the element being closed.
+        name: String,
+        /// The name of the child element that is still open.
+        open: String,
+        /// The byte range of the closing tag.
+        range: Span,
+    },
+}
+
+/// Returns `true` if `c` may appear in a tag name.
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ':')
+}
+
+/// Parses the attributes between a tag name and its closing `>`.
+///
+/// `offset` is the byte offset of `source` within the translation text.
+fn parse_attributes(source: &str, offset: usize) -> BTreeMap<String, Attribute> {
+    let mut attributes = BTreeMap::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if !is_name_char(c) {
+            chars.next();
+            continue;
+        }
+        let mut end = start;
+        while let Some(&(index, c)) = chars.peek() {
+            if !is_name_char(c) {
+                break;
+            }
+            end = index + c.len_utf8();
+            chars.next();
+        }
+        let name = source.get(start..end).unwrap_or_default().to_lowercase();
+        while chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+            chars.next();
+        }
+        let mut value = None;
+        if chars.peek().is_some_and(|(_, c)| *c == '=') {
+            chars.next();
+            while chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+                chars.next();
+            }
+            let quote = chars
+                .peek()
+                .map(|(_, c)| *c)
+                .filter(|c| matches!(c, '"' | '\''));
+            if quote.is_some() {
+                chars.next();
+            }
+            let value_start = chars.peek().map_or(source.len(), |(index, _)| *index);
+            let mut value_end = value_start;
+            while let Some(&(index, c)) = chars.peek() {
+                if quote.map_or(c.is_whitespace() || c == '/', |quote| c == quote) {
+                    break;
+                }
+                value_end = index + c.len_utf8();
+                chars.next();
+            }
+            end = value_end;
+            if let Some(quote) = quote
+                && chars.next_if(|(_, c)| *c == quote).is_some()
+            {
+                end += quote.len_utf8();
+            }
+            value = Some(
+                source
+                    .get(value_start..value_end)
+                    .unwrap_or_default()
+                    .to_string(),
+            );
+        }
+        attributes.insert(
+            name,
+            Attribute {
+                value,
+                range: offset + start..offset + end,
+            },
+        );
+    }
+    attributes
+}
+
+/// Parses the tag starting at byte `start` of `text`, if there is one.
+fn parse_tag(text: &str, start: usize) -> Option<Tag> {
+    let rest = text.get(start + 1..)?;
+    let (closing, name_start) = match rest.strip_prefix('/') {
+        Some(_) => (true, start + 2),
+        None => (false, start + 1),
+    };
+    let tail = text.get(name_start..)?;
+    if !tail.chars().next().is_some_and(char::is_alphanumeric) {
+        return None;
+    }
+    let name_len = tail.find(|c: char| !is_name_char(c)).unwrap_or(tail.len());
+    let after_name = tail.get(name_len..)?;
+    if !after_name.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+        return None;
+    }
+    let end = after_name.find(['>', '<'])?;
+    if !after_name.get(end..)?.starts_with('>') {
+        return None;
+    }
+    let name = tail.get(..name_len)?.to_lowercase();
+    let inner = after_name.get(..end)?;
+    let self_closing = inner.trim_end().ends_with('/');
+    let kind = if closing {
+        TagKind::Close
+    } else if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+        TagKind::SelfClosing
+    } else {
+        TagKind::Open
+    };
+    let attributes = if closing {
+        BTreeMap::new()
+    } else {
+        parse_attributes(inner.trim_end_matches('/'), name_start + name_len)
+    };
+    Some(Tag {
+        name,
+        kind,
+        attributes,
+        range: start..name_start + name_len + end + 1,
+    })
+}
+
+/// Finds the template expressions in `text`: `{{…}}`, raw `{{{…}}}`, and
+/// comments `{{!-- … --}}`, which may contain `}}`.
+///
+/// An expression that is never closed runs to the end of the text.
+#[must_use]
+pub fn expressions(text: &str) -> Vec<Span> {
+    let mut expressions = Vec::new();
+    let mut index = 0;
+    while let Some(offset) = text.get(index..).and_then(|tail| tail.find("{{")) {
+        let start = index + offset;
+        let tail = text.get(start..).unwrap_or_default();
+        let close = if tail.starts_with("{{!--") {
+            "--}}"
+        } else if tail.starts_with("{{{") {
+            "}}}"
+        } else {
+            "}}"
+        };
+        index = tail
+            .get(2..)
+            .and_then(|inner| inner.find(close))
+            .map_or(text.len(), |end| start + 2 + end + close.len());
+        expressions.push(start..index);
+    }
+    expressions
+}
+
+/// Finds the markup tags in `text`, skipping template expressions (`{{…}}`).
+///
+/// A `<` that does not start a well-formed tag, as in `a < b`, is treated as
+/// text.
+#[must_use]
+pub fn tags(text: &str) -> Vec<Tag> {
+    let expressions = expressions(text);
+    let mut tags = Vec::new();
+    let mut index = 0;
+    while let Some(offset) = text.get(index..).and_then(|tail| tail.find('<')) {
+        let start = index + offset;
+        if let Some(expression) = expressions
+            .iter()
+            .find(|expression| expression.contains(&start))
+        {
+            index = expression.end;
+            continue;
+        }
+        match parse_tag(text, start) {
+            Some(tag) => {
+                index = tag.range.end;
+                tags.push(tag);
+            }
+            None => index = start + 1,
+        }
+    }
+    tags
+}
+
+/// Pairs the tags of `text` into elements, in document order of their
+/// opening tags.
+///
+/// # Errors
+///
+/// Returns every unclosed, unexpected, or badly nested tag.
+pub fn elements(text: &str) -> Result<Vec<Element>, Vec<MarkupError>> {
+    let mut elements = Vec::new();
+    let mut errors = Vec::new();
+    let mut open: Vec<Tag> = Vec::new();
+    let ancestors =
+        |open: &[Tag]| -> Vec<String> { open.iter().map(|tag| tag.name.clone()).collect() };
+
+    for tag in tags(text) {
+        match tag.kind {
+            TagKind::Open => {
+                elements.push(Element {
+                    name: tag.name.clone(),
+                    attributes: tag.attributes.clone(),
+                    ancestors: ancestors(&open),
+                    range: tag.range.clone(),
+                });
+                open.push(tag);
+            }
+            TagKind::SelfClosing => elements.push(Element {
+                name: tag.name,
+                attributes: tag.attributes,
+                ancestors: ancestors(&open),
+                range: tag.range,
+            }),
+            TagKind::Close => match open.iter().rposition(|opened| opened.name == tag.name) {
+                None => errors.push(MarkupError::UnexpectedClose {
+                    name: tag.name,
+                    range: tag.range,
+                }),
+                Some(position) => {
+                    if let Some(child) = open.get(position + 1) {
+                        errors.push(MarkupError::BadlyNested {
+                            name: tag.name,
+                            open: child.name.clone(),
+                            range: tag.range,
+                        });
+                    }
+                    open.truncate(position);
+                }
+            },
+        }
+    }
+    errors.extend(open.into_iter().map(|tag| MarkupError::Unclosed {
+        name: tag.name,
+        range: tag.range,
+    }));
+    if errors.is_empty() {
+        Ok(elements)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MarkupError, TagKind, elements, expressions, tags};
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn finds_tags_and_attributes() {
+        let text = "Read <a href=\"/terms\" title='Terms'>the <0>terms</0></a>{{#if x}}<br>{{/if}} if a < b";
+        let have: Vec<_> = tags(text)
+            .into_iter()
+            .map(|tag| {
+                let attributes: Vec<_> = tag
+                    .attributes
+                    .iter()
+                    .map(|(name, attribute)| {
+                        (
+                            name.clone(),
+                            attribute.value.clone(),
+                            text.get(attribute.range.clone()).map(str::to_string),
+                        )
+                    })
+                    .collect();
+                (
+                    tag.name,
+                    tag.kind,
+                    text.get(tag.range).map(str::to_string),
+                    attributes,
+                )
+            })
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                (
+                    "a".to_string(),
+                    TagKind::Open,
+                    Some("<a href=\"/terms\" title='Terms'>".to_string()),
+                    vec![
+                        (
+                            "href".to_string(),
+                            Some("/terms".to_string()),
+                            Some("href=\"/terms\"".to_string()),
+                        ),
+                        (
+                            "title".to_string(),
+                            Some("Terms".to_string()),
+                            Some("title='Terms'".to_string()),
+                        ),
+                    ],
+                ),
+                ("0".to_string(), TagKind::Open, Some("<0>".to_string()), vec![]),
+                ("0".to_string(), TagKind::Close, Some("</0>".to_string()), vec![]),
+                ("a".to_string(), TagKind::Close, Some("</a>".to_string()), vec![]),
+                ("br".to_string(), TagKind::SelfClosing, Some("<br>".to_string()), vec![]),
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn finds_template_expressions() {
+        let text = "{{name}} has {{{html}}}{{!-- a }} b --}} and {{#if x}}<b>{{/if}} {{oops";
+        let have: Vec<_> = expressions(text)
+            .into_iter()
+            .filter_map(|range| text.get(range))
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                "{{name}}",
+                "{{{html}}}",
+                "{{!-- a }} b --}}",
+                "{{#if x}}",
+                "{{/if}}",
+                "{{oops",
+            ]
+        );
+    }
+
+    #[test_util::test]
+    fn reports_structural_errors() {
+        let have = elements("<b><i>x</b></i></u><s>");
+        sim_assert_eq!(
+            have: have,
+            want: Err(vec![
+                MarkupError::BadlyNested {
+                    name: "b".to_string(),
+                    open: "i".to_string(),
+                    range: 7..11,
+                },
+                MarkupError::UnexpectedClose {
+                    name: "i".to_string(),
+                    range: 11..15,
+                },
+                MarkupError::UnexpectedClose {
+                    name: "u".to_string(),
+                    range: 15..19,
+                },
+                MarkupError::Unclosed {
+                    name: "s".to_string(),
+                    range: 19..22,
+                },
+            ])
+        );
+    }
+}
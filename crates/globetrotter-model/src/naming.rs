@@ -0,0 +1,296 @@
+//! Naming conventions for translation keys.
+//!
+//! Each dotted segment of a key (`app`, `sign_out` in `app.sign_out`) can be
+//! checked against a case style, a set of allowed characters, and a list of
+//! reserved words, and the number of segments can be limited.
+
+/// The case style every key segment is written in.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+pub enum CaseStyle {
+    /// `sign_out`.
+    #[serde(rename = "snake_case")]
+    #[strum(serialize = "snake_case")]
+    Snake,
+    /// `SIGN_OUT`.
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    #[strum(serialize = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnake,
+    /// `sign-out`.
+    #[serde(rename = "kebab-case")]
+    #[strum(serialize = "kebab-case")]
+    Kebab,
+    /// `signOut`.
+    #[serde(rename = "camelCase")]
+    #[strum(serialize = "camelCase")]
+    Camel,
+    /// `SignOut`.
+    #[serde(rename = "PascalCase")]
+    #[strum(serialize = "PascalCase")]
+    Pascal,
+}
+
+/// Splits a segment into lower-cased words at `_`, `-`, spaces, and
+/// lower-to-upper case changes.
+fn words(segment: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in segment.chars() {
+        if matches!(c, '_' | '-' | ' ' | '.') {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Upper-cases the first character of `word`.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+impl CaseStyle {
+    /// Returns `true` if `segment` is written in this case style.
+    ///
+    /// Purely numeric segments, such as `404`, match every style.
+    #[must_use]
+    pub fn matches(self, segment: &str) -> bool {
+        if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+            return true;
+        }
+        self.convert(segment) == segment
+    }
+
+    /// Converts `segment` to this case style, e.g. `signOut` to `sign_out`.
+    #[must_use]
+    pub fn convert(self, segment: &str) -> String {
+        let words = words(segment);
+        match self {
+            Self::Snake => words.join("_"),
+            Self::ScreamingSnake => words.join("_").to_uppercase(),
+            Self::Kebab => words.join("-"),
+            Self::Camel => {
+                let mut words = words.iter();
+                let first = words.next().cloned().unwrap_or_default();
+                std::iter::once(first)
+                    .chain(words.map(|word| capitalize(word)))
+                    .collect()
+            }
+            Self::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+        }
+    }
+}
+
+/// A set of characters written like a regular expression character class
+/// without brackets, e.g. `a-z0-9_`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CharacterSet {
+    ranges: Vec<(char, char)>,
+    spec: String,
+}
+
+impl CharacterSet {
+    /// Parses a character set such as `a-z0-9_`. A `-` at the start or end is
+    /// a literal hyphen.
+    ///
+    /// Returns `None` for an empty set or a range whose start is after its
+    /// end.
+    #[must_use]
+    pub fn parse(spec: &str) -> Option<Self> {
+        let chars: Vec<char> = spec.chars().collect();
+        let mut ranges = Vec::new();
+        let mut index = 0;
+        while let Some(&start) = chars.get(index) {
+            if let (Some('-'), Some(&end)) = (chars.get(index + 1), chars.get(index + 2)) {
+                if start > end {
+                    return None;
+                }
+                ranges.push((start, end));
+                index += 3;
+            } else {
+                ranges.push((start, start));
+                index += 1;
+            }
+        }
+        if ranges.is_empty() {
+            return None;
+        }
+        Some(Self {
+            ranges,
+            spec: spec.to_string(),
+        })
+    }
+
+    /// Returns `true` if `c` is in the set.
+    #[must_use]
+    pub fn contains(&self, c: char) -> bool {
+        self.ranges
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&c))
+    }
+}
+
+impl std::fmt::Display for CharacterSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.spec)
+    }
+}
+
+/// Naming rules for translation keys. Every rule is optional.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct KeyNaming {
+    /// The case style of every key segment.
+    pub case: Option<CaseStyle>,
+    /// The maximum number of dotted segments in a key.
+    pub max_depth: Option<usize>,
+    /// Words no key segment may be, compared case-insensitively.
+    pub reserved: Vec<String>,
+    /// The characters key segments may contain.
+    pub allowed_characters: Option<CharacterSet>,
+}
+
+/// A violation of the naming rules by one key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// A segment is not written in the configured case style.
+    Case {
+        /// The offending segment.
+        segment: String,
+        /// The segment converted to the configured style.
+        expected: String,
+        /// The configured case style.
+        style: CaseStyle,
+    },
+    /// The key has more segments than allowed.
+    Depth {
+        /// The number of segments.
+        depth: usize,
+        /// The maximum number of segments.
+        max: usize,
+    },
+    /// A segment is a reserved word.
+    Reserved {
+        /// The offending segment.
+        segment: String,
+    },
+    /// A segment contains a character outside the allowed set.
+    Character {
+        /// The offending segment.
+        segment: String,
+        /// The first disallowed character.
+        character: char,
+    },
+}
+
+impl KeyNaming {
+    /// Checks `key` against the naming rules.
+    #[must_use]
+    pub fn check(&self, key: &str) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let segments: Vec<&str> = key.split('.').collect();
+        if let Some(max) = self.max_depth
+            && segments.len() > max
+        {
+            violations.push(Violation::Depth {
+                depth: segments.len(),
+                max,
+            });
+        }
+        for segment in segments {
+            if self
+                .reserved
+                .iter()
+                .any(|reserved| reserved.eq_ignore_ascii_case(segment))
+            {
+                violations.push(Violation::Reserved {
+                    segment: segment.to_string(),
+                });
+            }
+            if let Some(allowed) = &self.allowed_characters
+                && let Some(character) = segment.chars().find(|c| !allowed.contains(*c))
+            {
+                violations.push(Violation::Character {
+                    segment: segment.to_string(),
+                    character,
+                });
+            }
+            if let Some(style) = self.case
+                && !style.matches(segment)
+            {
+                violations.push(Violation::Case {
+                    segment: segment.to_string(),
+                    expected: style.convert(segment),
+                    style,
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaseStyle, CharacterSet};
+    use color_eyre::eyre::OptionExt;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn converts_between_case_styles() {
+        let have: Vec<String> = [
+            CaseStyle::Snake,
+            CaseStyle::ScreamingSnake,
+            CaseStyle::Kebab,
+            CaseStyle::Camel,
+            CaseStyle::Pascal,
+        ]
+        .into_iter()
+        .flat_map(|style| ["signOut", "sign-out", "SignOut2fa"].map(|key| style.convert(key)))
+        .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                "sign_out", "sign_out", "sign_out2fa",
+                "SIGN_OUT", "SIGN_OUT", "SIGN_OUT2FA",
+                "sign-out", "sign-out", "sign-out2fa",
+                "signOut", "signOut", "signOut2fa",
+                "SignOut", "SignOut", "SignOut2fa",
+            ]
+        );
+        assert!(CaseStyle::Snake.matches("404"));
+        assert!(!CaseStyle::Camel.matches("sign_out"));
+    }
+
+    #[test_util::test]
+    fn parses_character_sets() {
+        let set = CharacterSet::parse("a-z0-9_-").ok_or_eyre("invalid character set")?;
+        assert!(set.contains('q') && set.contains('7') && set.contains('_') && set.contains('-'));
+        assert!(!set.contains('A') && !set.contains(' '));
+        assert!(CharacterSet::parse("z-a").is_none());
+        assert!(CharacterSet::parse("").is_none());
+    }
+}
@@ -0,0 +1,237 @@
+//! Pseudo-localization.
+//!
+//! Turns source-language text into a synthetic locale, such as `en-XA`, that
+//! stays readable but makes hard-coded, truncated, and clipped strings stand
+//! out in the UI: letters are accented, text is padded to simulate longer
+//! languages and wrapped in brackets, and it can be mirrored to test
+//! right-to-left layouts. Placeholders, markup tags, and character references
+//! are kept as they are.
+
+use crate::length::ExpansionRatio;
+
+/// The expansion ratio used unless configured otherwise.
+pub const DEFAULT_EXPANSION: f64 = 1.3;
+
+/// The character appended to pad text to its expanded length.
+const PADDING: char = '~';
+
+/// Accented look-alikes of `a` to `z`.
+const ACCENTED_LOWER: &str = "ȧƀƈḓḗƒɠħīĵķŀḿƞǿƥɋřşŧŭṽẇẋẏẑ";
+/// Accented look-alikes of `A` to `Z`.
+const ACCENTED_UPPER: &str = "ȦƁƇḒḖƑƓĦĪĴĶĿḾȠǾƤɊŘŞŦŬṼẆẊẎẐ";
+
+/// Right-to-left override, which displays the following text mirrored.
+const RIGHT_TO_LEFT_OVERRIDE: char = '\u{202e}';
+/// Pop directional formatting, which ends the override.
+const POP_DIRECTIONAL_FORMATTING: char = '\u{202c}';
+
+/// How text is pseudo-localized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The ratio the visible text is padded to, such as `1.3` for text 30%
+    /// longer than the source. Text is not padded when `None`.
+    pub expansion: Option<ExpansionRatio>,
+    /// Whether text is wrapped in `[` and `]`, so that truncation shows.
+    pub brackets: bool,
+    /// Whether text is wrapped in right-to-left overrides, so that it displays
+    /// mirrored.
+    pub mirror: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            expansion: ExpansionRatio::new(DEFAULT_EXPANSION),
+            brackets: true,
+            mirror: false,
+        }
+    }
+}
+
+/// A piece of text that is either pseudo-localized or kept as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Verbatim(&'a str),
+}
+
+/// Returns the length of the placeholder, tag, or character reference that
+/// `rest` starts with, if any.
+fn verbatim_len(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices();
+    let (_, first) = chars.next()?;
+    match first {
+        '{' => {
+            // Placeholders nest, as in `{count, plural, one {# item}}`; an
+            // unclosed one extends to the end of the text.
+            let mut depth = 0usize;
+            for (index, c) in rest.char_indices() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(index + 1);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Some(rest.len())
+        }
+        '<' => {
+            // `a < b` is text; `<b>`, `</b>`, and `<0>` are tags.
+            let (_, next) = chars.next()?;
+            if !(next.is_ascii_alphanumeric() || next == '/') {
+                return None;
+            }
+            rest.find('>').map(|end| end + 1)
+        }
+        '&' => {
+            let end = rest.find(';')?;
+            let name = rest.get(1..end)?.trim_start_matches('#');
+            (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())).then_some(end + 1)
+        }
+        _ => None,
+    }
+}
+
+/// Splits `text` into text and verbatim segments.
+fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut index = 0;
+    while let Some(rest) = text.get(index..) {
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        if let Some(len) = verbatim_len(rest) {
+            if let Some(before) = text.get(start..index).filter(|before| !before.is_empty()) {
+                segments.push(Segment::Text(before));
+            }
+            segments.push(Segment::Verbatim(rest.get(..len).unwrap_or(rest)));
+            index += len;
+            start = index;
+        } else {
+            index += c.len_utf8();
+        }
+    }
+    if let Some(rest) = text.get(start..).filter(|rest| !rest.is_empty()) {
+        segments.push(Segment::Text(rest));
+    }
+    segments
+}
+
+/// Returns the accented look-alike of an ASCII letter, and any other
+/// character unchanged.
+fn accent(c: char) -> char {
+    let (table, offset) = if c.is_ascii_lowercase() {
+        (ACCENTED_LOWER, u32::from(c) - u32::from('a'))
+    } else if c.is_ascii_uppercase() {
+        (ACCENTED_UPPER, u32::from(c) - u32::from('A'))
+    } else {
+        return c;
+    };
+    table.chars().nth(offset as usize).unwrap_or(c)
+}
+
+/// The number of padding characters that expand `len` characters by `ratio`.
+fn padding(len: usize, ratio: ExpansionRatio) -> usize {
+    #[expect(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "translation lengths are far below 2^52, and the padding is positive"
+    )]
+    let padding = (len as f64 * (ratio.get() - 1.0)).ceil().max(0.0) as usize;
+    padding
+}
+
+/// Pseudo-localizes `text`, keeping placeholders, markup tags, and character
+/// references as they are. Empty text stays empty.
+#[must_use]
+pub fn pseudolocalize(text: &str, options: &Options) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let mut output = String::with_capacity(text.len() * 2);
+    let mut visible = 0;
+    if options.brackets {
+        output.push('[');
+    }
+    for segment in segments(text) {
+        match segment {
+            Segment::Verbatim(verbatim) => output.push_str(verbatim),
+            Segment::Text(text) => {
+                if options.mirror {
+                    output.push(RIGHT_TO_LEFT_OVERRIDE);
+                }
+                output.extend(text.chars().map(accent));
+                if options.mirror {
+                    output.push(POP_DIRECTIONAL_FORMATTING);
+                }
+                visible += text.chars().count();
+            }
+        }
+    }
+    if let Some(ratio) = options.expansion {
+        output.extend(std::iter::repeat_n(PADDING, padding(visible, ratio)));
+    }
+    if options.brackets {
+        output.push(']');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ACCENTED_LOWER, ACCENTED_UPPER, Options, pseudolocalize};
+    use crate::length::ExpansionRatio;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn accents_pads_and_brackets_text() {
+        assert_eq!(ACCENTED_LOWER.chars().count(), 26);
+        assert_eq!(ACCENTED_UPPER.chars().count(), 26);
+
+        let options = Options::default();
+        sim_assert_eq!(have: pseudolocalize("Sign in", &options), want: "[Şīɠƞ īƞ~~~]");
+        sim_assert_eq!(have: pseudolocalize("", &options), want: "");
+
+        let options = Options {
+            expansion: ExpansionRatio::new(2.0),
+            brackets: false,
+            mirror: false,
+        };
+        sim_assert_eq!(have: pseudolocalize("Save", &options), want: "Şȧṽḗ~~~~");
+    }
+
+    #[test_util::test]
+    fn keeps_placeholders_and_markup() {
+        let options = Options {
+            expansion: None,
+            ..Options::default()
+        };
+        sim_assert_eq!(
+            have: pseudolocalize("Hi {{name}}, see <a href=\"/x\">terms</a> &amp; a < b", &options),
+            want: "[Ħī {{name}}, şḗḗ <a href=\"/x\">ŧḗřḿş</a> &amp; ȧ < ƀ]"
+        );
+        sim_assert_eq!(
+            have: pseudolocalize("{count, plural, one {# item} other {# items}}", &options),
+            want: "[{count, plural, one {# item} other {# items}}]"
+        );
+    }
+
+    #[test_util::test]
+    fn mirrors_text_runs() {
+        let options = Options {
+            expansion: None,
+            brackets: false,
+            mirror: true,
+        };
+        sim_assert_eq!(
+            have: pseudolocalize("Hi {{name}}", &options),
+            want: "\u{202e}Ħī \u{202c}{{name}}"
+        );
+    }
+}
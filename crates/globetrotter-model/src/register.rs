@@ -0,0 +1,254 @@
+//! Formal and informal address.
+//!
+//! Many languages address the reader either formally (`Sie`, `vous`, `usted`)
+//! or informally (`du`, `tu`, `tú`), and a product usually settles on one
+//! register per language. This module finds the pronouns and possessives that
+//! mark a translation as one or the other, for the languages where they are
+//! unambiguous enough to check without a language model.
+//!
+//! Only pronouns are detected: an imperative such as "Melde dich an" is caught
+//! by its `dich`, but "Klicke hier" carries its register in the verb alone
+//! and is not.
+
+use crate::Language;
+use std::ops::Range;
+
+/// The register a translation addresses its reader in.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Register {
+    /// `Sie`, `vous`, `usted`.
+    Formal,
+    /// `du`, `tu`, `tú`.
+    Informal,
+}
+
+/// The languages whose register can be detected.
+pub const SUPPORTED: &[Language] = &[
+    Language::De,
+    Language::Es,
+    Language::Fr,
+    Language::It,
+    Language::Nl,
+];
+
+/// A word marking the register of a translation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Marker {
+    /// The register the word belongs to.
+    pub register: Register,
+    /// The byte range of the word in the translation.
+    pub range: Range<usize>,
+}
+
+/// Pronouns and possessives of one register, matched case-insensitively.
+struct Words {
+    informal: &'static [&'static str],
+    formal: &'static [&'static str],
+    /// Formal words that are only formal when capitalized in the middle of a
+    /// sentence, such as German `Sie` ("you"), which is `sie` ("she", "they")
+    /// otherwise.
+    capitalized_formal: &'static [&'static str],
+    /// Whether an elided `t'` marks informal address, as in French
+    /// `t'inscrire`.
+    elided_informal: bool,
+}
+
+/// Returns the register words of `language`, or `None` if its register is
+/// not detected.
+fn words(language: Language) -> Option<Words> {
+    Some(match language {
+        Language::De => Words {
+            informal: &[
+                "du", "dich", "dir", "dein", "deine", "deinen", "deinem", "deiner", "deines",
+                "euch", "euer", "eure", "euren", "eurem", "eurer", "eures",
+            ],
+            formal: &[],
+            capitalized_formal: &[
+                "Sie", "Ihnen", "Ihr", "Ihre", "Ihren", "Ihrem", "Ihrer", "Ihres",
+            ],
+            elided_informal: false,
+        },
+        Language::Es => Words {
+            informal: &[
+                "tú", "tu", "tus", "ti", "te", "contigo", "tuyo", "tuya", "tuyos", "tuyas",
+                "vosotros", "vosotras", "os", "vuestro", "vuestra", "vuestros", "vuestras",
+            ],
+            formal: &["usted", "ustedes", "ud", "uds", "vd", "vds"],
+            capitalized_formal: &[],
+            elided_informal: false,
+        },
+        Language::Fr => Words {
+            informal: &["tu", "te", "toi", "ton", "ta", "tes"],
+            formal: &["vous", "votre", "vos"],
+            capitalized_formal: &[],
+            elided_informal: true,
+        },
+        Language::It => Words {
+            informal: &["tu", "ti", "te", "tuo", "tua", "tuoi", "tue"],
+            formal: &[],
+            capitalized_formal: &["Lei", "Suo", "Sua", "Suoi", "Sue"],
+            elided_informal: false,
+        },
+        Language::Nl => Words {
+            informal: &["je", "jij", "jou", "jouw"],
+            formal: &["u", "uw"],
+            capitalized_formal: &[],
+            elided_informal: false,
+        },
+        _ => return None,
+    })
+}
+
+/// Returns `true` if the register of `language` can be detected.
+#[must_use]
+pub fn is_supported(language: Language) -> bool {
+    SUPPORTED.contains(&language)
+}
+
+/// Finds the words marking the register of a translation in `language`.
+///
+/// Placeholders (`{…}`) and markup tags (`<…>`) are skipped. Languages whose
+/// register is not detected have no markers.
+#[must_use]
+pub fn markers(text: &str, language: Language) -> Vec<Marker> {
+    let Some(words) = words(language) else {
+        return Vec::new();
+    };
+    let mut markers = Vec::new();
+    for (range, sentence_start) in tokens(text) {
+        let word = text.get(range.clone()).unwrap_or_default();
+        let lower = word.to_lowercase();
+        let apostrophe = text
+            .get(range.end..)
+            .and_then(|rest| rest.chars().next())
+            .filter(|c| matches!(c, '\'' | '’'));
+        let elided = words.elided_informal && lower == "t" && apostrophe.is_some();
+        let register = if elided || words.informal.contains(&lower.as_str()) {
+            Register::Informal
+        } else if words.formal.contains(&lower.as_str())
+            || (!sentence_start && words.capitalized_formal.contains(&word))
+        {
+            Register::Formal
+        } else {
+            continue;
+        };
+        let end = match apostrophe {
+            Some(apostrophe) if elided => range.end + apostrophe.len_utf8(),
+            _ => range.end,
+        };
+        markers.push(Marker {
+            register,
+            range: range.start..end,
+        });
+    }
+    markers
+}
+
+/// Splits `text` into words outside of placeholders and tags, and tells
+/// whether each starts a sentence.
+fn tokens(text: &str) -> Vec<(Range<usize>, bool)> {
+    let mut tokens = Vec::new();
+    let mut braces = 0usize;
+    let mut in_tag = false;
+    let mut start: Option<usize> = None;
+    // Whether the next word starts a sentence.
+    let mut sentence_start = true;
+    for (index, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        if c.is_alphabetic() && braces == 0 && !in_tag {
+            start.get_or_insert(index);
+            continue;
+        }
+        if let Some(start) = start.take() {
+            tokens.push((start..index, sentence_start));
+            sentence_start = false;
+        }
+        match c {
+            '{' => braces += 1,
+            '}' => braces = braces.saturating_sub(1),
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            '.' | '!' | '?' | '¡' | '¿' | ':' | '\n' if braces == 0 && !in_tag => {
+                sentence_start = true;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Register, markers};
+    use crate::Language;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    fn found(text: &str, language: Language) -> Vec<(&str, Register)> {
+        markers(text, language)
+            .into_iter()
+            .map(|marker| (text.get(marker.range).unwrap_or_default(), marker.register))
+            .collect()
+    }
+
+    #[test_util::test]
+    fn detects_pronouns_per_language() {
+        sim_assert_eq!(
+            have: found("Melde dich an, um deine Daten zu sehen.", Language::De),
+            want: vec![("dich", Register::Informal), ("deine", Register::Informal)]
+        );
+        sim_assert_eq!(
+            have: found("Bitte melden Sie sich an. Ihr Konto ist gesperrt.", Language::De),
+            want: vec![("Sie", Register::Formal)]
+        );
+        sim_assert_eq!(
+            have: found("Sie hat geantwortet.", Language::De),
+            want: vec![]
+        );
+        sim_assert_eq!(
+            have: found("Connecte-toi pour t’inscrire. Vous avez un message.", Language::Fr),
+            want: vec![
+                ("toi", Register::Informal),
+                ("t’", Register::Informal),
+                ("Vous", Register::Formal),
+            ]
+        );
+        sim_assert_eq!(
+            have: found("¿Quiere usted guardar tus cambios?", Language::Es),
+            want: vec![("usted", Register::Formal), ("tus", Register::Informal)]
+        );
+        sim_assert_eq!(
+            have: found("Grazie per il Suo ordine. Lei ha detto di sì.", Language::It),
+            want: vec![("Suo", Register::Formal)]
+        );
+        sim_assert_eq!(
+            have: found("Wijzig uw wachtwoord of je profiel.", Language::Nl),
+            want: vec![("uw", Register::Formal), ("je", Register::Informal)]
+        );
+        sim_assert_eq!(have: found("Sign in to your account.", Language::En), want: vec![]);
+    }
+
+    #[test_util::test]
+    fn skips_placeholders_and_markup() {
+        sim_assert_eq!(
+            have: found("Hallo {{ du }}, <du>lies</du> {count} Nachrichten.", Language::De),
+            want: vec![]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Review status of translations.
+//!
+//! A key may mark some of its languages as awaiting review, for example text
+//! filled in by machine translation: `status = { fr = "machine" }`, or
+//! `status = "machine"` for every language of the key.
+
+use crate::Language;
+use indexmap::IndexMap;
+
+/// The review status of a translation.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    strum::Display,
+    strum::EnumString,
+    strum::VariantNames,
+    strum::IntoStaticStr,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    /// Machine-translated and not yet reviewed by a person.
+    Machine,
+}
+
+/// The review statuses declared for a translation key.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Statuses {
+    /// The status of languages without an explicit one.
+    pub default: Option<Status>,
+    /// Per-language statuses that take precedence over [`Statuses::default`].
+    pub languages: IndexMap<Language, Status>,
+}
+
+impl Statuses {
+    /// Returns the status of `language`, if any.
+    #[must_use]
+    pub fn get(&self, language: Language) -> Option<Status> {
+        self.languages.get(&language).copied().or(self.default)
+    }
+
+    /// Returns `true` if no status is declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.languages.is_empty()
+    }
+}
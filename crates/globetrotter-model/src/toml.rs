@@ -7,6 +7,10 @@ use crate::{
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use indexmap::IndexMap;
 
+/// Entries of a translation table that configure the translation rather than
+/// hold the text of a language.
+pub const RESERVED_FIELDS: [&str; 5] = ["arguments", "args", "allow", "max_length", "status"];
+
 /// Errors that can occur while parsing translations from TOML.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -36,6 +40,14 @@ pub enum Error {
         /// The source span of the offending entry.
         span: Span,
     },
+    /// A `max_length` limit was zero or negative.
+    #[error("maximum length must be a positive integer, found {value}")]
+    InvalidMaxLength {
+        /// The rejected limit.
+        value: i64,
+        /// The source span of the offending value.
+        span: Span,
+    },
     /// Deserializing a value via serde failed.
     #[error("{source}")]
     Serde {
@@ -111,6 +123,15 @@ mod diagnostics {
                         .with_notes(vec![format!("valid codes are: {valid}")]);
                     vec![diagnostic]
                 }
+                Self::InvalidMaxLength { span, .. } => {
+                    let diagnostic = Diagnostic::error()
+                        .with_message(self.to_string())
+                        .with_labels(vec![
+                            Label::primary(file_id, span.clone())
+                                .with_message("expected a limit of at least 1"),
+                        ]);
+                    vec![diagnostic]
+                }
                 Self::Serde { source, span } => {
                     let diagnostic = Diagnostic::error()
                         .with_message(self.to_string())
@@ -215,6 +236,145 @@ fn parse_allow_entry(code: &str, span: Span) -> Result<crate::lint::AllowEntry,
         })
 }
 
+/// Parses a `max_length` limit, which must be a positive integer.
+fn parse_limit(value: &toml_span::Value) -> Result<Spanned<usize>, Error> {
+    let limit = value.as_integer().ok_or_else(|| Error::UnexpectedType {
+        message: "maximum length must be an integer".to_string(),
+        expected: vec![ValueKind::Integer],
+        found: value.into(),
+        span: value.span.into(),
+    })?;
+    usize::try_from(limit)
+        .ok()
+        .filter(|limit| *limit > 0)
+        .map(|limit| Spanned::new(value.span, limit))
+        .ok_or(Error::InvalidMaxLength {
+            value: limit,
+            span: value.span.into(),
+        })
+}
+
+/// Parses the optional `max_length` key of a translation.
+///
+/// Accepts either a single limit for all languages (`max_length = 24`) or a
+/// table with an optional `default`, an optional `unit` (`"graphemes"` or
+/// `"width"`), and per-language limits keyed by language code.
+///
+/// # Errors
+///
+/// Returns an error if a limit is not a positive integer, or if the table
+/// contains an unknown unit or language code.
+fn parse_max_length(
+    table: &mut toml_span::value::Table,
+) -> Result<Option<crate::length::MaxLength>, Error> {
+    let Some(value) = table.remove("max_length") else {
+        return Ok(None);
+    };
+    match value.as_ref() {
+        toml_span::value::ValueInner::Integer(_) => Ok(Some(crate::length::MaxLength {
+            default: Some(parse_limit(&value)?),
+            ..crate::length::MaxLength::default()
+        })),
+        toml_span::value::ValueInner::Table(limits) => {
+            let mut max_length = crate::length::MaxLength::default();
+            for (key, limit) in limits {
+                match key.name.as_ref() {
+                    "default" => max_length.default = Some(parse_limit(limit)?),
+                    "unit" => {
+                        let unit = limit.as_str().ok_or_else(|| Error::UnexpectedType {
+                            message: "length unit must be a string".to_string(),
+                            expected: vec![ValueKind::String],
+                            found: limit.into(),
+                            span: limit.span.into(),
+                        })?;
+                        max_length.unit =
+                            serde_json::from_value(serde_json::Value::String(unit.to_string()))
+                                .map_err(|source| Error::Serde {
+                                    source,
+                                    span: limit.span.into(),
+                                })?;
+                    }
+                    language => {
+                        let language: Language =
+                            serde_json::from_value(serde_json::Value::String(language.to_string()))
+                                .map_err(|source| Error::Serde {
+                                    source,
+                                    span: key.span.into(),
+                                })?;
+                        max_length.languages.insert(language, parse_limit(limit)?);
+                    }
+                }
+            }
+            Ok(Some(max_length))
+        }
+        _other => Err(Error::UnexpectedType {
+            message: "max_length must be an integer or a table".to_string(),
+            expected: vec![ValueKind::Integer, ValueKind::Table],
+            found: value.as_ref().into(),
+            span: value.span.into(),
+        }),
+    }
+}
+
+/// Parses one status value, such as `"machine"`.
+fn parse_status_value(value: &toml_span::Value) -> Result<crate::status::Status, Error> {
+    let status = value.as_str().ok_or_else(|| Error::UnexpectedType {
+        message: "status must be a string".to_string(),
+        expected: vec![ValueKind::String],
+        found: value.into(),
+        span: value.span.into(),
+    })?;
+    serde_json::from_value(serde_json::Value::String(status.to_string())).map_err(|source| {
+        Error::Serde {
+            source,
+            span: value.span.into(),
+        }
+    })
+}
+
+/// Parses the optional `status` key of a translation.
+///
+/// Accepts either a single status for all languages (`status = "machine"`) or
+/// a table of statuses keyed by language code (`status = { fr = "machine" }`).
+///
+/// # Errors
+///
+/// Returns an error if a status is unknown or the table contains an unknown
+/// language code.
+fn parse_status(table: &mut toml_span::value::Table) -> Result<crate::status::Statuses, Error> {
+    let mut statuses = crate::status::Statuses::default();
+    let Some(value) = table.remove("status") else {
+        return Ok(statuses);
+    };
+    match value.as_ref() {
+        toml_span::value::ValueInner::String(_) => {
+            statuses.default = Some(parse_status_value(&value)?);
+        }
+        toml_span::value::ValueInner::Table(languages) => {
+            for (key, status) in languages {
+                let language: Language =
+                    serde_json::from_value(serde_json::Value::String(key.name.to_string()))
+                        .map_err(|source| Error::Serde {
+                            source,
+                            span: key.span.into(),
+                        })?;
+                statuses
+                    .languages
+                    .insert(language, parse_status_value(status)?);
+            }
+        }
+        _other => {
+            return Err(Error::UnexpectedType {
+                message: "status must be a string or a table".to_string(),
+                expected: vec![ValueKind::String, ValueKind::Table],
+                found: value.as_ref().into(),
+                span: value.span.into(),
+            });
+        }
+    }
+    Ok(statuses)
+}
+
 /// Parses a single translation table from a TOML value.
 ///
 /// # Errors
@@ -272,14 +432,17 @@ pub fn parse_translation(
         })
         .transpose()?;
 
-    // Remove `allow` before scanning scalar entries so it cannot be mistaken
-    // for a language code.
+    // Remove `allow`, `max_length`, and `status` before scanning scalar entries so they cannot
+    // be mistaken for language codes.
     let allow = parse_allow(table)?;
+    let max_length = parse_max_length(table)?;
+    let status = parse_status(table)?;
 
     let languages: Vec<String> = table
         .iter()
         .filter_map(|(language_value, translation_value)| {
-            let terminal = translation_value.as_str().is_some();
+            let terminal = translation_value.as_str().is_some()
+                && !RESERVED_FIELDS.contains(&language_value.name.as_ref());
             if terminal {
                 Some(language_value.name.to_string())
             } else {
@@ -327,6 +490,8 @@ pub fn parse_translation(
             arguments: arguments.unwrap_or_default(),
             file_id,
             allow,
+            max_length,
+            status,
         }))
     }
 }
@@ -497,6 +662,85 @@ mod tests {
         );
     }
 
+    #[test_util::test]
+    fn parses_max_length_forms() {
+        use crate::{Language, length::LengthUnit};
+
+        let translations = parse("[save]\nen = \"Save\"\nmax_length = 12\n")?;
+        let max_length = translations
+            .0
+            .values()
+            .next()
+            .and_then(|translation| translation.max_length.clone());
+        assert_eq!(
+            max_length.and_then(|max_length| max_length.default.map(|limit| limit.inner)),
+            Some(12)
+        );
+
+        let translations = parse(
+            "[save]\nen = \"Save\"\nmax_length = { default = 12, de = 16, unit = \"width\" }\n",
+        )?;
+        let translation = translations.0.values().next();
+        let max_length = translation.and_then(|translation| translation.max_length.as_ref());
+        assert_eq!(
+            max_length.map(|max_length| max_length.unit),
+            Some(LengthUnit::Width)
+        );
+        assert_eq!(
+            max_length.and_then(|max_length| max_length.limit(Language::De).map(|l| l.inner)),
+            Some(16)
+        );
+        // `max_length` is not mistaken for a language or a nested key.
+        assert_eq!(translations.0.len(), 1);
+        assert_eq!(
+            translation.map(|translation| translation.language.len()),
+            Some(1)
+        );
+    }
+
+    #[test_util::test]
+    fn rejects_non_positive_max_length() {
+        let result = parse("[save]\nen = \"Save\"\nmax_length = 0\n");
+        assert!(
+            matches!(result, Err(Error::InvalidMaxLength { value: 0, .. })),
+            "{result:?}"
+        );
+    }
+
+    #[test_util::test]
+    fn parses_status_forms() {
+        use crate::{Language, status::Status};
+
+        let translations =
+            parse("[save]\nen = \"Save\"\nfr = \"Sauver\"\nstatus = { fr = \"machine\" }\n")?;
+        let translation = translations.0.values().next();
+        let status = translation.map(|translation| &translation.status);
+        assert_eq!(
+            status.and_then(|status| status.get(Language::Fr)),
+            Some(Status::Machine)
+        );
+        assert_eq!(status.and_then(|status| status.get(Language::En)), None);
+        // `status` is not mistaken for a language.
+        assert_eq!(
+            translation.map(|translation| translation.language.len()),
+            Some(2)
+        );
+
+        let translations = parse("[save]\nen = \"Save\"\nstatus = \"machine\"\n")?;
+        let status = translations
+            .0
+            .values()
+            .next()
+            .map(|translation| &translation.status);
+        assert_eq!(
+            status.and_then(|status| status.get(Language::En)),
+            Some(Status::Machine)
+        );
+
+        let result = parse("[save]\nen = \"Save\"\nstatus = \"draft\"\n");
+        assert!(matches!(result, Err(Error::Serde { .. })), "{result:?}");
+    }
+
     /// Non-string leaf values produce the normal typed parse error instead of
     /// falling through an unfinished catch-all error path.
     #[test_util::test]
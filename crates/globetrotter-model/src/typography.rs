@@ -0,0 +1,212 @@
+//! Locale-specific typographic conventions.
+//!
+//! Describes how each language writes sentence-final punctuation and quotation
+//! marks, and where it expects no-break spaces, for the typography lint.
+
+use crate::Language;
+
+/// The typographic conventions of one language.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conventions {
+    /// Characters accepted as a sentence-final full stop.
+    pub full_stops: &'static [char],
+    /// Characters accepted as a question mark.
+    pub question_marks: &'static [char],
+    /// Characters accepted as an exclamation mark.
+    pub exclamation_marks: &'static [char],
+    /// Accepted pairs of opening and closing quotation marks.
+    pub quotes: &'static [(char, char)],
+    /// Whether high punctuation (`?`, `!`, `;`, `:`) and guillemets are set
+    /// off by a no-break space, as in French.
+    pub spaced_punctuation: bool,
+    /// Whether questions and exclamations open with an inverted mark (`¿`, `¡`).
+    pub inverted_marks: bool,
+}
+
+const LATIN_FULL_STOPS: &[char] = &['.'];
+const LATIN_QUESTION_MARKS: &[char] = &['?'];
+const LATIN_EXCLAMATION_MARKS: &[char] = &['!'];
+
+const ENGLISH_QUOTES: &[(char, char)] = &[('“', '”'), ('"', '"')];
+const LOW_HIGH_QUOTES: &[(char, char)] = &[('„', '“'), ('»', '«')];
+const LOW_QUOTES: &[(char, char)] = &[('„', '”'), ('«', '»')];
+const GUILLEMETS: &[(char, char)] = &[('«', '»'), ('“', '”')];
+const NORDIC_QUOTES: &[(char, char)] = &[('”', '”'), ('»', '»')];
+const JAPANESE_QUOTES: &[(char, char)] = &[('「', '」'), ('『', '』')];
+const CHINESE_QUOTES: &[(char, char)] = &[('“', '”'), ('「', '」'), ('『', '』')];
+
+const DEFAULT: Conventions = Conventions {
+    full_stops: LATIN_FULL_STOPS,
+    question_marks: LATIN_QUESTION_MARKS,
+    exclamation_marks: LATIN_EXCLAMATION_MARKS,
+    quotes: ENGLISH_QUOTES,
+    spaced_punctuation: false,
+    inverted_marks: false,
+};
+
+/// Every character treated as a double quotation mark.
+pub const QUOTATION_MARKS: &[char] = &['"', '“', '”', '„', '«', '»', '「', '」', '『', '』'];
+
+/// Returns the typographic conventions of `language`.
+///
+/// Languages without specific conventions use English punctuation and quotes.
+#[must_use]
+pub fn conventions(language: Language) -> Conventions {
+    match language {
+        Language::De
+        | Language::Cs
+        | Language::Sk
+        | Language::Sl
+        | Language::Bg
+        | Language::Et
+        | Language::Lt
+        | Language::Is => Conventions {
+            quotes: LOW_HIGH_QUOTES,
+            ..DEFAULT
+        },
+        Language::Pl | Language::Hu | Language::Ro | Language::Hr => Conventions {
+            quotes: LOW_QUOTES,
+            ..DEFAULT
+        },
+        Language::Fr => Conventions {
+            quotes: GUILLEMETS,
+            spaced_punctuation: true,
+            ..DEFAULT
+        },
+        Language::Es => Conventions {
+            quotes: GUILLEMETS,
+            inverted_marks: true,
+            ..DEFAULT
+        },
+        Language::It
+        | Language::Pt
+        | Language::Ca
+        | Language::Ru
+        | Language::Uk
+        | Language::Be
+        | Language::Nb
+        | Language::Nn
+        | Language::No
+        | Language::Tr => Conventions {
+            quotes: GUILLEMETS,
+            ..DEFAULT
+        },
+        Language::El => Conventions {
+            question_marks: &[';', '\u{37e}'],
+            quotes: GUILLEMETS,
+            ..DEFAULT
+        },
+        Language::Sv | Language::Fi => Conventions {
+            quotes: NORDIC_QUOTES,
+            ..DEFAULT
+        },
+        Language::Ar | Language::Fa | Language::Ur => Conventions {
+            question_marks: &['؟'],
+            quotes: GUILLEMETS,
+            ..DEFAULT
+        },
+        Language::Hi | Language::Bn => Conventions {
+            full_stops: &['।'],
+            ..DEFAULT
+        },
+        Language::Hy => Conventions {
+            full_stops: &['։'],
+            quotes: GUILLEMETS,
+            ..DEFAULT
+        },
+        Language::Ja => Conventions {
+            full_stops: &['。'],
+            question_marks: &['？', '?'],
+            exclamation_marks: &['！', '!'],
+            quotes: JAPANESE_QUOTES,
+            ..DEFAULT
+        },
+        Language::Zh => Conventions {
+            full_stops: &['。'],
+            question_marks: &['？'],
+            exclamation_marks: &['！'],
+            quotes: CHINESE_QUOTES,
+            ..DEFAULT
+        },
+        _ => DEFAULT,
+    }
+}
+
+impl Conventions {
+    /// Returns `true` if `mark` is an accepted quotation mark.
+    #[must_use]
+    pub fn accepts_quote(&self, mark: char) -> bool {
+        self.quotes
+            .iter()
+            .any(|(open, close)| mark == *open || mark == *close)
+    }
+
+    /// Describes the accepted quotation marks, e.g. `„…“ or »…«`.
+    #[must_use]
+    pub fn describe_quotes(&self) -> String {
+        self.quotes
+            .iter()
+            .map(|(open, close)| format!("{open}…{close}"))
+            .collect::<Vec<_>>()
+            .join(" or ")
+    }
+}
+
+/// The punctuation a text ends with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Terminal {
+    /// No sentence-final punctuation.
+    None,
+    /// A full stop.
+    FullStop,
+    /// A question mark.
+    QuestionMark,
+    /// An exclamation mark.
+    ExclamationMark,
+    /// An ellipsis.
+    Ellipsis,
+    /// A colon.
+    Colon,
+}
+
+impl std::fmt::Display for Terminal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "no punctuation",
+            Self::FullStop => "a full stop",
+            Self::QuestionMark => "a question mark",
+            Self::ExclamationMark => "an exclamation mark",
+            Self::Ellipsis => "an ellipsis",
+            Self::Colon => "a colon",
+        })
+    }
+}
+
+/// Closing characters skipped when looking for a text's final punctuation.
+const CLOSING: &[char] = &['"', '”', '“', '»', '«', '」', '』', ')', ']', '\'', '’'];
+
+/// Finds the punctuation `text` ends with, and its byte range, in the
+/// conventions of `language`. Closing quotes and brackets are skipped.
+#[must_use]
+pub fn terminal(text: &str, language: Language) -> (Terminal, std::ops::Range<usize>) {
+    let body = text.trim_end().trim_end_matches(CLOSING);
+    let end = body.len();
+    if body.ends_with("...") {
+        return (Terminal::Ellipsis, end - 3..end);
+    }
+    let Some(last) = body.chars().next_back() else {
+        return (Terminal::None, end..end);
+    };
+    let range = end - last.len_utf8()..end;
+    let greek_question = language == Language::El && matches!(last, ';' | '\u{37e}');
+    let kind = match last {
+        _ if greek_question => Terminal::QuestionMark,
+        '.' | '。' | '．' | '।' | '։' => Terminal::FullStop,
+        '?' | '？' | '؟' => Terminal::QuestionMark,
+        '!' | '！' => Terminal::ExclamationMark,
+        '…' => Terminal::Ellipsis,
+        ':' | '：' => Terminal::Colon,
+        _ => return (Terminal::None, end..end),
+    };
+    (kind, range)
+}
@@ -0,0 +1,223 @@
+//! Unicode hygiene: normalization, invisible characters, and bidi controls.
+//!
+//! Text copied from documents, spreadsheets, or chat tools often carries
+//! characters that render as nothing (or render in a different order) but
+//! break string comparison, search, and layout. This module detects them, and
+//! letters of different scripts mixed within one word, which are usually
+//! look-alike characters (homoglyphs) typed on the wrong keyboard layout.
+
+use crate::{Language, diagnostics::Span};
+use unicode_normalization::{UnicodeNormalization, is_nfc};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Characters that render as nothing and have no place in UI text.
+///
+/// Zero-width joiners and non-joiners are deliberately absent: they shape
+/// Persian and Indic scripts and join emoji sequences.
+const INVISIBLE: &[(char, &str)] = &[
+    ('\u{180e}', "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{200b}', "ZERO WIDTH SPACE"),
+    ('\u{2060}', "WORD JOINER"),
+    ('\u{2061}', "FUNCTION APPLICATION"),
+    ('\u{2062}', "INVISIBLE TIMES"),
+    ('\u{2063}', "INVISIBLE SEPARATOR"),
+    ('\u{2064}', "INVISIBLE PLUS"),
+    ('\u{feff}', "ZERO WIDTH NO-BREAK SPACE"),
+];
+
+/// Directional marks, embeddings, and isolates. Right-to-left languages use
+/// them legitimately to order mixed-direction text.
+const BIDI_MARKS: &[(char, &str)] = &[
+    ('\u{61c}', "ARABIC LETTER MARK"),
+    ('\u{200e}', "LEFT-TO-RIGHT MARK"),
+    ('\u{200f}', "RIGHT-TO-LEFT MARK"),
+    ('\u{202a}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202b}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202c}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', "FIRST STRONG ISOLATE"),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+];
+
+/// Directional overrides, which reorder the characters that follow them and
+/// are never needed in translations.
+const BIDI_OVERRIDES: &[(char, &str)] = &[
+    ('\u{202d}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202e}', "RIGHT-TO-LEFT OVERRIDE"),
+];
+
+/// A script whose letters are commonly confused with those of another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, strum::Display)]
+pub enum Script {
+    /// Latin letters.
+    Latin,
+    /// Greek letters.
+    Greek,
+    /// Cyrillic letters.
+    Cyrillic,
+}
+
+impl Script {
+    /// Returns the script of `c`, if it is a letter of a confusable script.
+    #[must_use]
+    pub fn of(c: char) -> Option<Self> {
+        if !c.is_alphabetic() {
+            return None;
+        }
+        match u32::from(c) {
+            0x41..=0x24f | 0x1e00..=0x1eff | 0xff21..=0xff5a => Some(Self::Latin),
+            0x370..=0x3ff | 0x1f00..=0x1fff => Some(Self::Greek),
+            0x400..=0x52f | 0x1c80..=0x1c8f | 0x2de0..=0x2dff | 0xa640..=0xa69f => {
+                Some(Self::Cyrillic)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A kind of Unicode hygiene issue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The text is not in Normalization Form C.
+    NotNfc,
+    /// An invisible character.
+    Invisible(char),
+    /// A bidirectional control character not allowed for the language.
+    Bidi(char),
+    /// A word mixes letters of two scripts.
+    MixedScript(Script, Script),
+}
+
+/// A Unicode hygiene issue in a text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Issue {
+    /// The kind of issue.
+    pub kind: IssueKind,
+    /// The byte range of the offending characters or word.
+    pub range: Span,
+}
+
+/// Returns the Unicode name of an invisible or bidi control character.
+#[must_use]
+pub fn char_name(c: char) -> &'static str {
+    INVISIBLE
+        .iter()
+        .chain(BIDI_MARKS)
+        .chain(BIDI_OVERRIDES)
+        .find(|(known, _)| *known == c)
+        .map_or("", |(_, name)| name)
+}
+
+fn is_invisible(c: char) -> bool {
+    INVISIBLE.iter().any(|(known, _)| *known == c)
+}
+
+/// Returns `true` if `c` is a bidi control that `language` may not use.
+fn is_disallowed_bidi(c: char, language: Language) -> bool {
+    BIDI_OVERRIDES.iter().any(|(known, _)| *known == c)
+        || (!language.is_right_to_left() && BIDI_MARKS.iter().any(|(known, _)| *known == c))
+}
+
+/// Finds the Unicode hygiene issues of a translation in `language`.
+#[must_use]
+pub fn issues(text: &str, language: Language) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if !is_nfc(text) {
+        // Report the span from the first to the last cluster that changes.
+        let changed: Vec<Span> = text
+            .grapheme_indices(true)
+            .filter(|(_, cluster)| !is_nfc(cluster))
+            .map(|(start, cluster)| start..start + cluster.len())
+            .collect();
+        let range = match (changed.first(), changed.last()) {
+            (Some(first), Some(last)) => first.start..last.end,
+            _ => 0..text.len(),
+        };
+        issues.push(Issue {
+            kind: IssueKind::NotNfc,
+            range,
+        });
+    }
+
+    for (index, c) in text.char_indices() {
+        let kind = if is_invisible(c) {
+            IssueKind::Invisible(c)
+        } else if is_disallowed_bidi(c, language) {
+            IssueKind::Bidi(c)
+        } else {
+            continue;
+        };
+        issues.push(Issue {
+            kind,
+            range: index..index + c.len_utf8(),
+        });
+    }
+
+    for (index, word) in text.split_word_bound_indices() {
+        let mut scripts = word.chars().filter_map(Script::of);
+        let Some(first) = scripts.next() else {
+            continue;
+        };
+        if let Some(other) = scripts.find(|script| *script != first) {
+            issues.push(Issue {
+                kind: IssueKind::MixedScript(first, other),
+                range: index..index + word.len(),
+            });
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.range.start);
+    issues
+}
+
+/// Normalizes a translation in `language` to NFC and removes invisible
+/// characters and disallowed bidi controls.
+///
+/// Mixed-script words are left unchanged: which letter is the intended one
+/// cannot be decided automatically.
+#[must_use]
+pub fn fix(text: &str, language: Language) -> String {
+    text.chars()
+        .filter(|c| !is_invisible(*c) && !is_disallowed_bidi(*c, language))
+        .nfc()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Issue, IssueKind, Script, fix, issues};
+    use crate::Language;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn finds_and_fixes_issues() {
+        // A decomposed `é`, a zero-width space, a right-to-left mark, and a
+        // Cyrillic `а` in a Latin word.
+        let text = "Cafe\u{301} Pa\u{200b}y \u{200f}P\u{430}ypal";
+        sim_assert_eq!(
+            have: issues(text, Language::En),
+            want: vec![
+                Issue { kind: IssueKind::NotNfc, range: 3..6 },
+                Issue { kind: IssueKind::Invisible('\u{200b}'), range: 9..12 },
+                Issue { kind: IssueKind::Bidi('\u{200f}'), range: 14..17 },
+                Issue {
+                    kind: IssueKind::MixedScript(Script::Latin, Script::Cyrillic),
+                    range: 17..24,
+                },
+            ]
+        );
+        sim_assert_eq!(have: fix(text, Language::En), want: "Café Pay P\u{430}ypal");
+    }
+
+    #[test_util::test]
+    fn allows_directional_marks_in_right_to_left_languages() {
+        let text = "\u{200f}שלום\u{2067}Globetrotter\u{2069}";
+        sim_assert_eq!(have: issues(text, Language::He), want: vec![]);
+        sim_assert_eq!(
+            have: issues("\u{202e}שלום", Language::He),
+            want: vec![Issue { kind: IssueKind::Bidi('\u{202e}'), range: 0..3 }]
+        );
+    }
+}
@@ -175,6 +175,8 @@ mod tests {
                 arguments: Arguments::default(),
                 file_id: 7,
                 allow: BTreeSet::default(),
+                max_length: None,
+                status: crate::status::Statuses::default(),
             },
         )]))
     }
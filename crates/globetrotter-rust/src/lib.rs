@@ -24,17 +24,80 @@ pub fn preamble() -> String {
     )
 }
 
-fn argument_to_rust_field_name(name: &str) -> String {
+/// Strict, reserved, and weak keywords of the 2024 edition. None of them can
+/// name a variant or field (`union` and `macro_rules` are weak keywords but
+/// valid field names, so they are absent).
+const KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be written as raw identifiers.
+const NON_RAW_KEYWORDS: &[&str] = &["Self", "crate", "self", "super"];
+
+/// Returns `true` if `identifier` is a Rust keyword.
+#[must_use]
+pub fn is_keyword(identifier: &str) -> bool {
+    KEYWORDS.contains(&identifier)
+}
+
+/// Returns `true` if `identifier` can be emitted as a Rust identifier: a
+/// letter or `_` followed by letters, digits, and `_`, other than a lone `_`
+/// or a keyword. Raw identifiers such as `r#type` are valid.
+#[must_use]
+pub fn is_valid_identifier(identifier: &str) -> bool {
+    let (name, raw) = match identifier.strip_prefix("r#") {
+        Some(name) => (name, true),
+        None => (identifier, false),
+    };
+    let mut chars = name.chars();
+    let starts_well = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_');
+    starts_well
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && name != "_"
+        && (raw || !is_keyword(name))
+        && !(raw && NON_RAW_KEYWORDS.contains(&name))
+}
+
+/// Escapes a generated name that is not a valid identifier as it stands:
+/// keywords become raw identifiers (`r#type`), keywords that cannot be raw
+/// get a trailing underscore (`self_`), and a leading digit gets a leading
+/// underscore (`_404`).
+///
+/// Characters that can never appear in an identifier are kept; check the
+/// result with [`is_valid_identifier`].
+fn escape_identifier(identifier: String) -> String {
+    if NON_RAW_KEYWORDS.contains(&identifier.as_str()) {
+        format!("{identifier}_")
+    } else if is_keyword(&identifier) {
+        format!("r#{identifier}")
+    } else if identifier.starts_with(|c: char| c.is_numeric()) {
+        format!("_{identifier}")
+    } else {
+        identifier
+    }
+}
+
+/// The Rust struct field name generated for a template argument, e.g.
+/// `first-name` becomes `first_name` and `type` becomes `r#type`.
+///
+/// The generated field keeps the original name through `#[serde(rename)]`.
+#[must_use]
+pub fn argument_to_rust_field_name(name: &str) -> String {
     let field_name = name.replace(' ', "").replace(['-', '.'], "_");
-    field_name.to_case(Case::Snake)
+    escape_identifier(field_name.to_case(Case::Snake))
 }
 
 /// The Rust enum variant identifier generated for a translation key, e.g.
-/// `translation.greeting` becomes `TranslationGreeting`.
+/// `translation.greeting` becomes `TranslationGreeting` and `404.title`
+/// becomes `_404Title`.
 #[must_use]
 pub fn key_to_rust_enum_variant(key: &str) -> String {
     let variant_name = key.replace(' ', "").replace(['-', '.'], "_");
-    variant_name.to_case(Case::UpperCamel)
+    escape_identifier(variant_name.to_case(Case::UpperCamel))
 }
 
 trait IntoTokenStream {
@@ -106,6 +169,29 @@ impl std::fmt::Display for DuplicateFieldError {
     }
 }
 
+/// A translation key or argument whose generated name cannot be a Rust
+/// identifier, even escaped, e.g. because it contains `!`.
+#[derive(thiserror::Error, Debug)]
+pub struct InvalidIdentifierError {
+    identifier: String,
+    key: String,
+    argument: Option<String>,
+}
+
+impl std::fmt::Display for InvalidIdentifierError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.argument {
+            Some(argument) => write!(f, "{}: argument {argument:?}", self.key)?,
+            None => write!(f, "key `{}`", self.key)?,
+        }
+        write!(
+            f,
+            " generates `{}`, which is not a valid Rust identifier",
+            self.identifier
+        )
+    }
+}
+
 /// Errors that can occur while generating Rust translation bindings.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -115,22 +201,24 @@ pub enum Error {
     /// Duplicate Rust struct field derived from translation arguments.
     #[error(transparent)]
     DuplicateField(#[from] DuplicateFieldError),
+    /// A key or argument whose generated name is not a valid Rust identifier.
+    #[error(transparent)]
+    InvalidIdentifier(#[from] InvalidIdentifierError),
     /// Error originating from the `syn` crate when pretty-printing generated code.
     #[error("{0}")]
     Syn(String),
 }
 
-/// Generates a Rust `Translation` enum for the given translations.
-///
-/// The generated code includes a `key` method that maps each variant back to
-/// its original translation key.
-///
-/// # Errors
-///
-/// Returns an error if translation keys or argument names would result in
-/// duplicate Rust identifiers, or if the generated code cannot be parsed by
-/// `syn` for pretty-printing.
-pub fn generate_translation_enum(translations: &model::Translations) -> Result<String, Error> {
+/// A generated variant name with the key and translation it is generated from.
+type VariantName<'a> = (
+    String,
+    &'a model::diagnostics::Spanned<String>,
+    &'a model::Translation,
+);
+
+/// Normalizes every translation key to its generated variant name, rejecting
+/// collisions and names that cannot be identifiers.
+fn variant_names(translations: &model::Translations) -> Result<Vec<VariantName<'_>>, Error> {
     use itertools::Itertools;
 
     // Normalize every translation key to its generated variant name.
@@ -155,6 +243,37 @@ pub fn generate_translation_enum(translations: &model::Translations) -> Result<S
         return Err(DuplicateIdentifierError { identifier, keys }.into());
     }
 
+    // Escaping cannot repair characters that never appear in identifiers.
+    if let Some((identifier, key, _)) = enum_variant_names
+        .iter()
+        .find(|(safe_key, _, _)| !is_valid_identifier(safe_key))
+    {
+        return Err(InvalidIdentifierError {
+            identifier: identifier.clone(),
+            key: key.to_string(),
+            argument: None,
+        }
+        .into());
+    }
+
+    Ok(enum_variant_names)
+}
+
+/// Generates a Rust `Translation` enum for the given translations.
+///
+/// The generated code includes a `key` method that maps each variant back to
+/// its original translation key.
+///
+/// # Errors
+///
+/// Returns an error if translation keys or argument names would result in
+/// duplicate or invalid Rust identifiers, or if the generated code cannot be
+/// parsed by `syn` for pretty-printing.
+pub fn generate_translation_enum(translations: &model::Translations) -> Result<String, Error> {
+    use itertools::Itertools;
+
+    let enum_variant_names = variant_names(translations)?;
+
     // Generate each variant after validating its normalized field names.
     let enum_variants = enum_variant_names
         .iter()
@@ -185,6 +304,17 @@ pub fn generate_translation_enum(translations: &model::Translations) -> Result<S
                 }));
             }
 
+            if let Some((identifier, name, _)) = fields
+                .iter()
+                .find(|(safe_name, _, _)| !is_valid_identifier(safe_name))
+            {
+                return Err(Error::from(InvalidIdentifierError {
+                    identifier: identifier.clone(),
+                    key: key.to_string(),
+                    argument: Some((*name).clone()),
+                }));
+            }
+
             let fields = fields.into_iter().map(|(safe_name, name, typ)| {
                 let field_ident = format_ident!("{safe_name}");
                 let (typ, uses_lifetime) = typ.into_token_stream();
@@ -198,9 +328,9 @@ pub fn generate_translation_enum(translations: &model::Translations) -> Result<S
             let (fields, uses_lifetime): (Vec<_>, Vec<_>) = fields.unzip();
             let uses_lifetime = uses_lifetime.iter().any(|v| *v);
 
-            let variant_name_ident = format_ident!("{safe_key}");
+            let variant = variant_head(safe_key, translation);
             let tokens = quote! {
-                #variant_name_ident {
+                #variant {
                     #(#fields)*
                 },
             };
@@ -254,6 +384,21 @@ pub fn generate_translation_enum(translations: &model::Translations) -> Result<S
     Ok(code)
 }
 
+/// Renders a variant's name, preceded by documentation of constraints declared
+/// on its translation, such as its maximum length.
+fn variant_head(safe_key: &str, translation: &model::Translation) -> proc_macro2::TokenStream {
+    let variant_name_ident = format_ident!("{safe_key}");
+    let doc = translation
+        .max_length
+        .as_ref()
+        .map(|max_length| format!(" Maximum length: {}.", max_length.describe()))
+        .map(|doc| quote! { #[doc = #doc] });
+    quote! {
+        #doc
+        #variant_name_ident
+    }
+}
+
 fn pretty_print<T>(input: T) -> Result<String, syn::Error>
 where
     T: quote::ToTokens,
@@ -284,6 +429,8 @@ mod tests {
                     arguments: [].into_iter().collect(),
                     file_id: 0,
                     allow: std::collections::BTreeSet::new(),
+                    max_length: None,
+                    status: model::status::Statuses::default(),
                 },
             ),
             (
@@ -304,6 +451,8 @@ mod tests {
                     .collect(),
                     file_id: 0,
                     allow: std::collections::BTreeSet::new(),
+                    max_length: None,
+                    status: model::status::Statuses::default(),
                 },
             ),
         ];
@@ -364,6 +513,8 @@ mod tests {
                     arguments: [].into_iter().collect(),
                     file_id: 0,
                     allow: std::collections::BTreeSet::new(),
+                    max_length: None,
+                    status: model::status::Statuses::default(),
                 },
             ),
             (
@@ -380,6 +531,8 @@ mod tests {
                         .collect(),
                     file_id: 0,
                     allow: std::collections::BTreeSet::new(),
+                    max_length: None,
+                    status: model::status::Statuses::default(),
                 },
             ),
         ];
@@ -416,4 +569,82 @@ mod tests {
         sim_assert_eq!(have: have, want: want);
         Ok(())
     }
+
+    /// A declared maximum length is documented on the generated variant.
+    #[test_util::test]
+    fn documents_max_length() -> eyre::Result<()> {
+        let translation = model::Translation {
+            language: [(model::Language::En, Spanned::dummy("Save".to_string()))]
+                .into_iter()
+                .collect(),
+            max_length: Some(model::length::MaxLength {
+                default: Some(Spanned::dummy(12)),
+                languages: [(model::Language::De, Spanned::dummy(16))]
+                    .into_iter()
+                    .collect(),
+                unit: model::length::LengthUnit::Graphemes,
+            }),
+            ..model::Translation::default()
+        };
+        let translations = model::Translations(
+            [(Spanned::dummy("save".to_string()), translation)]
+                .into_iter()
+                .collect(),
+        );
+        let have = super::generate_translation_enum(&translations)?;
+        assert!(
+            have.contains("    /// Maximum length: 12 graphemes (de: 16).\n    Save {},"),
+            "{have}"
+        );
+        Ok(())
+    }
+
+    /// Keywords and names starting with a digit are escaped, and the original
+    /// argument name is kept for serde.
+    #[test_util::test]
+    fn escapes_keywords_and_leading_digits() -> eyre::Result<()> {
+        let translation = |arguments: &[&str]| model::Translation {
+            language: [(model::Language::En, Spanned::dummy("Text".to_string()))]
+                .into_iter()
+                .collect(),
+            arguments: arguments
+                .iter()
+                .map(|name| ((*name).to_string(), model::ArgumentType::Number))
+                .collect(),
+            ..model::Translation::default()
+        };
+        let translations = model::Translations(
+            [
+                ("self", translation(&["type", "self", "2fa"])),
+                ("404.title", translation(&[])),
+            ]
+            .into_iter()
+            .map(|(key, translation)| (Spanned::dummy(key.to_string()), translation))
+            .collect(),
+        );
+        let have = super::generate_translation_enum(&translations)?;
+        for want in [
+            "#[serde(rename = \"type\")]\n        r#type: i64,",
+            "#[serde(rename = \"self\")]\n        self_: i64,",
+            "#[serde(rename = \"2fa\")]\n        _2_fa: i64,",
+            "Self::Self_ { .. } => \"self\",",
+            "Self::_404Title { .. } => \"404.title\",",
+        ] {
+            assert!(have.contains(want), "missing {want:?} in:\n{have}");
+        }
+
+        let translations = model::Translations(
+            [(Spanned::dummy("price€".to_string()), translation(&[]))]
+                .into_iter()
+                .collect(),
+        );
+        let Err(err) = super::generate_translation_enum(&translations) else {
+            eyre::bail!("`price€` should not be representable");
+        };
+        sim_assert_eq!(
+            have: err.to_string(),
+            want: "key `price€` generates `Price€`, which is not a valid Rust identifier"
+        );
+        Ok(())
+    }
 }
@@ -3,7 +3,10 @@
 use globetrotter_model as model;
 use swc_core::{
     base::{Compiler, PrintArgs},
-    common::DUMMY_SP,
+    common::{
+        BytePos, DUMMY_SP, Span,
+        comments::{Comment, CommentKind, Comments, SingleThreadedComments},
+    },
     ecma::ast,
 };
 
@@ -42,11 +45,16 @@ impl IntoAST<ast::TsType> for model::ArgumentType {
     }
 }
 
-fn emit_code(compiler: &Compiler, program: &ast::Program) -> Result<String, anyhow::Error> {
+fn emit_code(
+    compiler: &Compiler,
+    program: &ast::Program,
+    comments: Option<&dyn Comments>,
+) -> Result<String, anyhow::Error> {
     let printed = compiler.print(
         program,
         PrintArgs {
             preamble: &crate::preamble(),
+            comments,
             ..PrintArgs::default()
         },
     )?;
@@ -110,28 +118,64 @@ fn type_annotation_for_translation(translation: &model::Translation) -> ast::TsT
     }))
 }
 
-fn type_members(
-    translations: &model::Translations,
-) -> impl Iterator<Item = ast::TsTypeElement> + use<'_> {
-    translations.0.iter().map(|(key, translation)| {
-        let type_annotation = type_annotation_for_translation(translation);
-        let key = ast::Expr::Lit(ast::Lit::Str(ast::Str {
-            span: DUMMY_SP,
-            value: key.to_string().into(),
-            raw: None,
-        }));
-        ast::TsTypeElement::TsPropertySignature(ast::TsPropertySignature {
+/// Attaches a doc comment documenting the key's maximum length, if any.
+///
+/// The code generator only emits comments attached to real source positions,
+/// so the returned span is a synthetic position unique to the member.
+fn doc_comment_span(
+    index: usize,
+    translation: &model::Translation,
+    comments: &SingleThreadedComments,
+) -> Span {
+    let Some(max_length) = &translation.max_length else {
+        return DUMMY_SP;
+    };
+    let Some(pos) = u32::try_from(index)
+        .ok()
+        .and_then(|index| index.checked_add(1))
+        .map(BytePos)
+    else {
+        return DUMMY_SP;
+    };
+    comments.add_leading(
+        pos,
+        Comment {
+            kind: CommentKind::Block,
             span: DUMMY_SP,
-            readonly: true,
-            key: Box::new(key),
-            computed: false,
-            optional: false,
-            type_ann: Some(Box::new(ast::TsTypeAnn {
+            text: format!("* Maximum length: {}. ", max_length.describe()).into(),
+        },
+    );
+    Span::new(pos, pos)
+}
+
+fn type_members<'a>(
+    translations: &'a model::Translations,
+    comments: &'a SingleThreadedComments,
+) -> impl Iterator<Item = ast::TsTypeElement> + use<'a> {
+    translations
+        .0
+        .iter()
+        .enumerate()
+        .map(|(index, (key, translation))| {
+            let type_annotation = type_annotation_for_translation(translation);
+            let span = doc_comment_span(index, translation, comments);
+            let key = ast::Expr::Lit(ast::Lit::Str(ast::Str {
                 span: DUMMY_SP,
-                type_ann: Box::new(type_annotation),
-            })),
+                value: key.to_string().into(),
+                raw: None,
+            }));
+            ast::TsTypeElement::TsPropertySignature(ast::TsPropertySignature {
+                span,
+                readonly: true,
+                key: Box::new(key),
+                computed: false,
+                optional: false,
+                type_ann: Some(Box::new(ast::TsTypeAnn {
+                    span: DUMMY_SP,
+                    type_ann: Box::new(type_annotation),
+                })),
+            })
         })
-    })
 }
 
 /// Generates an exported TypeScript `Translations` type.
@@ -143,7 +187,8 @@ pub fn generate_translations_type_export(
     translations: &model::Translations,
 ) -> Result<String, Error> {
     // Convert translations into readonly type members.
-    let members: Vec<_> = type_members(translations).collect();
+    let comments = SingleThreadedComments::default();
+    let members: Vec<_> = type_members(translations, &comments).collect();
 
     // Wrap the members in an exported `Translations` type alias.
     let program = ast::Program::Module(ast::Module {
@@ -171,7 +216,7 @@ pub fn generate_translations_type_export(
     let compiler = Compiler::new(cm);
 
     // Emit the constructed AST with SWC's TypeScript code generator.
-    emit_code(&compiler, &program).map_err(Error::Codegen)
+    emit_code(&compiler, &program, Some(&comments)).map_err(Error::Codegen)
 }
 
 #[cfg(test)]
@@ -238,6 +283,8 @@ mod tests {
                         arguments: [].into_iter().collect(),
                         file_id: 0,
                         allow: std::collections::BTreeSet::new(),
+                        max_length: None,
+                        status: model::status::Statuses::default(),
                     },
                 ),
                 (
@@ -258,6 +305,8 @@ mod tests {
                         .collect(),
                         file_id: 0,
                         allow: std::collections::BTreeSet::new(),
+                        max_length: None,
+                        status: model::status::Statuses::default(),
                     },
                 ),
             ]
@@ -303,11 +352,47 @@ mod tests {
 
         let program = parse(&ts_compiler, fm)?;
 
-        let have = super::emit_code(&ts_compiler, &program).map_err(IntoEyre::into_eyre)?;
+        let have = super::emit_code(&ts_compiler, &program, None).map_err(IntoEyre::into_eyre)?;
         println!("{have}");
 
         let want = format!("{}{}", crate::preamble(), source_code);
         sim_assert_eq!(have: have, want: want);
         Ok(())
     }
+
+    /// A declared maximum length is rendered as a doc comment on the member.
+    #[test_util::test]
+    fn documents_max_length() -> eyre::Result<()> {
+        let translation = model::Translation {
+            language: [(model::Language::En, Spanned::dummy("Save".to_string()))]
+                .into_iter()
+                .collect(),
+            max_length: Some(model::length::MaxLength {
+                default: Some(Spanned::dummy(12)),
+                ..model::length::MaxLength::default()
+            }),
+            ..model::Translation::default()
+        };
+        let translations = model::Translations(
+            [
+                (Spanned::dummy("save".to_string()), translation),
+                (
+                    Spanned::dummy("cancel".to_string()),
+                    model::Translation::default(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let have = super::generate_translations_type_export(&translations)?;
+        let want = indoc::indoc! {r#"
+            export type Translations = {
+                /** Maximum length: 12 graphemes. */ readonly "save": string;
+                readonly "cancel": string;
+            };
+        "# };
+        let want = format!("{}{}", crate::preamble(), want);
+        sim_assert_eq!(have: have, want: want);
+        Ok(())
+    }
 }
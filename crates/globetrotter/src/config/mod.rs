@@ -157,6 +157,14 @@ pub enum ConfigError {
         /// The span of the offending value.
         span: Span,
     },
+    /// A value had the right type but is out of range or otherwise invalid.
+    #[error("{message}")]
+    InvalidValue {
+        /// A human-readable description of the problem.
+        message: String,
+        /// The span of the offending value.
+        span: Span,
+    },
     /// The underlying YAML could not be parsed.
     #[error(transparent)]
     YAML(#[from] yaml_spanned::Error),
@@ -206,6 +214,13 @@ impl ToDiagnostics for ConfigError {
                         Label::primary(file_id, span.clone()).with_message(source.to_string()),
                     ]),
             ],
+            Self::InvalidValue { message, span } => vec![
+                Diagnostic::error()
+                    .with_message(self.to_string())
+                    .with_labels(vec![
+                        Label::primary(file_id, span.clone()).with_message(message),
+                    ]),
+            ],
             Self::YAML(source) => {
                 use yaml_spanned::error::ToDiagnostics;
                 source.to_diagnostics(file_id)
@@ -298,6 +313,171 @@ mod tests {
         Ok(())
     }
 
+    /// The `lint.key_naming` rules parse into [`KeyNaming`], and an invalid
+    /// character set is rejected.
+    ///
+    /// [`KeyNaming`]: globetrotter_model::naming::KeyNaming
+    #[test_util::test]
+    fn parses_key_naming_rules() -> eyre::Result<()> {
+        use globetrotter_model::naming::{CaseStyle, CharacterSet, KeyNaming};
+
+        let config = |rules: &str| {
+            unindent::unindent(&format!(
+                r#"
+                version: 1
+                config:
+                  languages: ["en"]
+                  lint:
+                    key_naming: {rules}
+                  inputs:
+                    - ./translations/a.toml
+                "#
+            ))
+        };
+        let raw = config(
+            r#"{ case: snake_case, max_depth: 4, reserved: [type], allowed_characters: "a-z0-9_" }"#,
+        );
+        let mut diagnostics = vec![];
+        let configs = super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics)?;
+        sim_assert_eq!(
+            have: configs[0].config.lint.key_naming.clone(),
+            want: Some(KeyNaming {
+                case: Some(CaseStyle::Snake),
+                max_depth: Some(4),
+                reserved: vec!["type".to_string()],
+                allowed_characters: CharacterSet::parse("a-z0-9_"),
+            })
+        );
+
+        let raw = config(r#"{ allowed_characters: "z-a" }"#);
+        let result = super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics);
+        assert!(
+            matches!(result, Err(ConfigError::InvalidValue { .. })),
+            "{result:?}"
+        );
+        Ok(())
+    }
+
+    #[test_util::test]
+    fn parses_registers() -> eyre::Result<()> {
+        use globetrotter_model::{Language, register::Register};
+
+        let config = |registers: &str| {
+            unindent::unindent(&format!(
+                r#"
+                version: 1
+                config:
+                  languages: ["en", "de", "es"]
+                  lint:
+                    register: {registers}
+                  inputs:
+                    - ./translations/a.toml
+                "#
+            ))
+        };
+        let mut diagnostics = vec![];
+        let raw = config("{ de: formal, es: informal }");
+        let configs = super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics)?;
+        sim_assert_eq!(
+            have: configs[0].config.lint.register.clone(),
+            want: [
+                (Language::De, Register::Formal),
+                (Language::Es, Register::Informal),
+            ]
+            .into()
+        );
+
+        let raw = config("{ en: formal }");
+        let result = super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics);
+        assert!(
+            matches!(result, Err(ConfigError::InvalidValue { .. })),
+            "{result:?}"
+        );
+        let raw = config("{ de: polite }");
+        let result = super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics);
+        assert!(result.is_err(), "{result:?}");
+        Ok(())
+    }
+
+    #[test_util::test]
+    fn parses_pseudo_locale() -> eyre::Result<()> {
+        use color_eyre::eyre::OptionExt;
+        use globetrotter_model::{Language, length::ExpansionRatio, pseudo};
+
+        let config = |pseudo: &str| {
+            unindent::unindent(&format!(
+                r#"
+                version: 1
+                config:
+                  languages: ["en", "de"]
+                  pseudo: {pseudo}
+                  inputs:
+                    - ./translations/a.toml
+                "#
+            ))
+        };
+        let mut diagnostics = vec![];
+        let raw = config("{}");
+        let configs = super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics)?;
+        let pseudo = configs[0]
+            .config
+            .pseudo
+            .clone()
+            .ok_or_eyre("pseudo is unset")?;
+        sim_assert_eq!(have: pseudo.options, want: pseudo::Options::default());
+        sim_assert_eq!(have: pseudo.locale(Language::En), want: "en-XA");
+
+        let raw = config("{ expansion: 1.5, brackets: false, mirror: true }");
+        let configs = super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics)?;
+        let pseudo = configs[0]
+            .config
+            .pseudo
+            .clone()
+            .ok_or_eyre("pseudo is unset")?;
+        sim_assert_eq!(
+            have: pseudo.options,
+            want: pseudo::Options {
+                expansion: ExpansionRatio::new(1.5),
+                brackets: false,
+                mirror: true,
+            }
+        );
+        sim_assert_eq!(have: pseudo.locale(Language::En), want: "en-XB");
+
+        for invalid in ["{ locale: \"../en\" }", "{ expansion: 0 }"] {
+            let raw = config(invalid);
+            let result =
+                super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics);
+            assert!(
+                matches!(result, Err(ConfigError::InvalidValue { .. })),
+                "{result:?}"
+            );
+        }
+
+        // Every locale, including the pseudo-locale, needs its own JSON file.
+        for (path, valid) in [
+            ("./out/{{ language }}.json", true),
+            ("./out/all.json", false),
+        ] {
+            let raw = unindent::unindent(&format!(
+                r#"
+                version: 1
+                config:
+                  languages: ["en", "de"]
+                  pseudo: {{}}
+                  inputs:
+                    - ./translations/a.toml
+                  outputs:
+                    json: {path:?}
+                "#
+            ));
+            let result =
+                super::from_str(&raw, std::path::Path::new("."), (), None, &mut diagnostics);
+            sim_assert_eq!(have: result.is_ok(), want: valid, "{result:?}");
+        }
+        Ok(())
+    }
+
     /// Numeric, string, and prefixed version-one spellings parse identically.
     #[test_util::test]
     fn test_parse_version() -> eyre::Result<()> {
@@ -7,11 +7,12 @@ use globetrotter_model::{
     self as model,
     diagnostics::{DiagnosticExt, DisplayRepr, Spanned},
 };
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use yaml_spanned::{Mapping, Sequence, Value, value::Kind};
 
 /// A single parsed configuration together with its source location.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConfigFile<F> {
     /// The diagnostic file id of the source file, if any.
     pub file_id: Option<F>,
@@ -440,6 +441,196 @@ pub fn parse_outputs<F: Copy + PartialEq>(
     })
 }
 
+/// Parses the optional `lint` section of one config.
+///
+/// # Errors
+///
+/// Returns an error if the section is not a mapping, or if any lint setting
+/// has an unexpected type or an invalid value.
+pub fn parse_lint_config(value: &yaml_spanned::Spanned<Value>) -> Result<LintConfig, ConfigError> {
+    let Some(lint) = value.get("lint") else {
+        return Ok(LintConfig::default());
+    };
+    let (_span, lint) = expect_mapping(lint)?;
+
+    let max_expansion = parse_optional::<f64>(lint.get("max_expansion"))?
+        .map(|ratio| {
+            let span = ratio.span.clone();
+            model::length::ExpansionRatio::new(ratio.into_inner())
+                .map(|ratio| Spanned::new(span.clone(), ratio))
+                .ok_or_else(|| ConfigError::InvalidValue {
+                    message: "max_expansion must be a positive ratio, e.g. 1.5".to_string(),
+                    span,
+                })
+        })
+        .transpose()?;
+
+    let spelling = lint
+        .get("spelling")
+        .map(|spelling| {
+            let (span, spelling) = expect_mapping(spelling)?;
+            let dictionaries =
+                spelling
+                    .get("dictionaries")
+                    .ok_or_else(|| ConfigError::MissingKey {
+                        key: "dictionaries".to_string(),
+                        message: "missing directory of Hunspell dictionaries".to_string(),
+                        span: (*span).into(),
+                    })?;
+            Ok::<_, ConfigError>(SpellingConfig {
+                dictionaries: parse::<PathBuf>(dictionaries)?,
+                words: parse_optional::<PathBuf>(spelling.get("words"))?,
+            })
+        })
+        .transpose()?;
+
+    let key_naming = lint.get("key_naming").map(parse_key_naming).transpose()?;
+    let register = lint
+        .get("register")
+        .map(parse_registers)
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(LintConfig {
+        max_expansion,
+        spelling,
+        key_naming,
+        register,
+        typography: parse_optional::<bool>(lint.get("typography"))?
+            .is_some_and(Spanned::into_inner),
+        llm_triage: parse_optional::<PathBuf>(lint.get("llm_triage"))?,
+        source_lock: parse_optional::<PathBuf>(lint.get("source_lock"))?,
+    })
+}
+
+/// Parses the `key_naming` rules of the `lint` section.
+///
+/// # Errors
+///
+/// Returns an error if the rules are not a mapping, or if any rule has an
+/// unexpected type or an invalid value.
+pub fn parse_key_naming(
+    value: &yaml_spanned::Spanned<Value>,
+) -> Result<model::naming::KeyNaming, ConfigError> {
+    let (_span, naming) = expect_mapping(value)?;
+    let max_depth = parse_optional::<usize>(naming.get("max_depth"))?
+        .map(|depth| {
+            if *depth.as_ref() == 0 {
+                Err(ConfigError::InvalidValue {
+                    message: "max_depth must be at least 1".to_string(),
+                    span: depth.span,
+                })
+            } else {
+                Ok(depth.into_inner())
+            }
+        })
+        .transpose()?;
+    let allowed_characters = parse_optional::<String>(naming.get("allowed_characters"))?
+        .map(|spec| {
+            model::naming::CharacterSet::parse(spec.as_ref()).ok_or_else(|| {
+                ConfigError::InvalidValue {
+                    message: "allowed_characters must be a character set, e.g. `a-z0-9_`"
+                        .to_string(),
+                    span: spec.span,
+                }
+            })
+        })
+        .transpose()?;
+    Ok(model::naming::KeyNaming {
+        case: parse_optional::<model::naming::CaseStyle>(naming.get("case"))?
+            .map(Spanned::into_inner),
+        max_depth,
+        reserved: parse_optional::<Vec<String>>(naming.get("reserved"))?
+            .map(Spanned::into_inner)
+            .unwrap_or_default(),
+        allowed_characters,
+    })
+}
+
+/// Parses the expected `register` of each language in the `lint` section.
+///
+/// # Errors
+///
+/// Returns an error if the registers are not a mapping from languages to
+/// `formal` or `informal`, or name a language whose register is not detected.
+pub fn parse_registers(
+    value: &yaml_spanned::Spanned<Value>,
+) -> Result<BTreeMap<model::Language, model::register::Register>, ConfigError> {
+    let (_span, registers) = expect_mapping(value)?;
+    registers
+        .iter()
+        .map(|(language, register)| {
+            let language = parse::<model::Language>(language)?;
+            if !model::register::is_supported(*language.as_ref()) {
+                let supported: Vec<_> = model::register::SUPPORTED
+                    .iter()
+                    .map(|language| format!("`{}`", language.code()))
+                    .collect();
+                return Err(ConfigError::InvalidValue {
+                    message: format!(
+                        "the register of `{}` cannot be detected; supported languages are {}",
+                        language.as_ref().code(),
+                        supported.join(", ")
+                    ),
+                    span: language.span,
+                });
+            }
+            let register = parse::<model::register::Register>(register)?;
+            Ok((language.into_inner(), register.into_inner()))
+        })
+        .collect()
+}
+
+/// Parses the `pseudo` section, which generates a pseudo-locale from the
+/// source language.
+///
+/// # Errors
+///
+/// Returns an error if the section is not a mapping, or if any setting has an
+/// unexpected type or an invalid value.
+pub fn parse_pseudo(value: &yaml_spanned::Spanned<Value>) -> Result<PseudoConfig, ConfigError> {
+    let (_span, pseudo) = expect_mapping(value)?;
+    let locale = parse_optional::<String>(pseudo.get("locale"))?
+        .map(|locale| {
+            let valid = !locale.as_ref().is_empty()
+                && locale
+                    .as_ref()
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+            if valid {
+                Ok(locale)
+            } else {
+                Err(ConfigError::InvalidValue {
+                    message: "locale must be a locale code, e.g. `en-XA`".to_string(),
+                    span: locale.span,
+                })
+            }
+        })
+        .transpose()?;
+    let defaults = model::pseudo::Options::default();
+    let expansion = match parse_optional::<f64>(pseudo.get("expansion"))? {
+        None => defaults.expansion,
+        Some(ratio) => Some(
+            model::length::ExpansionRatio::new(*ratio.as_ref()).ok_or_else(|| {
+                ConfigError::InvalidValue {
+                    message: "expansion must be a positive ratio, e.g. 1.3".to_string(),
+                    span: ratio.span,
+                }
+            })?,
+        ),
+    };
+    Ok(PseudoConfig {
+        locale,
+        options: model::pseudo::Options {
+            expansion,
+            brackets: parse_optional::<bool>(pseudo.get("brackets"))?
+                .map_or(defaults.brackets, Spanned::into_inner),
+            mirror: parse_optional::<bool>(pseudo.get("mirror"))?
+                .map_or(defaults.mirror, Spanned::into_inner),
+        },
+    })
+}
+
 /// Parses one configuration entry.
 ///
 /// # Errors
@@ -458,6 +649,9 @@ pub fn parse_config<F: Copy + PartialEq>(
     let strict_config = parse_optional::<bool>(value.get("strict"))?.map(Spanned::into_inner);
     let strict = strict_override.unwrap_or(false);
     let languages = parse_languages(value, file_id, strict, diagnostics)?;
+    let source_language = parse_optional::<model::Language>(value.get("source_language"))?;
+    let lint = parse_lint_config(value)?;
+    let pseudo = value.get("pseudo").map(parse_pseudo).transpose()?;
     let template_engine = parse_optional::<model::TemplateEngine>(
         value.get("engine").or_else(|| value.get("template_engine")),
     )?;
@@ -475,9 +669,28 @@ pub fn parse_config<F: Copy + PartialEq>(
     let inputs = parse_inputs(value, config_span, file_id, strict, diagnostics)?;
     let outputs = parse_outputs(value, config_span, file_id, strict, diagnostics)?;
 
+    // The pseudo-locale is written through every JSON output as well, so an
+    // output shared by all languages would be written twice at once.
+    if pseudo.is_some()
+        && let Some(shared) = outputs
+            .json
+            .iter()
+            .find(|output| !mentions_language(output.path.as_ref()))
+    {
+        return Err(ConfigError::InvalidValue {
+            message: "include `{{language}}` in the path to write the pseudo-locale to its own \
+                      file"
+                .to_string(),
+            span: shared.path.span.clone(),
+        });
+    }
+
     Ok(Config {
         name,
         languages,
+        source_language,
+        lint,
+        pseudo,
         settings: SettingsLayer {
             strict: strict_config,
             check_templates,
@@ -490,6 +703,16 @@ pub fn parse_config<F: Copy + PartialEq>(
     })
 }
 
+/// Whether an output path template varies with `{{language}}`.
+fn mentions_language(path: &std::path::Path) -> bool {
+    let template: String = path
+        .to_string_lossy()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    template.contains("{{language}}")
+}
+
 /// Parses the top-level `config` or `configs` structure.
 ///
 /// A single `config` receives the synthetic name `"config"`. A `configs`
@@ -841,6 +1064,82 @@ impl Outputs {
     }
 }
 
+/// Configuration of individual lints.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct LintConfig {
+    /// The maximum ratio between a translation's length and the length of its
+    /// source-language text. Expansion is not checked when unset.
+    pub max_expansion: Option<Spanned<model::length::ExpansionRatio>>,
+    /// Spell checking against Hunspell dictionaries. Disabled when unset.
+    pub spelling: Option<SpellingConfig>,
+    /// Naming rules for translation keys. Keys are not checked when unset.
+    pub key_naming: Option<model::naming::KeyNaming>,
+    /// The register each language addresses the reader in. Languages without
+    /// an entry are not checked.
+    pub register: BTreeMap<model::Language, model::register::Register>,
+    /// Whether translations are checked against the typographic conventions
+    /// of their language. Only the comparison with the source language runs
+    /// when unset.
+    pub typography: bool,
+    /// The file recording decisions about LLM judge findings, relative to the
+    /// config file. Every finding is reported when unset.
+    pub llm_triage: Option<Spanned<PathBuf>>,
+    /// The file recording the source-language text each translation was
+    /// reviewed against, relative to the config file. Stale translations are
+    /// not detected when unset.
+    pub source_lock: Option<Spanned<PathBuf>>,
+}
+
+/// Configuration of the pseudo-locale generated from the source language.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct PseudoConfig {
+    /// The code of the pseudo-locale, such as `en-XA`. Defaults to the source
+    /// language with the region `XA`, or `XB` when mirrored.
+    pub locale: Option<Spanned<String>>,
+    /// How the source-language text is pseudo-localized.
+    pub options: model::pseudo::Options,
+}
+
+impl PseudoConfig {
+    /// Returns the code of the pseudo-locale generated from `source`.
+    #[must_use]
+    pub fn locale(&self, source: model::Language) -> String {
+        match &self.locale {
+            Some(locale) => locale.as_ref().clone(),
+            None if self.options.mirror => format!("{}-XB", source.code()),
+            None => format!("{}-XA", source.code()),
+        }
+    }
+}
+
+/// Configuration of the `spelling` lint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpellingConfig {
+    /// The directory holding a `.aff`/`.dic` pair per language, relative to
+    /// the config file.
+    pub dictionaries: Spanned<PathBuf>,
+    /// A project word list with one accepted word per line, relative to the
+    /// config file.
+    pub words: Option<Spanned<PathBuf>>,
+}
+
+impl SpellingConfig {
+    /// Creates a spelling configuration reading dictionaries from `dir`.
+    pub fn new(dictionaries: impl Into<PathBuf>) -> Self {
+        Self {
+            dictionaries: Spanned::dummy(dictionaries.into()),
+            words: None,
+        }
+    }
+
+    /// Sets the project word list.
+    #[must_use]
+    pub fn with_words(mut self, words: impl Into<PathBuf>) -> Self {
+        self.words = Some(Spanned::dummy(words.into()));
+        self
+    }
+}
+
 /// A single named translation configuration.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Config {
@@ -848,6 +1147,13 @@ pub struct Config {
     pub name: Spanned<String>,
     /// The languages that must be present in the translations.
     pub languages: Vec<Spanned<model::Language>>,
+    /// The language translations are authored in. Defaults to the first of
+    /// [`Config::languages`].
+    pub source_language: Option<Spanned<model::Language>>,
+    /// Settings of individual lints.
+    pub lint: LintConfig,
+    /// The pseudo-locale generated from the source language, if any.
+    pub pseudo: Option<PseudoConfig>,
     /// This config's settings layer.
     ///
     /// These are raw, unresolved values: caller overrides and built-in
@@ -869,6 +1175,9 @@ impl Config {
         Self {
             name: Spanned::dummy(name.into()),
             languages: vec![],
+            source_language: None,
+            lint: LintConfig::default(),
+            pseudo: None,
             settings: SettingsLayer::default(),
             inputs: vec![],
             outputs: Outputs::default(),
@@ -890,6 +1199,76 @@ impl Config {
         self
     }
 
+    /// Sets the language translations are authored in.
+    #[must_use]
+    pub fn with_source_language(mut self, language: impl Into<model::Language>) -> Self {
+        self.source_language = Some(Spanned::dummy(language.into()));
+        self
+    }
+
+    /// Sets the maximum ratio between a translation's length and the length
+    /// of its source-language text.
+    #[must_use]
+    pub fn with_max_expansion(mut self, max_expansion: model::length::ExpansionRatio) -> Self {
+        self.lint.max_expansion = Some(Spanned::dummy(max_expansion));
+        self
+    }
+
+    /// Enables spell checking.
+    #[must_use]
+    pub fn with_spelling(mut self, spelling: SpellingConfig) -> Self {
+        self.lint.spelling = Some(spelling);
+        self
+    }
+
+    /// Sets the naming rules for translation keys.
+    #[must_use]
+    pub fn with_key_naming(mut self, key_naming: model::naming::KeyNaming) -> Self {
+        self.lint.key_naming = Some(key_naming);
+        self
+    }
+
+    /// Sets the register `language` is expected to address the reader in.
+    #[must_use]
+    pub fn with_register(
+        mut self,
+        language: impl Into<model::Language>,
+        register: model::register::Register,
+    ) -> Self {
+        self.lint.register.insert(language.into(), register);
+        self
+    }
+
+    /// Sets whether translations are checked against the typographic
+    /// conventions of their language.
+    #[must_use]
+    pub fn with_typography(mut self, enabled: bool) -> Self {
+        self.lint.typography = enabled;
+        self
+    }
+
+    /// Sets the file recording the source-language text each translation was
+    /// reviewed against.
+    #[must_use]
+    pub fn with_source_lock(mut self, path: impl Into<PathBuf>) -> Self {
+        self.lint.source_lock = Some(Spanned::dummy(path.into()));
+        self
+    }
+
+    /// Generates a pseudo-locale from the source language.
+    #[must_use]
+    pub fn with_pseudo(mut self, pseudo: PseudoConfig) -> Self {
+        self.pseudo = Some(pseudo);
+        self
+    }
+
+    /// Sets the file recording decisions about LLM judge findings.
+    #[must_use]
+    pub fn with_llm_triage(mut self, path: impl Into<PathBuf>) -> Self {
+        self.lint.llm_triage = Some(Spanned::dummy(path.into()));
+        self
+    }
+
     /// Sets whether templates are validated.
     #[must_use]
     pub fn with_check_templates(mut self, check_templates: bool) -> Self {
@@ -980,6 +1359,16 @@ impl std::fmt::Display for Config {
 }
 
 impl Config {
+    /// Returns the language translations are authored in: the explicit
+    /// `source_language`, or else the first configured language.
+    #[must_use]
+    pub fn source_language(&self) -> Option<model::Language> {
+        self.source_language
+            .as_ref()
+            .or_else(|| self.languages.first())
+            .map(|language| *language.as_ref())
+    }
+
     /// Returns `true` if the configuration has no inputs or no outputs.
     #[must_use]
     pub fn is_empty(&self) -> bool {
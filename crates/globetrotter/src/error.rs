@@ -103,6 +103,15 @@ pub enum Error {
     #[cfg(feature = "llm-judge")]
     #[error(transparent)]
     LlmJudge(#[from] globetrotter_llm_judge::Error),
+
+    /// Reading a source lock failed.
+    #[error(transparent)]
+    SourceLock(#[from] crate::stale::Error),
+
+    /// Reading an LLM judge triage file failed.
+    #[cfg(feature = "llm-judge")]
+    #[error(transparent)]
+    Triage(#[from] crate::triage::Error),
 }
 
 /// Indicates that processing completed but surfaced one or more error
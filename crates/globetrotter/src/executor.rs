@@ -23,7 +23,7 @@ use globetrotter_model::{
 };
 use itertools::Itertools;
 use normalize_path::NormalizePath;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
@@ -33,8 +33,13 @@ use std::sync::Arc;
 pub struct LintParams {
     /// Whether to report duplicate translations (across keys and within a key).
     pub detect_duplicates: bool,
-    /// Source directories to scan for unused keys; empty disables the check.
+    /// Source directories to scan for unused and missing keys; empty disables
+    /// both checks.
     pub usages: Vec<PathBuf>,
+    /// Translation functions whose literal first argument is a key, checked
+    /// for keys that are not defined; empty uses
+    /// [`DEFAULT_CALLS`](crate::usages::DEFAULT_CALLS).
+    pub calls: Vec<String>,
     /// LLM-judged translation-consistency review; `None` disables the check.
     ///
     /// Requires the `llm-judge` feature to be enabled in this build; otherwise
@@ -42,6 +47,18 @@ pub struct LintParams {
     pub llm_judge: Option<LlmJudgeParams>,
 }
 
+/// Parameters for [`Executor::find_usages`].
+#[derive(Debug, Clone, Default)]
+pub struct UsagesParams {
+    /// Keys or key globs to look up, such as `app.cart.*`.
+    pub keys: Vec<String>,
+    /// Source directories to scan.
+    pub dirs: Vec<PathBuf>,
+    /// Translation functions whose literal first argument is a key; empty
+    /// uses [`DEFAULT_CALLS`](crate::usages::DEFAULT_CALLS).
+    pub calls: Vec<String>,
+}
+
 /// Settings for the LLM-judged translation-consistency review.
 ///
 /// Kept independent of the `llm-judge` feature so callers (e.g. the CLI) can
@@ -70,6 +87,18 @@ pub struct LlmJudgeParams {
     pub cache_dir: Option<PathBuf>,
     /// Maximum number of cached verdicts kept on disk; `0` disables caching.
     pub cache_capacity: usize,
+    /// Estimated prompt-token budget for judging several keys in one request;
+    /// `0` sends one request per key.
+    pub batch_tokens: usize,
+    /// Retries of a rate-limited or failed request.
+    pub max_retries: usize,
+    /// Requests started per minute at most; `None` for no limit.
+    pub requests_per_minute: Option<u32>,
+    /// Tokens per minute at most; `None` for no limit.
+    pub tokens_per_minute: Option<u32>,
+    /// Total tokens after which the keys left are skipped; `None` for no
+    /// limit.
+    pub token_budget: Option<u64>,
 }
 
 /// Reasoning effort requested from the judge model.
@@ -183,7 +212,7 @@ pub(crate) fn resolve_input_paths<'a>(
 
 type OutputFuture<'a> = Pin<Box<dyn Future<Output = Result<(), OutputError>> + 'a>>;
 
-type TranslationResult = (
+pub(crate) type TranslationResult = (
     config::Input,
     PathBuf,
     usize,
@@ -191,7 +220,7 @@ type TranslationResult = (
     Vec<Diagnostic<FileId>>,
 );
 
-fn combine_translations(
+pub(crate) fn combine_translations(
     translations: Vec<TranslationResult>,
     diagnostics: &mut Vec<Diagnostic<FileId>>,
 ) -> model::Translations {
@@ -226,7 +255,7 @@ fn combine_translations(
 
 /// Truncates `translations` to its first `max_keys` keys, warning about what is
 /// dropped; `None` is a no-op. See [`Executor::max_keys`].
-fn limit_keys(max_keys: Option<usize>, translations: &mut model::Translations) {
+pub(crate) fn limit_keys(max_keys: Option<usize>, translations: &mut model::Translations) {
     let Some(max_keys) = max_keys else {
         return;
     };
@@ -251,6 +280,7 @@ fn tally(severity: Severity, num_errors: &mut usize, num_warnings: &mut usize) {
 ///
 /// Configs may execute concurrently. Within one config, source diagnostics and
 /// catalog validation must succeed before any output future is polled.
+#[derive(Clone)]
 pub struct Executor {
     /// The caller's settings overrides, applied over each config's own
     /// settings by [`Settings::resolve`].
@@ -294,7 +324,7 @@ impl Executor {
     ///
     /// Lint reports warnings by default regardless of a config's `strict`
     /// (which governs generation); only an explicit override escalates.
-    fn lint_strict(&self) -> bool {
+    pub(crate) fn lint_strict(&self) -> bool {
         self.overrides.strict.unwrap_or(false)
     }
 
@@ -364,71 +394,13 @@ impl Executor {
                 Ok(translations) => translations,
             };
 
-            let mut prefix: Vec<String> = Vec::new();
-
-            if input
-                .prepend_relative_path
-                .as_deref()
-                .copied()
-                .unwrap_or(false)
-            {
-                if let Some(base_dir) = relative_base_dir.as_ref()
-                    && let Some(rel_path) = pathdiff::diff_paths(&input_path, base_dir)
-                {
-                    let mut components: Vec<String> = rel_path
-                        .components()
-                        .filter_map(|c| {
-                            use std::path::Component;
-                            match c {
-                                Component::Normal(os) => Some(os.to_string_lossy().to_string()),
-                                _ => None,
-                            }
-                        })
-                        .collect();
-
-                    if let Some(last) = components.last_mut()
-                        && let Some(stripped) = Path::new(last).file_stem()
-                    {
-                        *last = stripped.to_string_lossy().to_string();
-                    }
-
-                    prefix.extend(components.into_iter().filter(|p| !p.is_empty()));
-                }
-            } else if input.prepend_filename.as_deref().copied().unwrap_or(false) {
-                let file_stem = input_path
-                    .file_stem()
-                    .map(|name| name.to_string_lossy().to_string());
-                if let Some(file_stem) = file_stem
-                    && !file_stem.is_empty()
-                {
-                    prefix.push(file_stem);
-                }
-            }
-
-            if let Some(extra_prefix) = input
-                .prefix
-                .as_ref()
-                .map(|prefix| prefix.as_ref().as_str())
-                .filter(|extra_prefix| !extra_prefix.is_empty())
-            {
-                prefix.push(extra_prefix.to_string());
-            }
-
-            let separator = input
-                .separator
-                .as_ref()
-                .map_or(".", |sep| sep.as_ref().as_str());
-
+            let prefix = key_prefix(&input, &input_path, relative_base_dir.as_deref());
             if !prefix.is_empty() {
                 translations.0 = translations
                     .0
                     .into_iter()
                     .map(|(key, value)| {
-                        let prefixed_key = prefix
-                            .iter()
-                            .map(String::as_str)
-                            .chain([key.as_ref().as_str()])
-                            .join(separator);
+                        let prefixed_key = format!("{prefix}{}", key.as_ref());
                         (Spanned::new(key.span, prefixed_key), value)
                     })
                     .collect();
@@ -488,7 +460,7 @@ impl Executor {
     ///
     /// Diagnostics from input resolution are pushed onto `diagnostics`; per-file
     /// parse diagnostics travel in the returned tuples.
-    async fn load_translations(
+    pub(crate) async fn load_translations(
         &self,
         config_file: &config::ConfigFile<FileId>,
         strict: bool,
@@ -614,6 +586,16 @@ impl Executor {
                     &mut diagnostics,
                     &options,
                 );
+                // Naming problems often explain identifier collisions that
+                // generators would otherwise report without context.
+                if let Some(naming) = &config_file.config.lint.key_naming {
+                    translations.lint_key_naming(naming, settings.strict, &mut diagnostics);
+                }
+                diagnostics.extend(crate::identifiers::lint_identifiers(
+                    &config_file.config,
+                    &translations,
+                    settings.strict,
+                ));
                 Ok::<_, Error>(diagnostics)
             }
         });
@@ -695,7 +677,8 @@ impl Executor {
     /// Lints one configuration's translation files and emits diagnostics.
     ///
     /// Unlike [`Self::execute_config`], no outputs are generated. Returns the
-    /// number of error and warning diagnostics emitted.
+    /// number of error and warning diagnostics emitted, the linted
+    /// translations, limited to [`Self::max_keys`], and every translation.
     ///
     /// Findings are reported as warnings regardless of the config file's
     /// `strict` (which governs generation); only [`overrides`](Self::overrides)
@@ -705,11 +688,23 @@ impl Executor {
     ///
     /// Returns an error if input files cannot be read or parsed, a spawned task
     /// fails to join, or emitting a diagnostic fails.
+    #[allow(
+        clippy::too_many_lines,
+        reason = "each lint pass runs in sequence and reports through the same tally"
+    )]
     pub async fn lint_config(
         &self,
         config_file: Arc<config::ConfigFile<FileId>>,
         params: &LintParams,
-    ) -> Result<(usize, usize, Arc<model::Translations>), Error> {
+    ) -> Result<
+        (
+            usize,
+            usize,
+            Arc<model::Translations>,
+            Arc<model::Translations>,
+        ),
+        Error,
+    > {
         tracing::debug!(name = config_file.config.name.as_ref(), "linting");
 
         // Lint resolves like generation except for `strict`; see
@@ -736,13 +731,23 @@ impl Executor {
         }
 
         let max_keys = self.max_keys;
-        let (translations, combine_diagnostics) = tokio::task::spawn_blocking(move || {
-            let mut diagnostics = vec![];
-            let mut translations = combine_translations(translations, &mut diagnostics);
-            limit_keys(max_keys, &mut translations);
-            (Arc::new(translations), diagnostics)
-        })
-        .await?;
+        let (translations, all_translations, combine_diagnostics) =
+            tokio::task::spawn_blocking(move || {
+                let mut diagnostics = vec![];
+                let all = Arc::new(combine_translations(translations, &mut diagnostics));
+                // Only the lints are limited; keys used in source code are
+                // checked against every key, or each one past the limit would
+                // be reported as missing.
+                let translations = if max_keys.is_some_and(|max_keys| all.0.len() > max_keys) {
+                    let mut translations = (*all).clone();
+                    limit_keys(max_keys, &mut translations);
+                    Arc::new(translations)
+                } else {
+                    Arc::clone(&all)
+                };
+                (translations, all, diagnostics)
+            })
+            .await?;
 
         for diagnostic in &combine_diagnostics {
             tally(diagnostic.severity, &mut num_errors, &mut num_warnings);
@@ -760,8 +765,23 @@ impl Executor {
                     template_engine: settings.template_engine.as_ref(),
                     strict: settings.strict,
                     detect_duplicates,
+                    source_language: config_file.config.source_language(),
+                    max_expansion: config_file
+                        .config
+                        .lint
+                        .max_expansion
+                        .as_ref()
+                        .map(|ratio| *ratio.as_ref()),
+                    key_naming: config_file.config.lint.key_naming.as_ref(),
+                    registers: &config_file.config.lint.register,
+                    typography_conventions: config_file.config.lint.typography,
                 };
                 translations.lint(&mut diagnostics, &options);
+                diagnostics.extend(crate::identifiers::lint_identifiers(
+                    &config_file.config,
+                    &translations,
+                    settings.strict,
+                ));
                 diagnostics
             }
         })
@@ -772,7 +792,55 @@ impl Executor {
             self.diagnostic_printer.emit(diagnostic).await?;
         }
 
-        Ok((num_errors, num_warnings, translations))
+        if let Some(spelling) = &config_file.config.lint.spelling {
+            let base = config_file.config_dir.as_deref();
+            let dictionaries = resolve_path(base, spelling.dictionaries.as_ref());
+            let words = spelling
+                .words
+                .as_ref()
+                .map(|words| resolve_path(base, words.as_ref()));
+            let languages: BTreeSet<model::Language> = translations
+                .0
+                .values()
+                .flat_map(|translation| translation.language.keys().copied())
+                .collect();
+            let translations = Arc::clone(&translations);
+            let strict = settings.strict;
+            let spelling_diagnostics = tokio::task::spawn_blocking(move || {
+                let dictionaries = crate::spelling::Dictionaries::load(
+                    &dictionaries,
+                    words.as_deref(),
+                    languages,
+                )?;
+                Ok::<_, (PathBuf, std::io::Error)>(crate::spelling::lint_spelling(
+                    &translations,
+                    &dictionaries,
+                    strict,
+                ))
+            })
+            .await?
+            .map_err(|(path, source)| IoError::new(path, source))?;
+
+            for diagnostic in &spelling_diagnostics {
+                tally(diagnostic.severity, &mut num_errors, &mut num_warnings);
+                self.diagnostic_printer.emit(diagnostic).await?;
+            }
+        }
+
+        if let (Some(path), Some(source)) = (
+            crate::stale::source_lock_path(&config_file),
+            config_file.config.source_language(),
+        ) {
+            let lock = crate::stale::SourceLock::load(&path).await?;
+            let stale_diagnostics =
+                crate::stale::lint_stale(&translations, source, &lock, settings.strict);
+            for diagnostic in &stale_diagnostics {
+                tally(diagnostic.severity, &mut num_errors, &mut num_warnings);
+                self.diagnostic_printer.emit(diagnostic).await?;
+            }
+        }
+
+        Ok((num_errors, num_warnings, translations, all_translations))
     }
 
     /// Lints every configuration's translation files.
@@ -803,6 +871,7 @@ impl Executor {
         let mut num_errors = 0;
         let mut num_warnings = 0;
         let mut defined_keys: Vec<crate::dead_keys::DefinedKey> = Vec::new();
+        let mut known_keys: Vec<String> = Vec::new();
 
         // Create the judge once up front (cheap: no request is made until keys
         // are judged), reusing its HTTP client and verdict cache across configs.
@@ -820,7 +889,7 @@ impl Executor {
 
         for config_file in configs {
             let config_file = Arc::new(config_file);
-            let (errors, warnings, translations) =
+            let (errors, warnings, translations, all_translations) =
                 self.lint_config(Arc::clone(&config_file), params).await?;
             num_errors += errors;
             num_warnings += warnings;
@@ -835,6 +904,7 @@ impl Executor {
                         allow: translation.allow.clone(),
                     });
                 }
+                known_keys.extend(all_translations.0.keys().map(|key| key.as_ref().clone()));
             }
 
             // Judge findings are emitted as notes and deliberately not tallied:
@@ -842,15 +912,30 @@ impl Executor {
             // above the live progress bar as each verdict arrives.
             #[cfg(feature = "llm-judge")]
             if let Some(judge) = llm_judge.as_ref() {
-                self.stream_llm_judge(judge, &translations).await?;
+                self.stream_llm_judge(judge, &config_file, &translations)
+                    .await?;
             }
         }
 
         if scan_usages {
             let strict = self.lint_strict();
-            let usages = params.usages.clone();
-            let dead_diagnostics = tokio::task::spawn_blocking(move || {
-                crate::dead_keys::find_unused_keys(&defined_keys, &usages, &excluded, strict)
+            let dirs = params.usages.clone();
+            let calls = params.calls.clone();
+            let (dead_diagnostics, missing) = tokio::task::spawn_blocking(move || {
+                let forms = defined_keys
+                    .iter()
+                    .flat_map(|key| key.forms.iter().cloned());
+                let mut scanner = crate::usages::Scanner::new(forms)?;
+                if !calls.is_empty() {
+                    scanner = scanner.with_calls(calls);
+                }
+                let usages = scanner.scan(&dirs, &excluded);
+                let unused = crate::dead_keys::find_unused_keys(&defined_keys, &usages, strict);
+                let missing = crate::missing_keys::find_missing_keys(
+                    known_keys.iter().map(String::as_str),
+                    &usages,
+                );
+                Ok::<_, std::io::Error>((unused, missing))
             })
             .await?
             .map_err(|source| IoError::new("<usages>", source))?;
@@ -859,6 +944,10 @@ impl Executor {
                 tally(diagnostic.severity, &mut num_errors, &mut num_warnings);
                 self.diagnostic_printer.emit(diagnostic).await?;
             }
+            for diagnostic in self.missing_key_diagnostics(&missing, strict).await? {
+                tally(diagnostic.severity, &mut num_errors, &mut num_warnings);
+                self.diagnostic_printer.emit(&diagnostic).await?;
+            }
         }
 
         if num_errors > 0 || num_warnings > 0 {
@@ -871,12 +960,118 @@ impl Executor {
 
         Ok(self)
     }
+
+    /// Finds where the keys selected by [`UsagesParams::keys`] are used in
+    /// [`UsagesParams::dirs`], by dotted form or by a generated identifier of
+    /// any config defining them.
+    ///
+    /// Diagnostics from loading the translation files are emitted, but do not
+    /// fail the lookup.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a key pattern is not a valid glob, or if the
+    /// translation files cannot be read.
+    pub async fn find_usages(
+        &self,
+        configs: &config::Configs<FileId>,
+        params: &UsagesParams,
+    ) -> Result<Vec<crate::usages::KeyUsages>, Error> {
+        let patterns = params
+            .keys
+            .iter()
+            .map(|key| {
+                crate::usages::KeyPattern::new(key).map_err(|source| Error::Pattern {
+                    source,
+                    path: key.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut keys: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for config_file in configs {
+            let mut diagnostics = vec![];
+            let mut translations = self
+                .load_translations(config_file, self.lint_strict(), &mut diagnostics)
+                .await?;
+            let loaded: Vec<_> = translations
+                .iter_mut()
+                .flat_map(|res| res.4.drain(..))
+                .collect();
+            diagnostics.extend(loaded);
+            let translations = combine_translations(translations, &mut diagnostics);
+            for diagnostic in &diagnostics {
+                self.diagnostic_printer.emit(diagnostic).await?;
+            }
+            for key in translations.0.keys() {
+                let forms = keys.entry(key.as_ref().clone()).or_default();
+                for form in key_forms(&config_file.config, key.as_ref()) {
+                    if !forms.contains(&form) {
+                        forms.push(form);
+                    }
+                }
+            }
+        }
+
+        let excluded = output_dirs(configs);
+        let dirs = params.dirs.clone();
+        let calls = params.calls.clone();
+        let usages = tokio::task::spawn_blocking(move || {
+            let forms = keys
+                .iter()
+                .filter(|(key, _)| patterns.iter().any(|pattern| pattern.matches(key)))
+                .flat_map(|(_, forms)| forms.iter().cloned())
+                .chain(
+                    patterns
+                        .iter()
+                        .filter_map(crate::usages::KeyPattern::literal)
+                        .map(str::to_string),
+                );
+            let mut scanner = crate::usages::Scanner::new(forms)?;
+            if !calls.is_empty() {
+                scanner = scanner.with_calls(calls);
+            }
+            let usages = scanner.scan(&dirs, &excluded);
+            Ok::<_, std::io::Error>(crate::usages::key_usages(&keys, &patterns, &usages))
+        })
+        .await?
+        .map_err(|source| IoError::new("<usages>", source))?;
+        Ok(usages)
+    }
+
+    /// Builds the diagnostics for keys used in source but not defined,
+    /// registering each source file with a finding once so the diagnostics
+    /// point into it.
+    async fn missing_key_diagnostics(
+        &self,
+        missing: &[crate::missing_keys::MissingKey],
+        strict: bool,
+    ) -> Result<Vec<Diagnostic<FileId>>, Error> {
+        let mut file_ids: HashMap<&Path, FileId> = HashMap::new();
+        let mut diagnostics = Vec::with_capacity(missing.len());
+        for key in missing {
+            let path = key.usage.path.as_path();
+            let file_id = if let Some(file_id) = file_ids.get(path) {
+                *file_id
+            } else {
+                let source = tokio::fs::read_to_string(path)
+                    .await
+                    .map_err(|source| IoError::new(path, source))?;
+                let file_id = self.diagnostic_printer.add_source_file(path, source).await;
+                file_ids.insert(path, file_id);
+                file_id
+            };
+            diagnostics.push(key.diagnostic(file_id, strict));
+        }
+        Ok(diagnostics)
+    }
 }
 
 /// All canonical forms a usage of `key` may take across a config's enabled
 /// output targets: the dotted key (used by JSON/TypeScript) plus each target's
 /// generated identifier (e.g. the Rust enum variant `TranslationGreeting`).
-fn key_forms(config: &config::Config, key: &str) -> Vec<String> {
+#[must_use]
+pub fn key_forms(config: &config::Config, key: &str) -> Vec<String> {
     let mut forms = vec![key.to_string()];
     forms.extend(target_identifiers(config, key));
     forms
@@ -908,7 +1103,8 @@ fn insert_output_dir(dirs: &mut BTreeSet<PathBuf>, base: Option<&Path>, path: &P
 
 /// Canonicalized directories holding generated output, excluded from the
 /// dead-key scan so generated files do not mark every key as used.
-fn output_dirs(configs: &config::Configs<FileId>) -> BTreeSet<PathBuf> {
+#[must_use]
+pub fn output_dirs(configs: &config::Configs<FileId>) -> BTreeSet<PathBuf> {
     let mut dirs = BTreeSet::new();
     for config_file in configs {
         let base = config_file.config_dir.as_deref();
@@ -947,6 +1143,121 @@ fn output_dirs(configs: &config::Configs<FileId>) -> BTreeSet<PathBuf> {
     dirs
 }
 
+/// The prefix every key of the translation file at `input_path` is loaded
+/// under: the file's relative path or stem and the input's `prefix`, each
+/// followed by the input's separator. Empty if the input adds no prefix.
+///
+/// `relative_base_dir` is the config directory that `prepend_relative_path`
+/// is relative to.
+#[must_use]
+pub fn key_prefix(
+    input: &config::Input,
+    input_path: &Path,
+    relative_base_dir: Option<&Path>,
+) -> String {
+    let mut prefix: Vec<String> = Vec::new();
+
+    if input
+        .prepend_relative_path
+        .as_deref()
+        .copied()
+        .unwrap_or(false)
+    {
+        if let Some(base_dir) = relative_base_dir
+            && let Some(rel_path) = pathdiff::diff_paths(input_path, base_dir)
+        {
+            let mut components: Vec<String> = rel_path
+                .components()
+                .filter_map(|c| {
+                    use std::path::Component;
+                    match c {
+                        Component::Normal(os) => Some(os.to_string_lossy().to_string()),
+                        _ => None,
+                    }
+                })
+                .collect();
+
+            if let Some(last) = components.last_mut()
+                && let Some(stripped) = Path::new(last).file_stem()
+            {
+                *last = stripped.to_string_lossy().to_string();
+            }
+
+            prefix.extend(components.into_iter().filter(|p| !p.is_empty()));
+        }
+    } else if input.prepend_filename.as_deref().copied().unwrap_or(false) {
+        let file_stem = input_path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string());
+        if let Some(file_stem) = file_stem
+            && !file_stem.is_empty()
+        {
+            prefix.push(file_stem);
+        }
+    }
+
+    if let Some(extra_prefix) = input
+        .prefix
+        .as_ref()
+        .map(|prefix| prefix.as_ref().as_str())
+        .filter(|extra_prefix| !extra_prefix.is_empty())
+    {
+        prefix.push(extra_prefix.to_string());
+    }
+
+    let separator = input
+        .separator
+        .as_ref()
+        .map_or(".", |sep| sep.as_ref().as_str());
+    prefix
+        .iter()
+        .flat_map(|segment| [segment.as_str(), separator])
+        .collect()
+}
+
+/// A translation file of a config and the prefix its keys are loaded under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputFile {
+    /// The canonicalized path of the file.
+    pub path: PathBuf,
+    /// The [`key_prefix`] of the file.
+    pub key_prefix: String,
+}
+
+/// Resolves the translation files of `config_file`, with the prefix each
+/// one's keys are loaded under.
+///
+/// Patterns that match no files push a diagnostic into `diagnostics`.
+///
+/// # Errors
+///
+/// Returns an error if a matched file cannot be canonicalized.
+pub fn resolve_inputs(
+    config_file: &config::ConfigFile<FileId>,
+    strict: bool,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) -> Result<Vec<InputFile>, Error> {
+    let base_dir = config_file.config_dir.as_deref();
+    Executor::unique_input_paths(
+        &config_file.config.inputs,
+        base_dir,
+        strict,
+        config_file.file_id,
+        diagnostics,
+    )
+    .filter_map(Result::ok)
+    .map(|(input, path)| {
+        let path = path
+            .canonicalize()
+            .map_err(|source| IoError::new(&path, source))?;
+        Ok(InputFile {
+            key_prefix: key_prefix(&input, &path, base_dir),
+            path,
+        })
+    })
+    .collect()
+}
+
 /// Resolves all unique translation input paths referenced by `configs`.
 ///
 /// Patterns that match no files push a diagnostic into `diagnostics`. The
@@ -1109,6 +1420,39 @@ mod tests {
         Ok(())
     }
 
+    /// Keys past `max_keys` are not linted, but using them is not reported as
+    /// a missing key.
+    #[test_util::test]
+    async fn max_keys_does_not_report_used_keys_as_missing() -> eyre::Result<()> {
+        let dir = temp_dir("max-keys")?;
+        std::fs::create_dir_all(dir.join("translations"))?;
+        std::fs::create_dir_all(dir.join("src"))?;
+        std::fs::write(
+            dir.join("translations/app.toml"),
+            "[a]\nen = \"A\"\n\n[b]\nen = \"B\"\n",
+        )?;
+        std::fs::write(dir.join("src/app.ts"), "t(\"a\");\nt(\"b\");\n")?;
+
+        let configs = vec![config::ConfigFile {
+            file_id: None,
+            config_dir: Some(dir.clone()),
+            config: config::Config::new("demo")
+                .with_language(model::Language::En)
+                .with_input(config::Input::new("translations/*.toml")),
+        }];
+        let mut executor = Executor::new(&configs, crate::diagnostics::Printer::default());
+        executor.max_keys = Some(1);
+        let params = LintParams {
+            usages: vec![dir.join("src")],
+            ..LintParams::default()
+        };
+        let result = executor.lint(configs, &params).await;
+
+        std::fs::remove_dir_all(dir)?;
+        result?;
+        Ok(())
+    }
+
     /// Exclusion patterns remove matched files without producing diagnostics.
     #[test_util::test]
     fn unique_input_paths_respects_exclude_patterns() -> eyre::Result<()> {
@@ -0,0 +1,286 @@
+//! Finding hard-coded, user-facing strings that should become translations.
+//!
+//! TypeScript, JavaScript (including JSX), and Rust files are parsed, and
+//! three kinds of literals are reported as candidates: JSX text, string values
+//! of attributes such as `aria-label`, and plain string arguments of
+//! configurable functions such as `alert`. Strings with interpolations or
+//! escape sequences are skipped, as is JSX text right next to an expression,
+//! since their translations would need arguments.
+
+use crate::usages::{syntax, walk_sources};
+use globetrotter_model::diagnostics::Span;
+use globetrotter_model::naming::CaseStyle;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// The functions whose string arguments are extracted, unless configured
+/// otherwise. Rust macros carry their `!`.
+pub const DEFAULT_FUNCTIONS: &[&str] = &["alert", "confirm"];
+
+/// The JSX attributes whose string values are extracted, unless configured
+/// otherwise.
+pub const DEFAULT_ATTRIBUTES: &[&str] = &["aria-label", "alt", "placeholder", "title"];
+
+/// The number of words of a string used for its proposed key.
+const KEY_WORDS: usize = 4;
+
+/// File stems that say nothing about their content, replaced by the name of
+/// their directory in proposed keys.
+const GENERIC_STEMS: &[&str] = &["index", "mod", "lib", "main", "page", "layout"];
+
+/// Where a candidate string appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CandidateKind {
+    /// Text between JSX tags, as in `<h1>Your cart</h1>`.
+    JsxText,
+    /// The string value of a JSX attribute, as in `aria-label="Close"`.
+    Attribute,
+    /// The first argument of a configured function, as in `alert("Saved")`.
+    Argument,
+}
+
+impl std::fmt::Display for CandidateKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::JsxText => "jsx-text",
+            Self::Attribute => "attribute",
+            Self::Argument => "argument",
+        })
+    }
+}
+
+/// A likely user-facing string in a source file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Candidate {
+    /// The path of the file, as found below the scanned directory.
+    pub path: PathBuf,
+    /// The 1-based line of the string.
+    pub line: usize,
+    /// The 1-based column of the string, in characters.
+    pub column: usize,
+    /// The byte range replaced when the string is rewritten, including its
+    /// quotes.
+    pub range: Span,
+    /// Where the string appears.
+    pub kind: CandidateKind,
+    /// The attribute name or function the string belongs to, if any.
+    pub context: Option<String>,
+    /// The text of the string, with JSX whitespace collapsed.
+    pub text: String,
+}
+
+impl std::fmt::Display for Candidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+/// Finds candidate strings in source files.
+#[derive(Debug, Clone)]
+pub struct Extractor {
+    functions: Vec<String>,
+    attributes: Vec<String>,
+}
+
+impl Default for Extractor {
+    fn default() -> Self {
+        Self {
+            functions: DEFAULT_FUNCTIONS.iter().map(ToString::to_string).collect(),
+            attributes: DEFAULT_ATTRIBUTES.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl Extractor {
+    /// Extracts the arguments of [`DEFAULT_FUNCTIONS`] and the values of
+    /// [`DEFAULT_ATTRIBUTES`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the functions whose first string argument is extracted, replacing
+    /// [`DEFAULT_FUNCTIONS`]. Rust macros are named with their `!`.
+    #[must_use]
+    pub fn with_functions(
+        mut self,
+        functions: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.functions = functions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the JSX attributes whose string values are extracted, replacing
+    /// [`DEFAULT_ATTRIBUTES`].
+    #[must_use]
+    pub fn with_attributes(
+        mut self,
+        attributes: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.attributes = attributes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Finds the candidates in one source file. Files in other languages than
+    /// TypeScript, JavaScript, and Rust have none.
+    #[must_use]
+    pub fn candidates(&self, path: &Path, content: &str) -> Vec<Candidate> {
+        let Some(literals) = syntax::literals(path, content, &self.functions, &self.attributes)
+        else {
+            return Vec::new();
+        };
+        literals
+            .into_iter()
+            .map(|literal| {
+                let before = content.get(..literal.range.start).unwrap_or_default();
+                let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+                Candidate {
+                    path: path.to_path_buf(),
+                    line: before.matches('\n').count() + 1,
+                    column: before.get(line_start..).unwrap_or_default().chars().count() + 1,
+                    range: literal.range,
+                    kind: literal.kind,
+                    context: literal.context,
+                    text: literal.text,
+                }
+            })
+            .collect()
+    }
+
+    /// Finds the candidates in every source file below `dirs`, skipping the
+    /// same directories as the usage scan, such as `excluded` generated
+    /// output.
+    #[must_use]
+    pub fn scan(&self, dirs: &[PathBuf], excluded: &BTreeSet<PathBuf>) -> Vec<Candidate> {
+        let mut candidates = Vec::new();
+        walk_sources(dirs, excluded, |path, content| {
+            candidates.extend(self.candidates(path, content));
+        });
+        candidates.sort_by(|a, b| a.path.cmp(&b.path).then(a.range.start.cmp(&b.range.start)));
+        candidates
+    }
+}
+
+/// Proposes a key for `text` found in `path`: the file's stem (or its
+/// directory's name, for stems like `index`) followed by the first few words
+/// of the text, each segment written in `case`.
+///
+/// ```
+/// use globetrotter::extract::propose_key;
+/// use globetrotter_model::naming::CaseStyle;
+/// use std::path::Path;
+///
+/// let key = propose_key(Path::new("src/CartSummary.tsx"), "Your cart is empty!", CaseStyle::Snake);
+/// assert_eq!(key, "cart_summary.your_cart_is_empty");
+/// ```
+#[must_use]
+pub fn propose_key(path: &Path, text: &str, case: CaseStyle) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    // `Button.test.tsx` has the stem `Button.test`.
+    let stem = stem.split('.').next().unwrap_or_default();
+    let scope = if GENERIC_STEMS.contains(&stem) {
+        path.parent().and_then(Path::file_name).map_or_else(
+            || stem.to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    } else {
+        stem.to_string()
+    };
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(KEY_WORDS)
+        .collect();
+    let slug = if words.is_empty() {
+        "text".to_string()
+    } else {
+        case.convert(&words.join(" ").to_lowercase())
+    };
+    let scope = case.convert(&scope);
+    if scope.is_empty() {
+        slug
+    } else {
+        format!("{scope}.{slug}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CandidateKind, Extractor, propose_key};
+    use globetrotter_model::naming::CaseStyle;
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::Path;
+
+    #[test_util::test]
+    fn finds_user_facing_strings_in_jsx_and_rust() {
+        let tsx = indoc! {r#"
+            export function Cart({ count }) {
+              alert("Cart saved");
+              return (
+                <section aria-label="Shopping cart" className="cart">
+                  <h1>
+                    Your cart
+                  </h1>
+                  <p>{count} items</p>
+                  <button title={t("app.close")}>{t("app.close")}</button>
+                  {confirm(`Remove ${count}?`)}
+                </section>
+              );
+            }
+        "#};
+        let extractor = Extractor::new();
+        let found: Vec<_> = extractor
+            .candidates(Path::new("src/Cart.tsx"), tsx)
+            .into_iter()
+            .map(|c| (c.line, c.column, c.kind, c.text))
+            .collect();
+        sim_assert_eq!(
+            have: found,
+            want: vec![
+                (2, 9, CandidateKind::Argument, "Cart saved".to_string()),
+                (4, 25, CandidateKind::Attribute, "Shopping cart".to_string()),
+                (6, 9, CandidateKind::JsxText, "Your cart".to_string()),
+            ]
+        );
+
+        let rust = indoc! {r#"
+            fn view() {
+                label("Welcome back");
+                label("line\n");
+                println!("debug only");
+            }
+        "#};
+        let extractor = Extractor::new().with_functions(["label"]);
+        let found: Vec<_> = extractor
+            .candidates(Path::new("src/view.rs"), rust)
+            .into_iter()
+            .map(|c| (c.kind, c.context, c.text, c.range))
+            .collect();
+        sim_assert_eq!(
+            have: found,
+            want: vec![(
+                CandidateKind::Argument,
+                Some("label".to_string()),
+                "Welcome back".to_string(),
+                22..36,
+            )]
+        );
+    }
+
+    #[test_util::test]
+    fn proposes_keys_from_path_and_text() {
+        sim_assert_eq!(
+            have: propose_key(Path::new("src/cart/index.tsx"), "Proceed to checkout now, please", CaseStyle::Snake),
+            want: "cart.proceed_to_checkout_now"
+        );
+        sim_assert_eq!(
+            have: propose_key(Path::new("src/OrderList.test.tsx"), "…", CaseStyle::Camel),
+            want: "orderList.text"
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! Detection of keys and arguments that generate colliding or invalid
+//! identifiers in typed output targets.
+//!
+//! A generator rejects such keys only once it runs, and then without pointing
+//! at the translation files. Computing every enabled target's identifiers at
+//! lint time reports the problem at each offending key instead.
+//!
+//! TypeScript output quotes keys and argument names as string literals, so
+//! every key is representable there and it contributes no identifiers. The Go
+//! and Python targets have no emitter yet; once they do, their names belong in
+//! [`namespaces`].
+
+use crate::{config::v1 as config, target::Target};
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use globetrotter_model::{
+    Translation, Translations,
+    diagnostics::{DiagnosticExt, FileId, Spanned},
+    lint::{LintCode, is_allowed},
+};
+use std::collections::BTreeMap;
+
+/// A family of identifiers one target generates, all of which must be
+/// distinct and valid.
+struct Namespace {
+    /// The target generating the identifiers.
+    target: Target,
+    /// What an identifier names, e.g. `enum variant`.
+    kind: &'static str,
+    /// Whether the identifiers are generated from argument names, and so only
+    /// need to be distinct within one key.
+    arguments: bool,
+    /// Generates the identifier for a key or argument name.
+    generate: fn(&str) -> String,
+    /// Returns `true` if a generated identifier is usable in the target
+    /// language. Generators escape keywords, so this only fails for names no
+    /// escaping can repair.
+    is_valid: fn(&str) -> bool,
+}
+
+/// The identifier namespaces of the config's enabled typed targets.
+#[cfg(feature = "rust")]
+fn namespaces(config: &config::Config) -> Vec<Namespace> {
+    let mut namespaces = Vec::new();
+    if config.outputs.rust.is_some() {
+        namespaces.push(Namespace {
+            target: Target::Rust,
+            kind: "enum variant",
+            arguments: false,
+            generate: crate::rust::key_to_rust_enum_variant,
+            is_valid: crate::rust::is_valid_identifier,
+        });
+        namespaces.push(Namespace {
+            target: Target::Rust,
+            kind: "field",
+            arguments: true,
+            generate: crate::rust::argument_to_rust_field_name,
+            is_valid: crate::rust::is_valid_identifier,
+        });
+    }
+    namespaces
+}
+
+#[cfg(not(feature = "rust"))]
+fn namespaces(_config: &config::Config) -> Vec<Namespace> {
+    Vec::new()
+}
+
+/// A key or argument that generates an identifier.
+struct Source<'a> {
+    /// The key, or the key whose arguments include the argument.
+    key: &'a Spanned<String>,
+    translation: &'a Translation,
+    /// The key or argument name the identifier is generated from.
+    name: &'a str,
+}
+
+/// Checks the identifiers every enabled typed target of `config` generates
+/// for `translations`.
+///
+/// Reports keys (or arguments of one key) that generate the same identifier,
+/// labelled at every colliding key, and identifiers that are invalid in the
+/// target language even once escaped.
+#[must_use]
+pub fn lint_identifiers(
+    config: &config::Config,
+    translations: &Translations,
+    strict: bool,
+) -> Vec<Diagnostic<FileId>> {
+    let mut diagnostics = Vec::new();
+    for namespace in namespaces(config) {
+        if namespace.arguments {
+            for (key, translation) in &translations.0 {
+                let sources = translation.arguments.keys().map(|name| Source {
+                    key,
+                    translation,
+                    name,
+                });
+                check(&namespace, sources, strict, &mut diagnostics);
+            }
+        } else {
+            let sources = translations.0.iter().map(|(key, translation)| Source {
+                key,
+                translation,
+                name: key.as_ref(),
+            });
+            check(&namespace, sources, strict, &mut diagnostics);
+        }
+    }
+    diagnostics
+}
+
+/// Reports collisions and invalid identifiers among `sources`.
+fn check<'a>(
+    namespace: &Namespace,
+    sources: impl Iterator<Item = Source<'a>>,
+    strict: bool,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    let mut identifiers: BTreeMap<String, Vec<Source<'a>>> = BTreeMap::new();
+    for source in sources {
+        identifiers
+            .entry((namespace.generate)(source.name))
+            .or_default()
+            .push(source);
+    }
+
+    for (identifier, sources) in &identifiers {
+        if sources
+            .iter()
+            .all(|source| is_allowed(&source.translation.allow, LintCode::Identifier))
+        {
+            continue;
+        }
+        let what = format!("{} {} `{identifier}`", namespace.target, namespace.kind);
+        if let [source] = sources.as_slice() {
+            if !(namespace.is_valid)(identifier) {
+                diagnostics.push(invalid(namespace, source, identifier, &what, strict));
+            }
+            continue;
+        }
+
+        let names = sources
+            .iter()
+            .map(|source| format!("`{}`", source.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let (message, labels) = match sources.first() {
+            // Arguments of one key share its span, so they get one label.
+            Some(source) if namespace.arguments => (
+                format!(
+                    "arguments {names} of `{}` generate the same {what}",
+                    source.key.as_ref()
+                ),
+                vec![
+                    Label::primary(source.translation.file_id, source.key.span.clone())
+                        .with_message(format!("{names} generate `{identifier}`")),
+                ],
+            ),
+            _ => (
+                format!("keys {names} generate the same {what}"),
+                sources
+                    .iter()
+                    .map(|source| {
+                        Label::primary(source.translation.file_id, source.key.span.clone())
+                            .with_message(format!("`{}` generates `{identifier}`", source.name))
+                    })
+                    .collect(),
+            ),
+        };
+        diagnostics.push(
+            Diagnostic::warning_or_error(strict)
+                .with_code(LintCode::Identifier)
+                .with_message(message)
+                .with_labels(labels)
+                .with_notes(vec![format!(
+                    "generating {} output fails until all but one are renamed",
+                    namespace.target
+                )]),
+        );
+    }
+}
+
+/// Reports the invalid identifier generated from a single source.
+fn invalid(
+    namespace: &Namespace,
+    source: &Source<'_>,
+    identifier: &str,
+    what: &str,
+    strict: bool,
+) -> Diagnostic<FileId> {
+    let subject = if namespace.arguments {
+        format!("argument `{}` of `{}`", source.name, source.key.as_ref())
+    } else {
+        format!("key `{}`", source.name)
+    };
+    Diagnostic::warning_or_error(strict)
+        .with_code(LintCode::Identifier)
+        .with_message(format!(
+            "{subject} generates the {what}, which is not a valid identifier"
+        ))
+        .with_labels(vec![
+            Label::primary(source.translation.file_id, source.key.span.clone())
+                .with_message(format!("`{}` generates `{identifier}`", source.name)),
+        ])
+}
+
+#[cfg(all(test, feature = "rust"))]
+mod tests {
+    use super::lint_identifiers;
+    use crate::config::v1 as config;
+    use globetrotter_model::Translations;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn reports_colliding_and_invalid_identifiers() {
+        let raw = indoc::indoc! {r#"
+            [a.b-c]
+            en = "One"
+
+            [a.b_c]
+            en = "Two"
+
+            ["price€"]
+            en = "Price"
+
+            [greeting]
+            en = "Hi {{first-name}} {{first_name}}"
+            arguments = { first-name = "string", first_name = "string", "e-mail!" = "string" }
+
+            [legacy-key]
+            en = "Old"
+            allow = ["identifier"]
+
+            [legacy_key]
+            en = "Old"
+            allow = ["identifier"]
+        "#};
+        let mut parse_diagnostics = vec![];
+        let translations = Translations::from_str(raw, 0, false, &mut parse_diagnostics)?;
+        let config = config::Config::new("app").with_outputs(config::Outputs::new().with_rust(
+            crate::rust::OutputConfig::new([std::path::PathBuf::from("translations.rs")]),
+        ));
+
+        let diagnostics = lint_identifiers(&config, &translations, false);
+        let have: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let labels: Vec<_> = diagnostic
+                    .labels
+                    .iter()
+                    .filter_map(|label| raw.get(label.range.clone()))
+                    .filter_map(|text| text.lines().next())
+                    .collect();
+                (diagnostic.message.clone(), labels)
+            })
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                (
+                    "keys `a.b-c`, `a.b_c` generate the same Rust enum variant `ABC`".to_string(),
+                    vec!["[a.b-c]", "[a.b_c]"],
+                ),
+                (
+                    "key `price€` generates the Rust enum variant `Price€`, which is not a valid identifier"
+                        .to_string(),
+                    vec!["[\"price€\"]"],
+                ),
+                (
+                    "argument `e-mail!` of `greeting` generates the Rust field `e_mail!`, which is not a valid identifier"
+                        .to_string(),
+                    vec!["[greeting]"],
+                ),
+                (
+                    "arguments `first-name`, `first_name` of `greeting` generate the same Rust field `first_name`"
+                        .to_string(),
+                    vec!["[greeting]"],
+                ),
+            ]
+        );
+    }
+}
@@ -49,20 +49,88 @@ fn human_readable_bytes(len: usize) -> String {
     human_bytes::human_bytes(len as f64)
 }
 
+/// A language, or a pseudo-locale generated from one, written to JSON.
+#[derive(Debug, Clone, Copy)]
+enum Locale<'a> {
+    /// A configured language.
+    Language(model::Language),
+    /// A pseudo-locale of the source language.
+    Pseudo {
+        source: model::Language,
+        code: &'a str,
+        options: &'a model::pseudo::Options,
+    },
+}
+
+impl std::fmt::Display for Locale<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Language(language) => language.fmt(f),
+            Self::Pseudo { code, .. } => f.write_str(code),
+        }
+    }
+}
+
+/// Serializes one locale of `translations` as pretty-printed JSON.
+fn serialize_locale(
+    translations: &model::Translations,
+    locale: Locale<'_>,
+    settings: &Settings,
+) -> Result<Vec<u8>, JsonOutputError> {
+    let template_engine = settings
+        .template_engine
+        .as_ref()
+        .map(|tpl| tpl.as_ref().clone());
+    let mut json = Vec::new();
+    let mut writer = std::io::BufWriter::new(std::io::Cursor::new(&mut json));
+    match locale {
+        Locale::Language(language) => {
+            translations.write_translations_json(
+                language,
+                template_engine,
+                settings.strict,
+                &mut writer,
+            )?;
+        }
+        Locale::Pseudo {
+            source,
+            code,
+            options,
+        } => {
+            translations.write_pseudo_translations_json(
+                source,
+                code,
+                options,
+                template_engine,
+                settings.strict,
+                &mut writer,
+            )?;
+        }
+    }
+    let _ = writer.flush();
+    drop(writer);
+    Ok(json)
+}
+
 impl executor::Executor {
     fn resolve_json_output_path(
         &self,
         path: &Path,
-        language: model::Language,
+        locale: Locale<'_>,
     ) -> Result<PathBuf, JsonOutputError> {
         #[derive(Debug, serde::Serialize)]
         struct TemplateData {
-            language: model::Language,
+            language: String,
         }
         let template = path.to_string_lossy().to_string();
         let path = self
             .handlebars
-            .render_template(&template, &TemplateData { language })
+            .render_template(
+                &template,
+                &TemplateData {
+                    language: locale.to_string(),
+                },
+            )
             .map_err(|source| JsonOutputError::Template { template, source })?;
         Ok(path.into())
     }
@@ -75,11 +143,27 @@ impl executor::Executor {
     ) -> Result<(), JsonOutputError> {
         let config = &config_file.config;
 
-        // Resolve every configured output template for every language.
-        let json_output_paths = config.languages.iter().flat_map(|language| {
+        // The pseudo-locale is written alongside the configured languages.
+        let pseudo = config
+            .pseudo
+            .as_ref()
+            .zip(config.source_language())
+            .map(|(pseudo, source)| (source, pseudo.locale(source), &pseudo.options));
+        let locales = config
+            .languages
+            .iter()
+            .map(|language| Locale::Language(**language))
+            .chain(pseudo.iter().map(|(source, code, options)| Locale::Pseudo {
+                source: *source,
+                code,
+                options,
+            }));
+
+        // Resolve every configured output template for every locale.
+        let json_output_paths = locales.flat_map(|locale| {
             config.outputs.json.iter().cloned().map(move |config| {
-                let output_path = self.resolve_json_output_path(&config.path, **language)?;
-                Ok::<_, JsonOutputError>((config, output_path, language))
+                let output_path = self.resolve_json_output_path(&config.path, locale)?;
+                Ok::<_, JsonOutputError>((config, output_path, locale))
             })
         });
         stream::iter(json_output_paths)
@@ -88,29 +172,14 @@ impl executor::Executor {
             .try_for_each(|res| {
                 let translations = Arc::clone(translations);
                 async move {
-                    let (_json_config, json_output_path, language) = res;
+                    let (_json_config, json_output_path, locale) = res;
                     let json_output_path = executor::resolve_path(
                         config_file.config_dir.as_deref(),
                         &json_output_path,
                     );
 
-                    // Serialize one language once for both writing and sizing.
-                    let mut json = Vec::new();
-                    {
-                        let mut writer = std::io::BufWriter::new(std::io::Cursor::new(&mut json));
-                        translations.write_translations_json(
-                            **language,
-                            settings
-                                .template_engine
-                                .as_ref()
-                                .map(|tpl| tpl.as_ref().clone()),
-                            settings.strict,
-                            &mut writer,
-                        )?;
-                        let _ = writer.flush();
-                    }
-
-                    let json = Arc::new(json);
+                    // Serialize one locale once for both writing and sizing.
+                    let json = Arc::new(serialize_locale(&translations, locale, settings)?);
 
                     // Compute the gzipped display size off the async runtime.
                     // Compression is CPU-bound and can run alongside the write.
@@ -140,7 +209,7 @@ impl executor::Executor {
                     if dry_run {
                         println!(
                             "{} {} {}",
-                            self.logger.language_log_prefix(&config.name, **language),
+                            self.logger.language_log_prefix(&config.name, locale),
                             self.logger.dry_run_would_write(&json_output_path),
                             format!(
                                 "({}, {} gzipped)",
@@ -162,7 +231,7 @@ impl executor::Executor {
                         };
                         println!(
                             "{} wrote {} ({}, {} gzipped)",
-                            self.logger.language_log_prefix(&config.name, **language),
+                            self.logger.language_log_prefix(&config.name, locale),
                             displayed_path,
                             human_readable_bytes(json.len()),
                             human_readable_bytes(num_bytes_gzip).bold().magenta()
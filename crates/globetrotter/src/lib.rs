@@ -49,17 +49,33 @@ pub mod diagnostics;
 pub mod error;
 /// Orchestration of translation loading, validation, and output generation.
 pub mod executor;
+/// Detection of hard-coded, user-facing strings in source code.
+#[cfg(feature = "tree-sitter")]
+pub mod extract;
 /// Gzip size estimation for generated JSON outputs.
 pub mod gzip;
+/// Detection of keys that generate colliding or invalid target identifiers.
+pub mod identifiers;
 /// JSON translation output generation.
 pub mod json;
 /// LLM-judged translation-consistency review during linting.
 #[cfg(feature = "llm-judge")]
 pub mod llm_judge;
+/// Detection of translation keys used in source code but never defined.
+pub mod missing_keys;
 /// Progress logging and output path formatting.
 pub mod progress;
+/// Offline spell checking of translations against Hunspell dictionaries.
+pub mod spelling;
+/// Detection of translations whose source-language text changed since review.
+pub mod stale;
 /// Code generation targets and their per-target output errors.
 pub mod target;
+/// Recorded decisions about LLM judge findings.
+#[cfg(feature = "llm-judge")]
+pub mod triage;
+/// Finding references to translation keys in source code.
+pub mod usages;
 
 #[cfg(feature = "typescript")]
 pub use globetrotter_typescript as typescript;
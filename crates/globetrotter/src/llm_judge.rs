@@ -9,17 +9,20 @@
 //! never as warnings or errors: the judge is a review aid tuned for recall — a
 //! finding is a suggestion for inspection, not a pass/fail signal.
 
+use crate::config::v1::{ConfigFile, Configs};
 use crate::executor::{LlmJudgeEffort, LlmJudgeParams};
+use crate::triage::{self, Triage};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
-use globetrotter_llm_judge::{Effort, Judge, KeyInput, LanguageText, Options};
+use globetrotter_llm_judge::{Effort, Judge, KeyInput, Options};
 use globetrotter_model::{
-    Translations,
+    Translation, Translations,
     diagnostics::{FileId, Span},
-    lint::{LintCode, is_allowed},
+    lint::{LintCode, handlebars_variables, is_allowed},
 };
 use std::collections::HashMap;
+use std::path::PathBuf;
 
-pub use globetrotter_llm_judge::{Error, Stats};
+pub use globetrotter_llm_judge::{Error, LanguageText, Stats, cache, eval, translate};
 
 /// Drives an [`indicatif::ProgressBar`] from judging progress.
 pub struct BarProgress(
@@ -36,13 +39,9 @@ impl globetrotter_llm_judge::Progress for BarProgress {
     }
 }
 
-/// Creates a judge from executor parameters.
-///
-/// # Errors
-///
-/// Returns an error if the verdict cache cannot be created.
-pub fn judge(params: &LlmJudgeParams) -> Result<Judge, Error> {
-    Judge::new(Options {
+/// Converts executor parameters into the judge crate's options.
+fn options(params: &LlmJudgeParams) -> Options {
+    Options {
         base_url: params.base_url.clone(),
         model: params.model.clone(),
         api_key_env: params.api_key_env.clone(),
@@ -57,7 +56,60 @@ pub fn judge(params: &LlmJudgeParams) -> Result<Judge, Error> {
         min_confidence: params.min_confidence,
         cache_dir: params.cache_dir.clone(),
         cache_capacity: params.cache_capacity,
-    })
+        batch_tokens: params.batch_tokens,
+        max_retries: params.max_retries,
+        requests_per_minute: params.requests_per_minute,
+        tokens_per_minute: params.tokens_per_minute,
+        token_budget: params.token_budget,
+    }
+}
+
+/// Creates a judge from executor parameters.
+///
+/// # Errors
+///
+/// Returns an error if the verdict cache cannot be created.
+pub fn judge(params: &LlmJudgeParams) -> Result<Judge, Error> {
+    Judge::new(options(params))
+}
+
+/// Creates a machine translator using the endpoint settings of executor
+/// parameters.
+#[must_use]
+pub fn translator(params: &LlmJudgeParams) -> translate::Translator {
+    translate::Translator::new(options(params))
+}
+
+/// Judges the labelled `cases` with a judge created from `params`, showing
+/// progress, and scores the findings against the labels.
+///
+/// # Errors
+///
+/// Returns an error if the judge cannot be created or judging aborts.
+pub async fn evaluate(
+    params: &LlmJudgeParams,
+    cases: &[eval::Case],
+) -> Result<eval::Report, Error> {
+    let judge = judge(params)?;
+    let bar = judge_progress_bar();
+    let report = eval::run(&judge, cases, &BarProgress(bar.clone())).await;
+    bar.finish_and_clear();
+    report
+}
+
+/// Returns `true` if a machine translation uses exactly the template variables
+/// of its source text.
+///
+/// The translator already restores placeholders verbatim; this also catches a
+/// translation that no longer compiles, for example because the model moved a
+/// placeholder out of its block helper. A source that does not compile itself
+/// cannot be compared and passes.
+#[must_use]
+pub fn keeps_variables(source: &str, translation: &str) -> bool {
+    match handlebars_variables(source) {
+        Some(variables) => handlebars_variables(translation) == Some(variables),
+        None => true,
+    }
 }
 
 /// Source spans for one key, used to attach diagnostics back to the file.
@@ -65,74 +117,279 @@ struct KeySpans<'a> {
     file_id: FileId,
     key_span: Span,
     language_spans: HashMap<&'a str, Span>,
+    /// The content hash of the key's strings, matched against triage entries.
+    hash: String,
 }
 
-/// Judges translations for cross-language drift.
+/// The judge input for one translation: its strings, one per language.
+fn key_input<'a>(key: &'a str, translation: &'a Translation) -> KeyInput<'a> {
+    KeyInput {
+        key,
+        languages: translation
+            .language
+            .iter()
+            .map(|(language, text)| LanguageText {
+                language: language.code(),
+                text: text.as_ref().as_str(),
+            })
+            .collect(),
+    }
+}
+
+/// The content hash of a translation's strings, as recorded in triage files.
+#[must_use]
+pub fn content_hash(translation: &Translation) -> String {
+    key_input("", translation).content_hash()
+}
+
+/// The triage file of `config_file`, resolved against its directory.
+#[must_use]
+pub fn triage_path(config_file: &ConfigFile<FileId>) -> Option<PathBuf> {
+    config_file
+        .config
+        .lint
+        .llm_triage
+        .as_ref()
+        .map(|path| crate::executor::resolve_path(config_file.config_dir.as_deref(), path.as_ref()))
+}
+
+/// Reads the triage file of `config_file`; without one, nothing is triaged.
 ///
-/// A note diagnostic is passed to `emit` for every flagged language as its
-/// verdict arrives. The returned [`Stats`] describe the complete run.
+/// # Errors
+///
+/// Returns an error if the triage file cannot be read or is invalid.
+pub async fn load_triage(config_file: &ConfigFile<FileId>) -> Result<Triage, triage::Error> {
+    match triage_path(config_file) {
+        Some(path) => Triage::load(&path).await,
+        None => Ok(Triage::default()),
+    }
+}
+
+/// The judge inputs for `translations`, with the spans of every judged key.
 ///
 /// Keys that suppress the `llm-drift` code via their `allow` list, and keys
 /// with fewer than two languages, are skipped.
-///
-/// # Errors
-///
-/// Returns an error if the endpoint keeps failing or the verdict cache cannot
-/// be written.
-pub async fn stream(
-    judge: &Judge,
-    translations: &Translations,
-    progress: &dyn globetrotter_llm_judge::Progress,
-    emit: &mut dyn FnMut(Diagnostic<FileId>),
-) -> Result<Stats, Error> {
+fn inputs(translations: &Translations) -> (Vec<KeyInput<'_>>, HashMap<&str, KeySpans<'_>>) {
     let mut inputs: Vec<KeyInput<'_>> = Vec::new();
     let mut spans: HashMap<&str, KeySpans<'_>> = HashMap::new();
 
     for (key, translation) in translations {
-        if is_allowed(&translation.allow, LintCode::LlmDrift) {
-            continue;
-        }
-
-        let mut languages: Vec<LanguageText<'_>> = Vec::new();
-        let mut language_spans: HashMap<&str, Span> = HashMap::new();
-        for (language, text) in &translation.language {
-            let code: &'static str = (*language).into();
-            languages.push(LanguageText {
-                language: code,
-                text: text.as_ref().as_str(),
-            });
-            language_spans.insert(code, text.span.clone());
-        }
-        if languages.len() < 2 {
+        if is_allowed(&translation.allow, LintCode::LlmDrift) || translation.language.len() < 2 {
             continue;
         }
 
-        let key_str = key.as_ref().as_str();
+        let input = key_input(key.as_ref().as_str(), translation);
+        let language_spans = translation
+            .language
+            .iter()
+            .map(|(language, text)| (language.code(), text.span.clone()))
+            .collect();
         spans.insert(
-            key_str,
+            input.key,
             KeySpans {
                 file_id: translation.file_id,
                 key_span: key.span.clone(),
                 language_spans,
+                hash: input.content_hash(),
             },
         );
-        inputs.push(KeyInput {
-            key: key_str,
-            languages,
-        });
+        inputs.push(input);
     }
+    (inputs, spans)
+}
 
+/// Judges translations for cross-language drift.
+///
+/// A note diagnostic is passed to `emit` for every flagged language as its
+/// verdict arrives, except for findings `triage` dismisses while the key's
+/// strings are unchanged. Returns the [`Stats`] of the complete run and the
+/// number of dismissed findings.
+///
+/// Keys that suppress the `llm-drift` code via their `allow` list, and keys
+/// with fewer than two languages, are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the endpoint keeps failing or the verdict cache cannot
+/// be written.
+pub async fn stream(
+    judge: &Judge,
+    translations: &Translations,
+    triage: &Triage,
+    progress: &dyn globetrotter_llm_judge::Progress,
+    emit: &mut dyn FnMut(Diagnostic<FileId>),
+) -> Result<(Stats, usize), Error> {
+    let (inputs, spans) = inputs(translations);
     if inputs.is_empty() {
-        return Ok(Stats::default());
+        return Ok((Stats::default(), 0));
     }
 
-    judge
+    let mut dismissed = 0;
+    let stats = judge
         .judge(&inputs, progress, &mut |finding| {
-            if let Some(diagnostic) = diagnostic_for(&spans, &finding) {
-                emit(diagnostic);
+            let Some(key_spans) = spans.get(finding.key.as_str()) else {
+                return;
+            };
+            let decision = triage.decision(&finding.key, &finding.language, &key_spans.hash);
+            if decision.is_some_and(|entry| entry.status == triage::Status::Dismissed) {
+                dismissed += 1;
+                return;
             }
+            emit(diagnostic_for(key_spans, &finding, decision));
         })
-        .await
+        .await?;
+    Ok((stats, dismissed))
+}
+
+/// A finding of the judge, located in its translation file, with the
+/// decision recorded for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewFinding {
+    /// The index of the config defining the key.
+    pub config: usize,
+    /// The translation file defining the key.
+    pub path: PathBuf,
+    /// The prefix the file's keys are loaded under.
+    pub key_prefix: String,
+    /// The full key, including the file's key prefix.
+    pub key: String,
+    /// The flagged language code.
+    pub language: String,
+    /// The model's explanation of what differs.
+    pub problem: String,
+    /// The model's self-reported certainty, from 0.0 to 1.0.
+    pub confidence: f64,
+    /// The current text of the flagged language.
+    pub current: String,
+    /// The model's corrected text, if any.
+    pub suggestion: Option<String>,
+    /// The content hash of the key's strings.
+    pub hash: String,
+    /// The decision recorded in the config's triage file for these strings.
+    pub decision: Option<triage::Entry>,
+}
+
+impl crate::executor::Executor {
+    /// Loads one config's translations for review, with the path and key
+    /// prefix of each file. Diagnostics are not emitted.
+    async fn review_translations(
+        &self,
+        config_file: &ConfigFile<FileId>,
+    ) -> Result<(Translations, HashMap<FileId, (PathBuf, String)>), crate::error::Error> {
+        let mut diagnostics = Vec::new();
+        let loaded = self
+            .load_translations(config_file, self.lint_strict(), &mut diagnostics)
+            .await?;
+        let files = loaded
+            .iter()
+            .map(|(input, path, file_id, _, _)| {
+                let key_prefix =
+                    crate::executor::key_prefix(input, path, config_file.config_dir.as_deref());
+                (*file_id, (path.clone(), key_prefix))
+            })
+            .collect();
+        let mut translations = crate::executor::combine_translations(loaded, &mut diagnostics);
+        crate::executor::limit_keys(self.max_keys, &mut translations);
+        Ok((translations, files))
+    }
+
+    /// Judges every config's translations and returns all findings with their
+    /// triage decisions, ordered by file, key, and language.
+    ///
+    /// Meant to run after [`Self::lint`] with the same parameters: verdicts
+    /// are then answered from the cache without new requests. Load and lint
+    /// diagnostics are not emitted again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a translation or triage file cannot be read or
+    /// parsed, the endpoint keeps failing, or the verdict cache cannot be
+    /// written.
+    pub async fn llm_findings(
+        &self,
+        configs: &Configs<FileId>,
+        params: &LlmJudgeParams,
+    ) -> Result<Vec<ReviewFinding>, crate::error::Error> {
+        let judge = judge(params)?;
+        let mut reviewed = Vec::new();
+        for (index, config_file) in configs.iter().enumerate() {
+            let triage = load_triage(config_file).await?;
+            let (translations, files) = self.review_translations(config_file).await?;
+            let (inputs, spans) = inputs(&translations);
+            if inputs.is_empty() {
+                continue;
+            }
+            let mut findings = Vec::new();
+            judge
+                .judge(&inputs, &(), &mut |finding| findings.push(finding))
+                .await?;
+            for finding in findings {
+                let (Some(key_spans), Some(translation)) = (
+                    spans.get(finding.key.as_str()),
+                    translation(&translations, &finding.key),
+                ) else {
+                    continue;
+                };
+                let current = translation
+                    .language
+                    .iter()
+                    .find(|(language, _)| language.code() == finding.language)
+                    .map(|(_, text)| text.as_ref().clone());
+                let (Some(current), Some((path, key_prefix))) =
+                    (current, files.get(&translation.file_id))
+                else {
+                    continue;
+                };
+                reviewed.push(ReviewFinding {
+                    config: index,
+                    path: path.clone(),
+                    key_prefix: key_prefix.clone(),
+                    decision: triage
+                        .decision(&finding.key, &finding.language, &key_spans.hash)
+                        .cloned(),
+                    hash: key_spans.hash.clone(),
+                    key: finding.key,
+                    language: finding.language,
+                    problem: finding.problem,
+                    confidence: finding.confidence,
+                    current,
+                    suggestion: finding.suggestion,
+                });
+            }
+        }
+        reviewed
+            .sort_by(|a, b| (&a.path, &a.key, &a.language).cmp(&(&b.path, &b.key, &b.language)));
+        Ok(reviewed)
+    }
+
+    /// The content hash of `key`'s strings and the index of the config
+    /// defining it, or `None` if no config defines the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a translation file cannot be read or parsed.
+    pub async fn llm_content_hash(
+        &self,
+        configs: &Configs<FileId>,
+        key: &str,
+    ) -> Result<Option<(usize, String)>, crate::error::Error> {
+        for (index, config_file) in configs.iter().enumerate() {
+            let (translations, _) = self.review_translations(config_file).await?;
+            if let Some(translation) = translation(&translations, key) {
+                return Ok(Some((index, content_hash(translation))));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// The translation of `key`.
+fn translation<'a>(translations: &'a Translations, key: &str) -> Option<&'a Translation> {
+    translations
+        .0
+        .iter()
+        .find(|(candidate, _)| candidate.as_ref() == key)
+        .map(|(_, translation)| translation)
 }
 
 /// The finding's confidence as a color-coded percent badge: red when the model
@@ -160,8 +417,10 @@ impl crate::executor::Executor {
     pub(crate) async fn stream_llm_judge(
         &self,
         judge: &Judge,
+        config_file: &ConfigFile<FileId>,
         translations: &std::sync::Arc<Translations>,
     ) -> Result<(), crate::error::Error> {
+        let triage = load_triage(config_file).await?;
         let bar = judge_progress_bar();
         let progress = BarProgress(bar.clone());
         let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Diagnostic<FileId>>();
@@ -173,7 +432,7 @@ impl crate::executor::Executor {
             let mut sink = |diagnostic| {
                 let _ = tx.send(diagnostic);
             };
-            stream(judge, translations.as_ref(), &progress, &mut sink).await
+            stream(judge, translations.as_ref(), &triage, &progress, &mut sink).await
         };
         let drain_future = async {
             // In a terminal, print findings above the live bar; otherwise the
@@ -189,17 +448,22 @@ impl crate::executor::Executor {
             }
             Ok::<_, crate::error::Error>(())
         };
-        let (stats, drained) = tokio::join!(judge_future, drain_future);
-        let stats = stats?;
+        let (judged, drained) = tokio::join!(judge_future, drain_future);
+        let (stats, dismissed) = judged?;
         drained?;
 
         bar.finish_and_clear();
         tracing::info!(
             judged = stats.judged,
             cached = stats.cached,
+            batched = stats.batched,
             failed = stats.failed,
+            skipped = stats.skipped,
             flagged = stats.flagged,
             suppressed = stats.suppressed,
+            dismissed,
+            tokens = stats.tokens,
+            retries = stats.retries,
             "llm judge finished"
         );
         Ok(())
@@ -226,11 +490,10 @@ fn judge_progress_bar() -> indicatif::ProgressBar {
 /// Builds a note diagnostic for one finding, pointing at the flagged language's
 /// translation string.
 fn diagnostic_for(
-    spans: &HashMap<&str, KeySpans<'_>>,
+    key_spans: &KeySpans<'_>,
     finding: &globetrotter_llm_judge::Finding,
-) -> Option<Diagnostic<FileId>> {
-    let key_spans = spans.get(finding.key.as_str())?;
-
+    decision: Option<&triage::Entry>,
+) -> Diagnostic<FileId> {
     let label = match key_spans.language_spans.get(finding.language.as_str()) {
         Some(span) => Label::primary(key_spans.file_id, span.clone())
             .with_message(format!("`{}` translation", finding.language)),
@@ -239,16 +502,26 @@ fn diagnostic_for(
         }
     };
 
-    Some(
-        Diagnostic::note()
-            .with_code(LintCode::LlmDrift)
-            .with_message(format!(
-                "`{}`: `{}` may tell users something different ({}): {}",
-                finding.key,
-                finding.language,
-                confidence_badge(finding.confidence),
-                finding.problem
-            ))
-            .with_labels(vec![label]),
-    )
+    let mut notes: Vec<String> = finding
+        .suggestion
+        .iter()
+        .map(|suggestion| format!("suggested: {suggestion:?}"))
+        .collect();
+    if let Some(entry) = decision {
+        notes.push(match &entry.note {
+            Some(note) => format!("{}: {note}", entry.status),
+            None => entry.status.to_string(),
+        });
+    }
+    Diagnostic::note()
+        .with_code(LintCode::LlmDrift)
+        .with_message(format!(
+            "`{}`: `{}` may tell users something different ({}): {}",
+            finding.key,
+            finding.language,
+            confidence_badge(finding.confidence),
+            finding.problem
+        ))
+        .with_labels(vec![label])
+        .with_notes(notes)
 }
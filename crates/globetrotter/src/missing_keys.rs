@@ -0,0 +1,128 @@
+//! Detection of translation keys used in source code but never defined.
+//!
+//! Only string literals passed to a translation call, such as `t("app.title")`
+//! (see [`Scanner::with_calls`](crate::usages::Scanner::with_calls)), are
+//! considered: any other string could be anything.
+
+use crate::usages::{Usage, UsageKind};
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use globetrotter_model::{
+    diagnostics::{DiagnosticExt, FileId},
+    lint::LintCode,
+};
+use std::collections::BTreeSet;
+
+/// Defined keys more than this many edits away from a missing key are not
+/// suggested.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// A key passed to a translation call but not defined in any translation file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingKey {
+    /// Where the key is used.
+    pub usage: Usage,
+    /// The defined key closest to the used one, if one is close.
+    pub suggestion: Option<String>,
+}
+
+/// Returns `true` if `text` could be a key, rather than prose passed to a
+/// gettext-style call.
+fn is_key_like(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
+/// The defined key with the smallest edit distance to `key`, if it is within
+/// [`MAX_SUGGESTION_DISTANCE`]. Ties go to the alphabetically first key.
+fn suggest(key: &str, defined: &BTreeSet<&str>) -> Option<String> {
+    defined
+        .iter()
+        .map(|candidate| (strsim::levenshtein(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Finds translation calls among `usages` whose key is not in `defined`.
+#[must_use]
+pub fn find_missing_keys<'a>(
+    defined: impl IntoIterator<Item = &'a str>,
+    usages: &[Usage],
+) -> Vec<MissingKey> {
+    let defined: BTreeSet<&str> = defined.into_iter().collect();
+    usages
+        .iter()
+        .filter(|usage| usage.kind == UsageKind::Call)
+        .filter(|usage| is_key_like(&usage.text) && !defined.contains(usage.text.as_str()))
+        .map(|usage| MissingKey {
+            usage: usage.clone(),
+            suggestion: suggest(&usage.text, &defined),
+        })
+        .collect()
+}
+
+impl MissingKey {
+    /// The diagnostic for this key, labelled in its source file, which is
+    /// registered as `file_id`.
+    #[must_use]
+    pub fn diagnostic(&self, file_id: FileId, strict: bool) -> Diagnostic<FileId> {
+        let label = match &self.suggestion {
+            Some(suggestion) => format!("did you mean `{suggestion}`?"),
+            None => "used here".to_string(),
+        };
+        Diagnostic::warning_or_error(strict)
+            .with_code(LintCode::MissingKey)
+            .with_message(format!(
+                "translation key `{}` is not defined",
+                self.usage.text
+            ))
+            .with_labels(vec![
+                Label::primary(file_id, self.usage.range.clone()).with_message(label),
+            ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MissingKey, find_missing_keys};
+    use crate::usages::{Usage, UsageKind};
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::PathBuf;
+
+    fn usage(kind: UsageKind, text: &str) -> Usage {
+        Usage {
+            path: PathBuf::from("app.ts"),
+            line: 1,
+            column: 1,
+            range: 0..0,
+            kind,
+            text: text.to_string(),
+        }
+    }
+
+    #[test_util::test]
+    fn reports_undefined_call_keys_with_suggestions() {
+        let usages = [
+            usage(UsageKind::Call, "app.title"),
+            usage(UsageKind::Call, "app.titel"),
+            usage(UsageKind::Call, "checkout.pay"),
+            usage(UsageKind::Call, "Welcome back!"),
+            usage(UsageKind::Literal, "app.other"),
+        ];
+        sim_assert_eq!(
+            have: find_missing_keys(["app.title", "app.subtitle"], &usages),
+            want: vec![
+                MissingKey {
+                    usage: usage(UsageKind::Call, "app.titel"),
+                    suggestion: Some("app.title".to_string()),
+                },
+                MissingKey {
+                    usage: usage(UsageKind::Call, "checkout.pay"),
+                    suggestion: None,
+                },
+            ]
+        );
+    }
+}
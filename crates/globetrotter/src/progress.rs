@@ -95,14 +95,15 @@ impl Logger {
         )
     }
 
-    /// Formats the aligned log prefix for a config name and language.
+    /// Formats the aligned log prefix for a config name and a language or
+    /// pseudo-locale.
     #[must_use]
-    pub fn language_log_prefix(&self, name: &str, language: Language) -> String {
+    pub fn language_log_prefix(&self, name: &str, language: impl std::fmt::Display) -> String {
         format!(
             "{}{}",
             pad_left(&name.green().to_string(), self.longest_config_name, ' '),
             pad_right(
-                &format!("[{}]", language.to_string().to_lowercase().bright_blue()),
+                &format!("[{}]", language.to_string().bright_blue()),
                 self.longest_target_name + 2,
                 ' '
             )
@@ -0,0 +1,623 @@
+//! A reader for the subset of Hunspell dictionaries needed to check words.
+//!
+//! Supports plain roots, prefix and suffix rules (including cross products),
+//! flag aliases, the `FLAG` encodings, `FORBIDDENWORD`, `NEEDAFFIX`, and simple
+//! `COMPOUNDFLAG`/`COMPOUNDBEGIN`/`COMPOUNDMIDDLE`/`COMPOUNDEND` compounding.
+//! Morphological analysis, `REP`/`MAP` tables, and compound rules are not
+//! implemented; suggestions are limited to single edits using the `TRY`
+//! characters.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+type Flag = u32;
+
+/// How flags are spelled in the `.aff` and `.dic` files (`FLAG` directive).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum FlagMode {
+    /// One character per flag (the default).
+    #[default]
+    Char,
+    /// Two characters per flag.
+    Long,
+    /// Comma-separated decimal numbers.
+    Num,
+}
+
+impl FlagMode {
+    fn parse(self, flags: &str) -> Vec<Flag> {
+        match self {
+            Self::Char => flags.chars().map(Flag::from).collect(),
+            Self::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars
+                    .chunks(2)
+                    .map(|pair| {
+                        pair.iter()
+                            .fold(0, |flag: Flag, c| (flag << 16) | (Flag::from(*c) & 0xffff))
+                    })
+                    .collect()
+            }
+            Self::Num => flags
+                .split(',')
+                .filter_map(|flag| flag.trim().parse().ok())
+                .collect(),
+        }
+    }
+
+    fn parse_one(self, flag: &str) -> Option<Flag> {
+        self.parse(flag).first().copied()
+    }
+}
+
+/// One character position of an affix condition.
+#[derive(Clone, Debug)]
+enum CharClass {
+    Any,
+    Set { chars: Vec<char>, negated: bool },
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Set { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// The condition an affix places on the root it attaches to, e.g. `[^aeiou]y`.
+#[derive(Clone, Debug, Default)]
+struct Condition(Vec<CharClass>);
+
+impl Condition {
+    fn parse(condition: &str) -> Self {
+        if condition == "." {
+            return Self::default();
+        }
+        let mut classes = Vec::new();
+        let mut chars = condition.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => classes.push(CharClass::Any),
+                '[' => {
+                    let mut set: Vec<char> = chars.by_ref().take_while(|c| *c != ']').collect();
+                    let negated = set.first() == Some(&'^');
+                    if negated {
+                        set.remove(0);
+                    }
+                    classes.push(CharClass::Set {
+                        chars: set,
+                        negated,
+                    });
+                }
+                c => classes.push(CharClass::Set {
+                    chars: vec![c],
+                    negated: false,
+                }),
+            }
+        }
+        Self(classes)
+    }
+
+    fn matches_start(&self, word: &str) -> bool {
+        let mut chars = word.chars();
+        self.0
+            .iter()
+            .all(|class| chars.next().is_some_and(|c| class.matches(c)))
+    }
+
+    fn matches_end(&self, word: &str) -> bool {
+        let mut chars = word.chars().rev();
+        self.0
+            .iter()
+            .rev()
+            .all(|class| chars.next().is_some_and(|c| class.matches(c)))
+    }
+}
+
+/// A prefix or suffix rule.
+#[derive(Clone, Debug)]
+struct Affix {
+    flag: Flag,
+    cross_product: bool,
+    strip: String,
+    add: String,
+    condition: Condition,
+}
+
+/// A Hunspell dictionary loaded from a `.aff`/`.dic` pair.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    /// Roots and the flags of each homonym.
+    words: HashMap<String, Vec<Vec<Flag>>>,
+    prefixes: Vec<Affix>,
+    suffixes: Vec<Affix>,
+    try_chars: Vec<char>,
+    forbidden: Option<Flag>,
+    need_affix: Option<Flag>,
+    no_suggest: Option<Flag>,
+    only_in_compound: Option<Flag>,
+    compound: Option<Flag>,
+    compound_begin: Option<Flag>,
+    compound_middle: Option<Flag>,
+    compound_end: Option<Flag>,
+    compound_min: usize,
+}
+
+/// Where a part occurs within a compound word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CompoundPosition {
+    Begin,
+    Middle,
+    End,
+}
+
+/// The maximum number of parts a compound word is split into.
+const MAX_COMPOUND_PARTS: usize = 4;
+
+/// Words longer than this (in characters) receive no suggestions; the number
+/// of candidate edits grows with the word length.
+const MAX_SUGGESTION_WORD_LENGTH: usize = 40;
+
+/// Decodes dictionary file contents according to the `.aff` file's `SET`,
+/// which defaults to UTF-8.
+///
+/// Hunspell spells some encodings differently from the WHATWG labels, e.g.
+/// `microsoft-cp1251` for `windows-1251`.
+fn decode(bytes: &[u8], set: Option<&str>) -> std::io::Result<String> {
+    let Some(set) = set else {
+        return Ok(String::from_utf8_lossy(bytes).into_owned());
+    };
+    let label = set.strip_prefix("microsoft-").unwrap_or(set);
+    let encoding = encoding_rs::Encoding::for_label(label.as_bytes()).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("unsupported dictionary encoding `SET {set}`"),
+        )
+    })?;
+    let (text, _, _) = encoding.decode(bytes);
+    Ok(text.into_owned())
+}
+
+/// Finds the `SET` directive, which is always spelled in ASCII.
+fn encoding(aff: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(aff).lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        (fields.next() == Some("SET"))
+            .then(|| fields.next().map(str::to_string))
+            .flatten()
+    })
+}
+
+impl Dictionary {
+    /// Reads a dictionary from its `.aff` and `.dic` files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be read, or the `.aff` file
+    /// declares an encoding that is not supported.
+    pub fn load(aff: &Path, dic: &Path) -> std::io::Result<Self> {
+        let aff = std::fs::read(aff)?;
+        let dic = std::fs::read(dic)?;
+        let encoding = encoding(&aff);
+        Ok(Self::parse(
+            &decode(&aff, encoding.as_deref())?,
+            &decode(&dic, encoding.as_deref())?,
+        ))
+    }
+
+    /// Parses a dictionary from the contents of its `.aff` and `.dic` files.
+    ///
+    /// Unsupported or malformed directives are ignored.
+    #[must_use]
+    pub fn parse(aff: &str, dic: &str) -> Self {
+        let mut dictionary = Self {
+            compound_min: 3,
+            ..Self::default()
+        };
+        let mut mode = FlagMode::default();
+        let mut aliases: Vec<Vec<Flag>> = Vec::new();
+        let mut counted_aliases = false;
+        let mut headers: HashMap<(bool, Flag), bool> = HashMap::new();
+
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => mode = FlagMode::Long,
+                ["FLAG", "num", ..] => mode = FlagMode::Num,
+                ["TRY", chars, ..] => dictionary.try_chars = chars.chars().collect(),
+                // The first `AF` line holds the number of aliases, and every
+                // later one an alias, numbered from 1.
+                ["AF", flags, ..] => {
+                    if counted_aliases {
+                        aliases.push(mode.parse(flags));
+                    } else {
+                        counted_aliases = true;
+                    }
+                }
+                ["FORBIDDENWORD", flag, ..] => dictionary.forbidden = mode.parse_one(flag),
+                ["NEEDAFFIX", flag, ..] => dictionary.need_affix = mode.parse_one(flag),
+                ["NOSUGGEST", flag, ..] => dictionary.no_suggest = mode.parse_one(flag),
+                ["ONLYINCOMPOUND", flag, ..] => dictionary.only_in_compound = mode.parse_one(flag),
+                ["COMPOUNDFLAG", flag, ..] => dictionary.compound = mode.parse_one(flag),
+                ["COMPOUNDBEGIN", flag, ..] => dictionary.compound_begin = mode.parse_one(flag),
+                ["COMPOUNDMIDDLE", flag, ..] => dictionary.compound_middle = mode.parse_one(flag),
+                ["COMPOUNDEND", flag, ..] => dictionary.compound_end = mode.parse_one(flag),
+                ["COMPOUNDMIN", min, ..] => {
+                    dictionary.compound_min = min.parse().unwrap_or(dictionary.compound_min);
+                }
+                [kind @ ("PFX" | "SFX"), flag, rest @ ..] => {
+                    let is_prefix = *kind == "PFX";
+                    let Some(flag) = mode.parse_one(flag) else {
+                        continue;
+                    };
+                    // The first line of each affix class is its header:
+                    // `SFX A Y 3` (cross product, number of rules).
+                    let Some(cross_product) = headers.get(&(is_prefix, flag)).copied() else {
+                        headers.insert((is_prefix, flag), rest.first() == Some(&"Y"));
+                        continue;
+                    };
+                    let [strip, add, rest @ ..] = rest else {
+                        continue;
+                    };
+                    let strip = if *strip == "0" { "" } else { strip };
+                    // Continuation classes (`add/flags`) are not supported.
+                    let add = add.split('/').next().unwrap_or_default();
+                    let add = if add == "0" { "" } else { add };
+                    let affix = Affix {
+                        flag,
+                        cross_product,
+                        strip: strip.to_string(),
+                        add: add.to_string(),
+                        condition: Condition::parse(rest.first().copied().unwrap_or(".")),
+                    };
+                    if is_prefix {
+                        dictionary.prefixes.push(affix);
+                    } else {
+                        dictionary.suffixes.push(affix);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut lines = dic.lines().peekable();
+        // The first line holds the approximate number of entries.
+        if lines
+            .peek()
+            .is_some_and(|first| first.trim().parse::<usize>().is_ok())
+        {
+            lines.next();
+        }
+        for line in lines {
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            if entry.is_empty() {
+                continue;
+            }
+            let (word, flags) = match entry.split_once('/') {
+                Some((word, flags)) => {
+                    let flags = if aliases.is_empty() {
+                        mode.parse(flags)
+                    } else {
+                        flags
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|index| aliases.get(index.checked_sub(1)?))
+                            .cloned()
+                            .unwrap_or_default()
+                    };
+                    (word, flags)
+                }
+                None => (entry, Vec::new()),
+            };
+            dictionary
+                .words
+                .entry(word.to_string())
+                .or_default()
+                .push(flags);
+        }
+        dictionary
+    }
+
+    /// Returns `true` if `word` is spelled correctly.
+    ///
+    /// Capitalized and upper-case spellings of lower-case dictionary words are
+    /// accepted, as at the start of a sentence.
+    #[must_use]
+    pub fn check(&self, word: &str) -> bool {
+        self.check_cased(word, true)
+    }
+
+    /// Checks `word` and its lower- and title-case spellings, trying compounds
+    /// only if `compounds` is set.
+    fn check_cased(&self, word: &str, compounds: bool) -> bool {
+        if word.is_empty() || self.check_exact(word, compounds) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        if lower != word && self.check_exact(&lower, compounds) {
+            return true;
+        }
+        // An all-caps word may be a capitalized noun.
+        let title = title_case(&lower);
+        title != word && self.check_exact(&title, compounds)
+    }
+
+    fn check_exact(&self, word: &str, compounds: bool) -> bool {
+        let has = |flags: &[Flag], flag: Option<Flag>| flag.is_some_and(|f| flags.contains(&f));
+        let standalone = |flags: &[Flag], affixed: bool| {
+            !has(flags, self.forbidden)
+                && (affixed || (!has(flags, self.need_affix) && !has(flags, self.only_in_compound)))
+        };
+        if self.is_forbidden(word) {
+            return false;
+        }
+        self.accepts(word, &standalone) || (compounds && self.check_compound(word, 0))
+    }
+
+    fn is_forbidden(&self, word: &str) -> bool {
+        self.forbidden.is_some_and(|forbidden| {
+            self.words
+                .get(word)
+                .is_some_and(|homonyms| homonyms.iter().all(|flags| flags.contains(&forbidden)))
+        })
+    }
+
+    /// Whether some root of `word` carries flags satisfying `accept`, either
+    /// directly or after removing a prefix, a suffix, or both.
+    fn accepts(&self, word: &str, accept: &dyn Fn(&[Flag], bool) -> bool) -> bool {
+        let root_accepts = |root: &str, required: &[Flag]| {
+            self.words.get(root).is_some_and(|homonyms| {
+                homonyms.iter().any(|flags| {
+                    required.iter().all(|flag| flags.contains(flag))
+                        && accept(flags, !required.is_empty())
+                })
+            })
+        };
+
+        if root_accepts(word, &[]) {
+            return true;
+        }
+
+        for suffix in &self.suffixes {
+            let Some(stem) = strip_suffix(word, &suffix.add) else {
+                continue;
+            };
+            let root = format!("{stem}{}", suffix.strip);
+            if !suffix.condition.matches_end(&root) {
+                continue;
+            }
+            if root_accepts(&root, &[suffix.flag]) {
+                return true;
+            }
+            if !suffix.cross_product {
+                continue;
+            }
+            for prefix in self.prefixes.iter().filter(|prefix| prefix.cross_product) {
+                let Some(rest) = strip_prefix(&root, &prefix.add) else {
+                    continue;
+                };
+                let root = format!("{}{rest}", prefix.strip);
+                if prefix.condition.matches_start(&root)
+                    && root_accepts(&root, &[prefix.flag, suffix.flag])
+                {
+                    return true;
+                }
+            }
+        }
+
+        self.prefixes.iter().any(|prefix| {
+            strip_prefix(word, &prefix.add).is_some_and(|rest| {
+                let root = format!("{}{rest}", prefix.strip);
+                prefix.condition.matches_start(&root) && root_accepts(&root, &[prefix.flag])
+            })
+        })
+    }
+
+    fn check_compound(&self, word: &str, depth: usize) -> bool {
+        if self.compound.is_none() && self.compound_begin.is_none() {
+            return false;
+        }
+        let min = self.compound_min.max(1);
+        let boundaries: Vec<usize> = word.char_indices().map(|(index, _)| index).collect();
+        let position = if depth == 0 {
+            CompoundPosition::Begin
+        } else {
+            CompoundPosition::Middle
+        };
+        for (count, split) in boundaries.iter().enumerate().skip(min) {
+            if boundaries.len() - count < min {
+                break;
+            }
+            let (head, tail) = word.split_at(*split);
+            if !self.is_compound_part(head, position) {
+                continue;
+            }
+            if self.is_compound_part(tail, CompoundPosition::End)
+                || (depth + 2 < MAX_COMPOUND_PARTS && self.check_compound(tail, depth + 1))
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_compound_part(&self, part: &str, position: CompoundPosition) -> bool {
+        let positional = match position {
+            CompoundPosition::Begin => self.compound_begin,
+            CompoundPosition::Middle => self.compound_middle,
+            CompoundPosition::End => self.compound_end,
+        };
+        let accept = |flags: &[Flag], _affixed: bool| {
+            let has = |flag: Option<Flag>| flag.is_some_and(|f| flags.contains(&f));
+            !has(self.forbidden) && (has(self.compound) || has(positional))
+        };
+        // Later parts of compounds are written in lower case even when the
+        // word on its own is capitalized (German "Haus" + "tür").
+        self.accepts(part, &accept)
+            || (position != CompoundPosition::Begin && self.accepts(&title_case(part), &accept))
+    }
+
+    /// Suggests up to `limit` correctly spelled words one edit away from
+    /// `word`, most likely corrections first.
+    ///
+    /// Candidates are generated lazily and only looked up as roots with their
+    /// affixes: splitting each of them into compounds would make suggesting
+    /// far slower than checking with large compounding dictionaries.
+    #[must_use]
+    pub fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        if limit == 0 || chars.is_empty() || chars.len() > MAX_SUGGESTION_WORD_LENGTH {
+            return Vec::new();
+        }
+        let alphabet: Vec<char> = if self.try_chars.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.try_chars.clone()
+        };
+        let edit = |change: &dyn Fn(&mut Vec<char>)| {
+            let mut edited = chars.clone();
+            change(&mut edited);
+            edited.into_iter().collect::<String>()
+        };
+
+        // Swapped neighbours, then replaced, removed, and inserted characters.
+        let swapped = (1..chars.len()).map(|index| edit(&|chars| chars.swap(index - 1, index)));
+        let replaced = (0..chars.len()).flat_map(|index| {
+            alphabet.iter().map(move |replacement| {
+                edit(&|chars| {
+                    if let Some(c) = chars.get_mut(index) {
+                        *c = *replacement;
+                    }
+                })
+            })
+        });
+        let removed = (0..chars.len()).map(|index| {
+            edit(&|chars| {
+                chars.remove(index);
+            })
+        });
+        let inserted = (0..=chars.len()).flat_map(|index| {
+            alphabet
+                .iter()
+                .map(move |inserted| edit(&|chars| chars.insert(index, *inserted)))
+        });
+
+        let mut seen = HashSet::new();
+        let mut suggestions: Vec<String> = swapped
+            .chain(replaced)
+            .chain(removed)
+            .chain(inserted)
+            .filter(|candidate| candidate != word && seen.insert(candidate.clone()))
+            .filter(|candidate| {
+                self.check_cased(candidate, false) && self.is_suggestible(candidate)
+            })
+            .take(limit)
+            .collect();
+
+        // A missing space joins two words.
+        if suggestions.len() < limit {
+            for (index, _) in word.char_indices().skip(1) {
+                let (head, tail) = word.split_at(index);
+                if self.check_cased(head, false) && self.check_cased(tail, false) {
+                    suggestions.push(format!("{head} {tail}"));
+                    if suggestions.len() >= limit {
+                        break;
+                    }
+                }
+            }
+        }
+        suggestions
+    }
+
+    fn is_suggestible(&self, word: &str) -> bool {
+        self.no_suggest.is_none_or(|no_suggest| {
+            self.words
+                .get(word)
+                .is_none_or(|homonyms| homonyms.iter().any(|flags| !flags.contains(&no_suggest)))
+        })
+    }
+}
+
+fn strip_suffix<'a>(word: &'a str, suffix: &str) -> Option<&'a str> {
+    word.strip_suffix(suffix).filter(|stem| !stem.is_empty())
+}
+
+fn strip_prefix<'a>(word: &'a str, prefix: &str) -> Option<&'a str> {
+    word.strip_prefix(prefix).filter(|rest| !rest.is_empty())
+}
+
+fn title_case(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dictionary, decode};
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    const AFF: &str = "SET UTF-8\nTRY esianrtolcdugmphbyfvkwz\n\nSFX S Y 2\nSFX S 0 s [^sxy]\nSFX S y ies [^aeiou]y\n\nPFX U Y 1\nPFX U 0 un .\n\nSFX D Y 1\nSFX D 0 ed [^e]\n\nFORBIDDENWORD !\n";
+    const DIC: &str = "6\nhello\nworld/S\ncity/S\nlock/UD\nthe\nteh/!\n";
+
+    #[test_util::test]
+    fn checks_roots_and_affixes() {
+        let dictionary = Dictionary::parse(AFF, DIC);
+        for word in [
+            "hello", "Hello", "HELLO", "worlds", "cities", "unlocked", "locked",
+        ] {
+            assert!(dictionary.check(word), "{word}");
+        }
+        for word in ["helo", "citys", "unhello", "teh", "worldz"] {
+            assert!(!dictionary.check(word), "{word}");
+        }
+    }
+
+    #[test_util::test]
+    fn suggests_single_edits() {
+        let dictionary = Dictionary::parse(AFF, DIC);
+        sim_assert_eq!(have: dictionary.suggest("helo", 3), want: vec!["hello".to_string()]);
+        sim_assert_eq!(have: dictionary.suggest("wrold", 3), want: vec!["world".to_string()]);
+        sim_assert_eq!(
+            have: dictionary.suggest("helloworld", 3),
+            want: vec!["hello world".to_string()]
+        );
+    }
+
+    #[test_util::test]
+    fn reads_numeric_flag_aliases() {
+        let aff =
+            "FLAG num\nAF 2\nAF 12\nAF 7,12\nSFX 12 Y 1\nSFX 12 0 s .\nSFX 7 Y 1\nSFX 7 0 ed .\n";
+        let dic = "2\nhello/1\nwork/2\n";
+        let dictionary = Dictionary::parse(aff, dic);
+        for word in ["hellos", "works", "worked"] {
+            assert!(dictionary.check(word), "{word}");
+        }
+        assert!(!dictionary.check("helloed"));
+    }
+
+    #[test_util::test]
+    fn checks_compounds() {
+        let aff = "COMPOUNDFLAG X\nCOMPOUNDMIN 3\n";
+        let dic = "2\nHaus/X\nTür/X\n";
+        let dictionary = Dictionary::parse(aff, dic);
+        assert!(dictionary.check("Haustür"));
+        assert!(!dictionary.check("Hausfoo"));
+        // Candidates are not split into compounds, which keeps suggesting fast.
+        sim_assert_eq!(have: dictionary.suggest("Haustüt", 3), want: Vec::<String>::new());
+        sim_assert_eq!(have: dictionary.suggest("Hauss", 3), want: vec!["Haus".to_string()]);
+    }
+
+    #[test_util::test]
+    fn decodes_the_declared_encoding() {
+        sim_assert_eq!(have: decode(b"\xa4uro", Some("ISO8859-15"))?, want: "€uro");
+        sim_assert_eq!(have: decode(b"\xa4uro", Some("ISO8859-1"))?, want: "¤uro");
+        sim_assert_eq!(have: decode(b"\xcc\xe8\xf0", Some("microsoft-cp1251"))?, want: "Мир");
+        sim_assert_eq!(have: decode("Tür".as_bytes(), None)?, want: "Tür");
+        assert!(decode(b"x", Some("ISCII-DEVANAGARI")).is_err());
+    }
+}
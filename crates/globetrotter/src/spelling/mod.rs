@@ -0,0 +1,274 @@
+//! Offline spell checking of translations against Hunspell dictionaries.
+//!
+//! Dictionaries are looked up per language in the configured directory, either
+//! directly (`de_DE.aff`/`de_DE.dic`, `fr.aff`/`fr.dic`) or in a subdirectory
+//! named after the language code (`de/index.dic`). Languages without a
+//! dictionary are not checked. Words in the project word list are always
+//! accepted.
+
+/// Reading and checking words against Hunspell dictionaries.
+pub mod hunspell;
+
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use globetrotter_model::{
+    Language, Translations,
+    diagnostics::{DiagnosticExt, FileId, Span},
+    lint::{LintCode, is_allowed, mask_non_text},
+};
+pub use hunspell::Dictionary;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The maximum number of suggestions shown for one misspelling.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Dictionaries for the languages of one config, plus the project word list.
+#[derive(Debug, Default)]
+pub struct Dictionaries {
+    /// The dictionary of each language that has one.
+    pub languages: HashMap<Language, Dictionary>,
+    /// Project-specific words accepted in every language.
+    pub words: HashSet<String>,
+}
+
+/// Finds the `.aff`/`.dic` pair for `language` in `dir`.
+///
+/// A file stem matches when it is the language code itself or starts with the
+/// code followed by `_` or `-` (`de_DE`, `pt-BR`). A subdirectory named after
+/// the code is searched as well.
+fn find_dictionary(dir: &Path, language: Language) -> std::io::Result<Option<(PathBuf, PathBuf)>> {
+    let code = language.code();
+    let matches_code = |stem: &str| {
+        let stem = stem.to_lowercase();
+        stem == code
+            || stem
+                .strip_prefix(code)
+                .is_some_and(|rest| rest.starts_with(['_', '-']))
+    };
+
+    for (candidate_dir, require_code) in [(dir.to_path_buf(), true), (dir.join(code), false)] {
+        if !candidate_dir.is_dir() {
+            continue;
+        }
+        let mut dics: Vec<PathBuf> = std::fs::read_dir(&candidate_dir)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "dic"))
+            .filter(|path| {
+                !require_code
+                    || path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(matches_code)
+            })
+            .collect();
+        dics.sort();
+        for dic in dics {
+            let aff = dic.with_extension("aff");
+            if aff.is_file() {
+                return Ok(Some((aff, dic)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Parses a word list: one word per line, `#` starts a comment.
+fn parse_words(raw: &str) -> impl Iterator<Item = String> + '_ {
+    raw.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+}
+
+impl Dictionaries {
+    /// Loads the dictionaries of `languages` from `dir`, and the word list at
+    /// `words`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns the path and error of the first dictionary or word list that
+    /// cannot be read.
+    pub fn load(
+        dir: &Path,
+        words: Option<&Path>,
+        languages: impl IntoIterator<Item = Language>,
+    ) -> Result<Self, (PathBuf, std::io::Error)> {
+        let mut dictionaries = Self::default();
+        for language in languages {
+            let found = find_dictionary(dir, language).map_err(|err| (dir.to_path_buf(), err))?;
+            let Some((aff, dic)) = found else {
+                tracing::debug!(language = language.code(), "no spelling dictionary");
+                continue;
+            };
+            let dictionary = Dictionary::load(&aff, &dic).map_err(|err| (dic.clone(), err))?;
+            dictionaries.languages.insert(language, dictionary);
+        }
+        if let Some(path) = words {
+            let raw = std::fs::read_to_string(path).map_err(|err| (path.to_path_buf(), err))?;
+            dictionaries.words.extend(parse_words(&raw));
+        }
+        Ok(dictionaries)
+    }
+
+    fn is_project_word(&self, word: &str) -> bool {
+        self.words.contains(word) || self.words.contains(&word.to_lowercase())
+    }
+}
+
+/// Splits a translation into the words to check and their byte ranges.
+///
+/// Skips template expressions, markup, and anything that does not look like a
+/// plain word: tokens with digits, acronyms, URLs, and e-mail addresses.
+#[must_use]
+pub fn words(text: &str) -> Vec<(Span, &str)> {
+    let masked = mask_non_text(text);
+    let mut words = Vec::new();
+    let mut offset = 0;
+    for chunk in masked.split_whitespace() {
+        let Some(start) = masked.get(offset..).and_then(|tail| tail.find(chunk)) else {
+            continue;
+        };
+        let start = offset + start;
+        offset = start + chunk.len();
+        if chunk.contains("://") || chunk.contains('@') || chunk.starts_with("www.") {
+            continue;
+        }
+        for (index, word) in chunk.split_word_bound_indices() {
+            let word = word.trim_matches(['\'', '’']);
+            if !word.chars().any(char::is_alphabetic)
+                || word.chars().any(|c| c.is_numeric() || c == '_')
+                || (word.chars().count() > 1 && word.chars().all(|c| !c.is_lowercase()))
+            {
+                continue;
+            }
+            let leading = chunk.get(index..).map_or(0, |tail| {
+                tail.len() - tail.trim_start_matches(['\'', '’']).len()
+            });
+            let range = start + index + leading..start + index + leading + word.len();
+            if let Some(original) = text.get(range.clone()) {
+                words.push((range, original));
+            }
+        }
+    }
+    words
+}
+
+/// Checks every translation against the dictionary of its language.
+///
+/// Returns one diagnostic per misspelled word, labelled at the word itself
+/// and suggesting corrections where the dictionary has any.
+#[must_use]
+pub fn lint_spelling(
+    translations: &Translations,
+    dictionaries: &Dictionaries,
+    strict: bool,
+) -> Vec<Diagnostic<FileId>> {
+    let mut diagnostics = Vec::new();
+    for translation in translations.0.values() {
+        if is_allowed(&translation.allow, LintCode::Spelling) {
+            continue;
+        }
+        for (language, value) in &translation.language {
+            let Some(dictionary) = dictionaries.languages.get(language) else {
+                continue;
+            };
+            for (range, word) in words(value.as_ref()) {
+                if dictionaries.is_project_word(word) || dictionary.check(word) {
+                    continue;
+                }
+                let suggestions = dictionary.suggest(word, MAX_SUGGESTIONS);
+                let label = if suggestions.is_empty() {
+                    format!("not in the `{}` dictionary", language.code())
+                } else {
+                    let suggestions = suggestions
+                        .iter()
+                        .map(|suggestion| format!("`{suggestion}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("did you mean {suggestions}?")
+                };
+                diagnostics.push(
+                    Diagnostic::warning_or_error(strict)
+                        .with_code(LintCode::Spelling)
+                        .with_message(format!(
+                            "possible misspelling `{word}` in `{}` translation",
+                            language.code()
+                        ))
+                        .with_labels(vec![
+                            Label::primary(translation.file_id, value.sub_span(range))
+                                .with_message(label),
+                        ])
+                        .with_notes(vec![
+                            "if the word is correct, add it to the project word list".to_string(),
+                        ]),
+                );
+            }
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dictionaries, Dictionary, lint_spelling, words};
+    use globetrotter_model::{Language, Translations};
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test_util::test]
+    fn skips_placeholders_markup_and_non_words() {
+        let text =
+            "Hi {{name}}, see <a href=\"x\">the FAQ</a> at https://x.io or mail a@b.c 3x don't";
+        let have: Vec<&str> = words(text).into_iter().map(|(_, word)| word).collect();
+        sim_assert_eq!(have: have, want: vec!["Hi", "see", "the", "at", "or", "mail", "don't"]);
+    }
+
+    #[test_util::test]
+    fn checks_text_inside_blocks_only() {
+        let text =
+            "{{#if (gt count 1)}}if many {{count}}{{else}}one{{/if}} {{> footer}}{{!-- note --}}";
+        let have: Vec<(std::ops::Range<usize>, &str)> = words(text);
+        sim_assert_eq!(
+            have: have,
+            want: vec![(20..22, "if"), (23..27, "many"), (45..48, "one")]
+        );
+    }
+
+    #[test_util::test]
+    fn reports_misspellings_with_exact_spans() {
+        let raw = "[greeting]\nen = \"Helo {{name}}, welcome to Globetrotter\"\n";
+        let mut parse_diagnostics = vec![];
+        let translations = Translations::from_str(raw, 0, false, &mut parse_diagnostics)?;
+        let dictionaries = Dictionaries {
+            languages: [(
+                Language::En,
+                Dictionary::parse("TRY elohw\n", "3\nhello\nwelcome\nto\n"),
+            )]
+            .into_iter()
+            .collect(),
+            words: ["globetrotter".to_string()].into_iter().collect(),
+        };
+
+        let diagnostics = lint_spelling(&translations, &dictionaries, false);
+        let have: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let label = diagnostic.labels.first();
+                (
+                    diagnostic.message.clone(),
+                    label.and_then(|label| raw.get(label.range.clone())),
+                    label.map(|label| label.message.clone()),
+                )
+            })
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![(
+                "possible misspelling `Helo` in `en` translation".to_string(),
+                Some("Helo"),
+                Some("did you mean `Hello`?".to_string()),
+            )]
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! Detection of translations whose source-language text changed since they
+//! were reviewed.
+//!
+//! A source lock is committed next to the config and records, for every
+//! reviewed translation, a hash of the source-language text it was reviewed
+//! against:
+//!
+//! ```toml
+//! ["cart.title"]
+//! de = "3f2a9c0d1b7e4a58"
+//! fr = "3f2a9c0d1b7e4a58"
+//! ```
+//!
+//! When the source text is edited, its hash no longer matches and the
+//! translation is reported as stale until it is marked as reviewed again with
+//! `globetrotter stale mark`. Translations without an entry are untracked and
+//! not reported.
+
+use crate::config::v1::ConfigFile;
+use crate::error::IoError;
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use globetrotter_model::{
+    Language, Translation, Translations,
+    diagnostics::{DiagnosticExt, FileId, Spanned},
+    lint::{LintCode, is_allowed},
+};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A short hash of a source-language text, as recorded in source locks.
+#[must_use]
+pub fn source_hash(text: &str) -> String {
+    blake3::hash(text.as_bytes())
+        .to_hex()
+        .get(..16)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// An error reading a source lock.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The file could not be read.
+    #[error(transparent)]
+    Io(#[from] IoError),
+    /// The file is not a valid source lock.
+    #[error("{}: {message}", path.display())]
+    Invalid {
+        /// The source lock.
+        path: PathBuf,
+        /// What is wrong with it.
+        message: String,
+    },
+}
+
+/// The source hashes recorded in one source lock.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceLock {
+    /// The recorded source hash by key and language code.
+    pub keys: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl SourceLock {
+    /// Reads the source lock at `path`; a missing file records nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid source
+    /// lock.
+    pub async fn load(path: &Path) -> Result<Self, Error> {
+        let content = match tokio::fs::read_to_string(path).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::default());
+            }
+            Err(error) => return Err(IoError::new(path, error).into()),
+        };
+        Self::parse(&content).map_err(|message| Error::Invalid {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    /// Parses the content of a source lock.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first invalid entry.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let value = toml_span::parse(content).map_err(|error| error.to_string())?;
+        let Some(table) = value.as_table() else {
+            return Ok(Self::default());
+        };
+        let keys = table
+            .iter()
+            .map(|(key, languages)| {
+                let key = key.name.to_string();
+                let languages = languages
+                    .as_table()
+                    .ok_or_else(|| format!("`{key}` must be a table of language hashes"))?
+                    .iter()
+                    .map(|(language, hash)| {
+                        let hash = hash.as_str().ok_or_else(|| {
+                            format!("`{key}.{}` must be a hash string", language.name)
+                        })?;
+                        Ok((language.name.to_string(), hash.to_string()))
+                    })
+                    .collect::<Result<_, String>>()?;
+                Ok((key, languages))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { keys })
+    }
+
+    /// The source hash recorded for `key` in `language`, if any.
+    #[must_use]
+    pub fn get(&self, key: &str, language: Language) -> Option<&str> {
+        self.keys.get(key)?.get(language.code()).map(String::as_str)
+    }
+}
+
+/// Whether a translation was reviewed against its current source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum State {
+    /// Reviewed against the current source text.
+    Current,
+    /// Reviewed against a source text that has changed since.
+    Stale,
+    /// Not recorded in the source lock.
+    Untracked,
+}
+
+/// One translation compared with the source lock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tracked {
+    /// The full translation key.
+    pub key: String,
+    /// The translated language.
+    pub language: Language,
+    /// The hash of the key's current source text.
+    pub source_hash: String,
+    /// How the recorded hash compares with the current one.
+    pub state: State,
+}
+
+/// The source-language text of `translation`, if it is not blank.
+fn source_text(translation: &Translation, source: Language) -> Option<&str> {
+    translation
+        .language
+        .get(&source)
+        .map(|text| text.as_ref().as_str())
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// Compares every translation outside the `source` language with `lock`, in
+/// key order.
+#[must_use]
+pub fn check(translations: &Translations, source: Language, lock: &SourceLock) -> Vec<Tracked> {
+    let mut tracked = Vec::new();
+    for (key, translation) in translations {
+        let Some(text) = source_text(translation, source) else {
+            continue;
+        };
+        let source_hash = source_hash(text);
+        for language in translation.language.keys().filter(|l| **l != source) {
+            let state = match lock.get(key.as_ref(), *language) {
+                None => State::Untracked,
+                Some(recorded) if recorded == source_hash => State::Current,
+                Some(_) => State::Stale,
+            };
+            tracked.push(Tracked {
+                key: key.as_ref().clone(),
+                language: *language,
+                source_hash: source_hash.clone(),
+                state,
+            });
+        }
+    }
+    tracked
+}
+
+/// Reports every stale translation of `translations`.
+///
+/// Keys that suppress the `stale` code via their `allow` list are skipped.
+#[must_use]
+pub fn lint_stale(
+    translations: &Translations,
+    source: Language,
+    lock: &SourceLock,
+    strict: bool,
+) -> Vec<Diagnostic<FileId>> {
+    let mut diagnostics = Vec::new();
+    for (key, translation) in translations {
+        if is_allowed(&translation.allow, LintCode::Stale) {
+            continue;
+        }
+        let Some(text) = source_text(translation, source) else {
+            continue;
+        };
+        let source_hash = source_hash(text);
+        for (language, value) in &translation.language {
+            let stale = *language != source
+                && lock
+                    .get(key.as_ref(), *language)
+                    .is_some_and(|recorded| recorded != source_hash);
+            if stale {
+                diagnostics.push(diagnostic(
+                    key,
+                    translation,
+                    *language,
+                    value,
+                    source,
+                    strict,
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+fn diagnostic(
+    key: &Spanned<String>,
+    translation: &Translation,
+    language: Language,
+    value: &Spanned<String>,
+    source: Language,
+    strict: bool,
+) -> Diagnostic<FileId> {
+    let mut labels = vec![
+        Label::primary(translation.file_id, value.span.clone())
+            .with_message(format!("reviewed against an earlier `{source}` text")),
+    ];
+    if let Some(text) = translation.language.get(&source) {
+        labels.push(
+            Label::secondary(translation.file_id, text.span.clone())
+                .with_message(format!("`{source}` text changed since")),
+        );
+    }
+    Diagnostic::warning_or_error(strict)
+        .with_code(LintCode::Stale)
+        .with_message(format!(
+            "`{language}` translation of `{key}` may be outdated",
+            key = key.as_ref()
+        ))
+        .with_labels(labels)
+        .with_notes(vec![format!(
+            "after updating it, run `globetrotter stale mark {} --language {language}`",
+            key.as_ref()
+        )])
+}
+
+/// The source lock of `config_file`, resolved against its directory.
+#[must_use]
+pub fn source_lock_path(config_file: &ConfigFile<FileId>) -> Option<PathBuf> {
+    config_file
+        .config
+        .lint
+        .source_lock
+        .as_ref()
+        .map(|path| crate::executor::resolve_path(config_file.config_dir.as_deref(), path.as_ref()))
+}
+
+impl crate::executor::Executor {
+    /// Compares the translations of `config_file` with its source lock.
+    ///
+    /// Returns `None` if the config has no source lock or no source language.
+    /// Diagnostics are not emitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a translation file or the source lock cannot be
+    /// read or parsed.
+    pub async fn tracked_translations(
+        &self,
+        config_file: &ConfigFile<FileId>,
+    ) -> Result<Option<(PathBuf, SourceLock, Vec<Tracked>)>, crate::error::Error> {
+        let (Some(path), Some(source)) = (
+            source_lock_path(config_file),
+            config_file.config.source_language(),
+        ) else {
+            return Ok(None);
+        };
+        let lock = SourceLock::load(&path).await?;
+        let mut diagnostics = Vec::new();
+        let loaded = self
+            .load_translations(config_file, self.lint_strict(), &mut diagnostics)
+            .await?;
+        let translations = crate::executor::combine_translations(loaded, &mut diagnostics);
+        let tracked = check(&translations, source, &lock);
+        Ok(Some((path, lock, tracked)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SourceLock, State, check, lint_stale, source_hash};
+    use globetrotter_model::{Language, Translations};
+    use indoc::indoc;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::collections::BTreeMap;
+
+    #[test_util::test]
+    fn flags_translations_of_changed_source_text() -> color_eyre::eyre::Result<()> {
+        let raw = indoc! {r#"
+            [cart.title]
+            en = "Your cart"
+            de = "Ihr Warenkorb"
+            fr = "Votre panier"
+
+            [cart.empty]
+            en = "Your cart is empty"
+            de = "Ihr Warenkorb ist leer"
+
+            [cart.legacy]
+            en = "Basket"
+            de = "Korb"
+            allow = ["stale"]
+        "#};
+        let mut diagnostics = vec![];
+        let translations = Translations::from_str(raw, 0, false, &mut diagnostics)?;
+        let lock = SourceLock::parse(&format!(
+            indoc! {r#"
+                ["cart.title"]
+                de = "{current}"
+                fr = "{old}"
+
+                ["cart.legacy"]
+                de = "{old}"
+            "#},
+            current = source_hash("Your cart"),
+            old = source_hash("Cart"),
+        ))
+        .map_err(color_eyre::eyre::Report::msg)?;
+
+        let states: Vec<_> = check(&translations, Language::En, &lock)
+            .into_iter()
+            .map(|tracked| (tracked.key, tracked.language, tracked.state))
+            .collect();
+        sim_assert_eq!(
+            have: states,
+            want: vec![
+                ("cart.empty".to_string(), Language::De, State::Untracked),
+                ("cart.legacy".to_string(), Language::De, State::Stale),
+                ("cart.title".to_string(), Language::De, State::Current),
+                ("cart.title".to_string(), Language::Fr, State::Stale),
+            ]
+        );
+
+        let messages: Vec<_> = lint_stale(&translations, Language::En, &lock, false)
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect();
+        sim_assert_eq!(
+            have: messages,
+            want: vec!["`fr` translation of `cart.title` may be outdated".to_string()]
+        );
+        Ok(())
+    }
+
+    #[test_util::test]
+    fn rejects_invalid_locks() {
+        assert!(SourceLock::parse("key = \"hash\"").is_err());
+        assert!(SourceLock::parse("[key]\nde = 1").is_err());
+        sim_assert_eq!(
+            have: SourceLock::parse("").map(|lock| lock.keys),
+            want: Ok(BTreeMap::new())
+        );
+    }
+}
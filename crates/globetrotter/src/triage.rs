@@ -0,0 +1,196 @@
+//! Recorded decisions about LLM judge findings.
+//!
+//! A triage file is committed next to the config and lists reviewed findings
+//! as `[[finding]]` tables:
+//!
+//! ```toml
+//! [[finding]]
+//! key = "cart.title"
+//! language = "fr"
+//! hash = "3f2a9c0d1b7e4a58"
+//! status = "dismissed"
+//! note = "Panier is the usual term"
+//! ```
+//!
+//! `hash` is the [content hash](globetrotter_llm_judge::KeyInput::content_hash)
+//! of the key's strings when the decision was made. A decision only applies
+//! while the hash matches, so editing any language of the key reopens its
+//! findings.
+
+use crate::error::IoError;
+use std::path::{Path, PathBuf};
+
+/// A decision about a finding.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumString, strum::IntoStaticStr, strum::Display,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Status {
+    /// The finding is a false positive and is no longer reported.
+    Dismissed,
+    /// The finding is a real issue; it is still reported, with the note.
+    Confirmed,
+}
+
+/// One recorded decision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The full translation key.
+    pub key: String,
+    /// The flagged language code.
+    pub language: String,
+    /// The content hash of the key's strings the decision was made for.
+    pub hash: String,
+    /// The decision.
+    pub status: Status,
+    /// Why the decision was made.
+    pub note: Option<String>,
+}
+
+/// An error reading a triage file.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The file could not be read.
+    #[error(transparent)]
+    Io(#[from] IoError),
+    /// The file is not a valid triage file.
+    #[error("{}: {message}", path.display())]
+    Invalid {
+        /// The triage file.
+        path: PathBuf,
+        /// What is wrong with it.
+        message: String,
+    },
+}
+
+/// The decisions recorded in one triage file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Triage {
+    /// The decisions, in file order.
+    pub entries: Vec<Entry>,
+}
+
+impl Triage {
+    /// Reads the triage file at `path`; a missing file has no decisions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid triage
+    /// file.
+    pub async fn load(path: &Path) -> Result<Self, Error> {
+        let content = match tokio::fs::read_to_string(path).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::default());
+            }
+            Err(error) => return Err(IoError::new(path, error).into()),
+        };
+        Self::parse(&content).map_err(|message| Error::Invalid {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    /// Parses the content of a triage file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first invalid entry.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let value = toml_span::parse(content).map_err(|error| error.to_string())?;
+        let Some(findings) = value.pointer("/finding") else {
+            return Ok(Self::default());
+        };
+        let Some(findings) = findings.as_array() else {
+            return Err("`finding` must be an array of tables".to_string());
+        };
+        let entries = findings
+            .iter()
+            .enumerate()
+            .map(|(index, finding)| {
+                let field = |name: &str| {
+                    finding
+                        .as_table()
+                        .and_then(|table| table.get(name))
+                        .and_then(|value| value.as_str())
+                        .map(str::to_string)
+                };
+                let required = |name: &str| {
+                    field(name).ok_or_else(|| format!("finding {} lacks `{name}`", index + 1))
+                };
+                let status = required("status")?;
+                Ok(Entry {
+                    key: required("key")?,
+                    language: required("language")?,
+                    hash: required("hash")?,
+                    status: status.parse().map_err(|_| {
+                        format!(
+                            "finding {}: unknown status `{status}`, expected `dismissed` or `confirmed`",
+                            index + 1
+                        )
+                    })?,
+                    note: field("note"),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { entries })
+    }
+
+    /// The decision recorded for a finding of `key` in `language` while the
+    /// key's strings have the content hash `hash`. The last matching entry
+    /// wins.
+    #[must_use]
+    pub fn decision(&self, key: &str, language: &str, hash: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.key == key && entry.language == language && entry.hash == hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Status, Triage};
+    use indoc::indoc;
+
+    #[test_util::test]
+    fn parses_decisions_matching_the_hash() {
+        let triage = Triage::parse(indoc! {r#"
+            [[finding]]
+            key = "cart.title"
+            language = "fr"
+            hash = "aaaa"
+            status = "dismissed"
+            note = "Panier is the usual term"
+
+            [[finding]]
+            key = "cart.title"
+            language = "de"
+            hash = "aaaa"
+            status = "confirmed"
+        "#})
+        .map_err(color_eyre::eyre::Report::msg)?;
+
+        let dismissed = triage.decision("cart.title", "fr", "aaaa");
+        assert_eq!(dismissed.map(|entry| entry.status), Some(Status::Dismissed));
+        assert_eq!(
+            dismissed.and_then(|entry| entry.note.as_deref()),
+            Some("Panier is the usual term")
+        );
+        assert_eq!(
+            triage
+                .decision("cart.title", "de", "aaaa")
+                .map(|entry| entry.status),
+            Some(Status::Confirmed)
+        );
+        // Changed strings reopen the finding.
+        assert_eq!(triage.decision("cart.title", "fr", "bbbb"), None);
+
+        assert!(
+            Triage::parse(
+                "[[finding]]\nkey = \"a\"\nlanguage = \"fr\"\nhash = \"aaaa\"\nstatus = \"ignored\""
+            )
+            .is_err()
+        );
+    }
+}
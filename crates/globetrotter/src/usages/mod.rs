@@ -0,0 +1,720 @@
+//! Finding references to translation keys in source code.
+//!
+//! With the `tree-sitter` feature, TypeScript, JavaScript, Rust, Go, and
+//! Python files are parsed: only string literals whose whole content is a key
+//! and identifiers equal to a generated identifier count, so keys mentioned in
+//! comments are not mistaken for usages. Strings built at runtime, such as
+//! `` `app.step.${n}` ``, `"app.step." + n`, `format!("app.step.{n}")`, or
+//! `fmt.Sprintf("app.step.%d", n)`, contribute their literal start as a
+//! dynamic prefix. A `// globetrotter-ignore` comment suppresses the usages
+//! of its line, or of the next line when it stands alone. Files in other
+//! languages, or all files without the feature, are searched as plain text.
+
+#[cfg(feature = "tree-sitter")]
+pub(crate) mod syntax;
+
+use globetrotter_model::diagnostics::Span;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Directory names never descended into while scanning for key usages.
+const SKIP_DIRS: &[&str] = &[
+    "node_modules",
+    ".git",
+    "dist",
+    "build",
+    "out",
+    "target",
+    ".next",
+    ".turbo",
+    ".svelte-kit",
+    ".cache",
+    "coverage",
+    "vendor",
+];
+
+/// File extensions scanned for key usages. Notably excludes `json`/`toml` so
+/// that generated translation files and the source `.toml` files do not mark
+/// every key as used.
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "js", "jsx", "mjs", "cjs", "vue", "svelte", "astro", "html", "htm", "rs", "go",
+    "py", "rb", "php", "java", "kt", "swift", "dart", "ex", "exs", "lua", "zig", "cs",
+];
+
+/// The translation functions whose first string argument is a key, unless
+/// configured otherwise. Rust macros carry their `!`.
+pub const DEFAULT_CALLS: &[&str] = &["t", "$t", "i18n.t", "t!"];
+
+/// Files larger than this are skipped (likely generated or binary).
+const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// How a usage refers to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UsageKind {
+    /// A string literal whose whole content is the key.
+    Literal,
+    /// An identifier equal to a generated identifier of the key, such as the
+    /// Rust enum variant `TranslationGreeting`.
+    Identifier,
+    /// The literal start of a key built at runtime, such as `app.step.` in
+    /// `` `app.step.${n}` ``. Every key starting with it may be used.
+    Prefix,
+    /// A plain-text occurrence in a file that was not parsed.
+    Text,
+    /// A string literal passed as the first argument of a translation call,
+    /// such as `t("app.title")`. Found whether or not a key matches, so keys
+    /// used but never defined can be reported.
+    Call,
+}
+
+impl std::fmt::Display for UsageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Literal => "literal",
+            Self::Identifier => "identifier",
+            Self::Prefix => "prefix",
+            Self::Text => "text",
+            Self::Call => "call",
+        })
+    }
+}
+
+/// A reference to a key, or to a family of keys, in a source file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Usage {
+    /// The file containing the usage.
+    pub path: PathBuf,
+    /// The 1-based line of the usage.
+    pub line: usize,
+    /// The 1-based column of the usage, in characters.
+    pub column: usize,
+    /// The byte range of the usage within the file.
+    pub range: Span,
+    /// How the usage refers to a key.
+    pub kind: UsageKind,
+    /// The key form, generated identifier, or dynamic prefix that was found.
+    pub text: String,
+}
+
+impl Usage {
+    /// Returns `true` if this usage references `key`, whose usable forms are
+    /// `forms` (the key itself and its generated identifiers).
+    #[must_use]
+    pub fn references(&self, key: &str, forms: &[String]) -> bool {
+        match self.kind {
+            UsageKind::Prefix => key.starts_with(&self.text),
+            UsageKind::Literal | UsageKind::Identifier | UsageKind::Text | UsageKind::Call => {
+                forms.contains(&self.text)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+/// Selects keys by their dotted name or by a glob such as `app.cart.*`.
+///
+/// `*` matches any run of characters, dots included, so `app.cart.*` selects
+/// every key under `app.cart`; `?` matches one character and `[…]` one of a
+/// set.
+#[derive(Debug, Clone)]
+pub struct KeyPattern {
+    pattern: glob::Pattern,
+}
+
+impl KeyPattern {
+    /// Compiles a key pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is not a valid glob.
+    pub fn new(pattern: &str) -> Result<Self, glob::PatternError> {
+        Ok(Self {
+            pattern: glob::Pattern::new(pattern)?,
+        })
+    }
+
+    /// Returns `true` if `key` is selected by this pattern.
+    #[must_use]
+    pub fn matches(&self, key: &str) -> bool {
+        self.pattern.matches(key)
+    }
+
+    /// The key this pattern names, if it has no wildcards. Such a key is looked
+    /// up even if no translation file defines it.
+    #[must_use]
+    pub fn literal(&self) -> Option<&str> {
+        let pattern = self.pattern.as_str();
+        (!pattern.contains(['*', '?', '['])).then_some(pattern)
+    }
+}
+
+impl std::fmt::Display for KeyPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.pattern.as_str())
+    }
+}
+
+/// The usages of one translation key.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct KeyUsages {
+    /// The dotted key.
+    pub key: String,
+    /// Whether a translation file defines the key.
+    pub defined: bool,
+    /// Every usage referencing the key, ordered by file and position.
+    pub usages: Vec<Usage>,
+}
+
+/// Groups `usages` by the keys `patterns` select.
+///
+/// `keys` maps every defined key to its usable forms (the key itself and its
+/// generated identifiers). Keys named literally by a pattern are included even
+/// if undefined, matched by their dotted form only.
+#[must_use]
+pub fn key_usages(
+    keys: &BTreeMap<String, Vec<String>>,
+    patterns: &[KeyPattern],
+    usages: &[Usage],
+) -> Vec<KeyUsages> {
+    let mut selected: BTreeMap<String, (bool, Vec<String>)> = keys
+        .iter()
+        .filter(|(key, _)| patterns.iter().any(|pattern| pattern.matches(key)))
+        .map(|(key, forms)| (key.clone(), (true, forms.clone())))
+        .collect();
+    for key in patterns.iter().filter_map(KeyPattern::literal) {
+        selected
+            .entry(key.to_string())
+            .or_insert_with(|| (false, vec![key.to_string()]));
+    }
+
+    selected
+        .into_iter()
+        .map(|(key, (defined, forms))| KeyUsages {
+            defined,
+            usages: usages
+                .iter()
+                .filter(|usage| usage.references(&key, &forms))
+                .cloned()
+                .collect(),
+            key,
+        })
+        .collect()
+}
+
+/// Creates a usage of `text` at `range` of a file, locating it by line and
+/// column.
+fn usage(path: &Path, content: &str, range: Span, kind: UsageKind, text: String) -> Usage {
+    let (line, column) = location(content, range.start);
+    Usage {
+        path: path.to_path_buf(),
+        line,
+        column,
+        range,
+        kind,
+        text,
+    }
+}
+
+/// The 1-based line and character column of byte `offset` in `content`.
+fn location(content: &str, offset: usize) -> (usize, usize) {
+    let before = content.get(..offset).unwrap_or_default();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let column = before.get(line_start..).unwrap_or_default().chars().count();
+    (before.matches('\n').count() + 1, column + 1)
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '_' | '-')
+}
+
+/// Whether the match at `start..end` is bounded by non-key characters, so that
+/// e.g. searching for `a.b` does not match inside `a.b.c`.
+fn is_whole_token(content: &str, start: usize, end: usize) -> bool {
+    let before_ok = content
+        .get(..start)
+        .and_then(|prefix| prefix.chars().next_back())
+        .is_none_or(|c| !is_key_char(c));
+    let after_ok = content
+        .get(end..)
+        .and_then(|suffix| suffix.chars().next())
+        .is_none_or(|c| !is_key_char(c));
+    before_ok && after_ok
+}
+
+/// Whether a dynamic prefix is specific enough to count. Requiring a dot keeps
+/// trivial prefixes such as `` `${x}` `` from masking large key subtrees.
+fn is_specific_prefix(prefix: &str) -> bool {
+    prefix.contains('.')
+}
+
+/// Collects literal key prefixes that immediately precede a `${ … }`
+/// interpolation, e.g. `` t(`a.b.${x}`) `` yields `a.b.` and
+/// `` `a.b.step${n}.x` `` yields `a.b.step`.
+fn dynamic_prefixes(content: &str) -> impl Iterator<Item = (usize, String)> + '_ {
+    content.match_indices("${").filter_map(|(pos, _)| {
+        let before = content.get(..pos)?;
+        let start = before
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_key_char(*c))
+            .last()
+            .map_or(pos, |(index, _)| index);
+        let run = content.get(start..pos)?;
+        is_specific_prefix(run).then(|| (start, run.to_string()))
+    })
+}
+
+/// Finds string literals passed as the first argument of `calls` by plain-text
+/// search, e.g. `'app.title'` in `t('app.title')`. Literals with escapes or
+/// `${` interpolations are skipped.
+fn call_literals<'a>(
+    content: &'a str,
+    calls: &'a [String],
+) -> impl Iterator<Item = (Span, String)> + 'a {
+    calls.iter().flat_map(move |call| {
+        let needle = format!("{call}(");
+        content
+            .match_indices(needle.as_str())
+            .filter_map(|(pos, _)| {
+                let bounded = content
+                    .get(..pos)
+                    .and_then(|before| before.chars().next_back())
+                    .is_none_or(|c| !(c.is_alphanumeric() || matches!(c, '_' | '.' | '$')));
+                let arguments = content.get(pos + needle.len()..)?;
+                let literal = arguments.trim_start();
+                let start = content.len() - literal.len();
+                let quote = literal
+                    .chars()
+                    .next()
+                    .filter(|c| matches!(c, '"' | '\'' | '`'))?;
+                let value = literal.get(1..)?.split(quote).next()?;
+                let dynamic = value.contains("${") || value.contains('\\');
+                (bounded && !dynamic && literal.len() > value.len() + 1)
+                    .then(|| (start..start + value.len() + 2, value.to_string()))
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+fn is_source_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| SOURCE_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
+
+/// Whether a directory should be pruned from the usage scan: a generated-output
+/// directory, a built-in skip directory, or another globetrotter source tree
+/// (any directory holding a globetrotter config file).
+fn is_pruned_dir(
+    path: &Path,
+    name: &str,
+    scan_roots: &BTreeSet<PathBuf>,
+    excluded: &BTreeSet<PathBuf>,
+) -> bool {
+    if SKIP_DIRS.contains(&name) {
+        return true;
+    }
+
+    let canonical = path.canonicalize().ok();
+    if canonical
+        .as_ref()
+        .is_some_and(|canonical| excluded.contains(canonical))
+    {
+        return true;
+    }
+
+    if canonical
+        .as_ref()
+        .is_some_and(|canonical| scan_roots.contains(canonical))
+    {
+        return false;
+    }
+
+    crate::config::config_file_names().any(|config| path.join(config).exists())
+}
+
+/// Finds usages of a set of key forms in source files.
+#[derive(Debug)]
+pub struct Scanner {
+    /// Every form searched for, dotted keys and generated identifiers, in
+    /// sorted automaton pattern order.
+    patterns: Vec<String>,
+    searcher: aho_corasick::AhoCorasick,
+    /// Translation functions whose literal first argument is a key.
+    calls: Vec<String>,
+}
+
+impl Scanner {
+    /// Creates a scanner for `forms`, the dotted keys and generated
+    /// identifiers to look for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plain-text pattern automaton cannot be built.
+    pub fn new(forms: impl IntoIterator<Item = String>) -> std::io::Result<Self> {
+        let patterns: Vec<String> = forms
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let searcher = aho_corasick::AhoCorasick::new(&patterns).map_err(std::io::Error::other)?;
+        Ok(Self {
+            patterns,
+            searcher,
+            calls: DEFAULT_CALLS.iter().map(ToString::to_string).collect(),
+        })
+    }
+
+    /// Sets the translation functions whose literal first argument is a key,
+    /// such as `t` or `i18n.t`, replacing [`DEFAULT_CALLS`]. Rust macros are
+    /// written with their `!`.
+    #[must_use]
+    pub fn with_calls(mut self, calls: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.calls = calls.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Finds the usages in one source file, parsing it if its language is
+    /// supported and searching it as plain text otherwise.
+    #[must_use]
+    pub fn scan_source(&self, path: &Path, content: &str) -> Vec<Usage> {
+        #[cfg(feature = "tree-sitter")]
+        if let Some(tokens) = syntax::tokens(path, content, &self.calls) {
+            return tokens
+                .into_iter()
+                .filter(|token| match token.kind {
+                    UsageKind::Prefix => is_specific_prefix(&token.text),
+                    UsageKind::Call => true,
+                    _ => self.patterns.binary_search(&token.text).is_ok(),
+                })
+                .map(|token| usage(path, content, token.range, token.kind, token.text))
+                .collect();
+        }
+        self.scan_text(path, content)
+    }
+
+    fn scan_text(&self, path: &Path, content: &str) -> Vec<Usage> {
+        let literals = self
+            .searcher
+            .find_overlapping_iter(content)
+            .filter(|found| is_whole_token(content, found.start(), found.end()))
+            .filter_map(|found| {
+                let text = self.patterns.get(found.pattern().as_usize())?;
+                Some(usage(
+                    path,
+                    content,
+                    found.range(),
+                    UsageKind::Text,
+                    text.clone(),
+                ))
+            });
+        let prefixes = dynamic_prefixes(content).map(|(start, prefix)| {
+            usage(
+                path,
+                content,
+                start..start + prefix.len(),
+                UsageKind::Prefix,
+                prefix,
+            )
+        });
+        let calls = call_literals(content, &self.calls)
+            .map(|(range, value)| usage(path, content, range, UsageKind::Call, value));
+        let mut usages: Vec<Usage> = literals.chain(prefixes).chain(calls).collect();
+        usages.sort_by_key(|usage| usage.range.start);
+        usages
+    }
+
+    /// Finds the usages in every source file below `dirs`, skipping the
+    /// directories [`walk_sources`] skips.
+    #[must_use]
+    pub fn scan(&self, dirs: &[PathBuf], excluded: &BTreeSet<PathBuf>) -> Vec<Usage> {
+        let mut usages = Vec::new();
+        walk_sources(dirs, excluded, |path, content| {
+            usages.extend(self.scan_source(path, content));
+        });
+        usages.sort_by(|a, b| a.path.cmp(&b.path).then(a.range.start.cmp(&b.range.start)));
+        usages
+    }
+}
+
+/// Calls `visit` with the path and content of every source file below `dirs`.
+///
+/// The walk respects `.gitignore`, skips other globetrotter source trees
+/// (any directory containing a config file), and skips `excluded`
+/// canonicalized directories (e.g. generated output) and well-known build
+/// and dependency directories (`node_modules`, `target`, `dist`, …).
+/// Unreadable files and files larger than the scan limit are skipped.
+pub(crate) fn walk_sources(
+    dirs: &[PathBuf],
+    excluded: &BTreeSet<PathBuf>,
+    mut visit: impl FnMut(&Path, &str),
+) {
+    // Configure one ignore-aware walker across all requested source roots.
+    let Some((first, rest)) = dirs.split_first() else {
+        return;
+    };
+    let mut builder = ignore::WalkBuilder::new(first);
+    for dir in rest {
+        builder.add(dir);
+    }
+    // Respect `.gitignore` and `.ignore` even outside a Git checkout.
+    builder.require_git(false);
+    let scan_roots: BTreeSet<PathBuf> = dirs
+        .iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect();
+    let excluded = excluded.clone();
+    builder.filter_entry(move |entry| {
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir())
+        {
+            return true;
+        }
+        !is_pruned_dir(
+            entry.path(),
+            entry.file_name().to_string_lossy().as_ref(),
+            &scan_roots,
+            &excluded,
+        )
+    });
+
+    for entry in builder.build().flatten() {
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let path = entry.path();
+        if !is_source_file(path) {
+            continue;
+        }
+        if entry
+            .metadata()
+            .is_ok_and(|metadata| metadata.len() > MAX_FILE_BYTES)
+        {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        visit(path, &content);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyPattern, Scanner, UsageKind, key_usages};
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn temp_dir(prefix: &str) -> eyre::Result<PathBuf> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        let unique = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "globetrotter-usages-{prefix}-{}-{nanos}-{unique}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&path)?;
+        Ok(path)
+    }
+
+    /// An explicitly requested root is scanned even when it contains a config.
+    #[test_util::test]
+    fn explicit_root_with_config_file_is_still_scanned() -> eyre::Result<()> {
+        let dir = temp_dir("root-config")?;
+        std::fs::write(dir.join(".globetrotter.yaml"), "version: 1\nconfigs: []\n")?;
+        std::fs::write(
+            dir.join("app.ts"),
+            "export const title = t('upload.title');\n",
+        )?;
+
+        let scanner = Scanner::new(["upload.title".to_string()])?;
+        let usages = scanner.scan(std::slice::from_ref(&dir), &BTreeSet::new());
+        let have: BTreeSet<&str> = usages.iter().map(|usage| usage.text.as_str()).collect();
+        sim_assert_eq!(have: have, want: BTreeSet::from(["upload.title"]));
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    fn scan(path: &str, content: &str) -> eyre::Result<Vec<(UsageKind, String, String)>> {
+        let scanner = Scanner::new(
+            ["app.title", "app.step.one", "AppTitle"]
+                .into_iter()
+                .map(str::to_string),
+        )?;
+        Ok(scanner
+            .scan_source(Path::new(path), content)
+            .into_iter()
+            .map(|usage| {
+                (
+                    usage.kind,
+                    usage.text,
+                    format!("{}:{}", usage.line, usage.column),
+                )
+            })
+            .collect())
+    }
+
+    #[test_util::test]
+    fn searches_unparsed_files_as_text() -> eyre::Result<()> {
+        let have = scan(
+            "App.vue",
+            "<p>{{ t('app.title') }}</p>\n<p>{{ t(`app.step.${n}`) }}</p>\n",
+        )?;
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                (UsageKind::Call, "app.title".to_string(), "1:9".to_string()),
+                (UsageKind::Text, "app.title".to_string(), "1:10".to_string()),
+                (UsageKind::Prefix, "app.step.".to_string(), "2:10".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test_util::test]
+    fn ignores_comments_in_parsed_files() -> eyre::Result<()> {
+        let typescript = indoc::indoc! {r#"
+            // t("app.title") is not a usage
+            const step = t(`app.step.${n}`);
+            const title = t("app.title");
+        "#};
+        sim_assert_eq!(
+            have: scan("app.ts", typescript)?,
+            want: vec![
+                (UsageKind::Prefix, "app.step.".to_string(), "2:16".to_string()),
+                (UsageKind::Call, "app.title".to_string(), "3:17".to_string()),
+            ]
+        );
+
+        let rust = indoc::indoc! {r#"
+            /// Shows `Translation::AppTitle`.
+            fn title() -> Translation { Translation::AppTitle {} }
+            fn step(n: u8) -> String { t(&format!("app.step.{n}")) }
+        "#};
+        sim_assert_eq!(
+            have: scan("main.rs", rust)?,
+            want: vec![
+                (UsageKind::Identifier, "AppTitle".to_string(), "2:42".to_string()),
+                (UsageKind::Prefix, "app.step.".to_string(), "3:39".to_string()),
+            ]
+        );
+
+        let go = indoc::indoc! {r#"
+            // "app.title"
+            var title = T("app.title")
+            var step = T(fmt.Sprintf("app.step.%d", n))
+        "#};
+        sim_assert_eq!(
+            have: scan("main.go", go)?,
+            want: vec![
+                (UsageKind::Literal, "app.title".to_string(), "2:15".to_string()),
+                (UsageKind::Prefix, "app.step.".to_string(), "3:26".to_string()),
+            ]
+        );
+
+        let python = indoc::indoc! {r#"
+            # t("app.title")
+            title = t('app.title')
+            step = t("app.step." + n)
+            other = t(f"app.step.{n}")
+        "#};
+        sim_assert_eq!(
+            have: scan("main.py", python)?,
+            want: vec![
+                (UsageKind::Call, "app.title".to_string(), "2:11".to_string()),
+                (UsageKind::Prefix, "app.step.".to_string(), "3:10".to_string()),
+                (UsageKind::Prefix, "app.step.".to_string(), "4:11".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test_util::test]
+    fn skips_usages_marked_as_ignored() -> eyre::Result<()> {
+        let typescript = indoc::indoc! {r#"
+            // globetrotter-ignore
+            const legacy = t("app.title");
+            const step = t(`app.step.${n}`); /* globetrotter-ignore: fixture */
+            const title = t("app.title");
+        "#};
+        sim_assert_eq!(
+            have: scan("app.ts", typescript)?,
+            want: vec![(UsageKind::Call, "app.title".to_string(), "4:17".to_string())]
+        );
+
+        let rust = indoc::indoc! {r#"
+            fn title() -> Translation { Translation::AppTitle {} } // globetrotter-ignore
+            // globetrotter-ignore
+            fn step(n: u8) -> String { t(&format!("app.step.{n}")) }
+            fn heading() -> Translation { Translation::AppTitle {} }
+        "#};
+        sim_assert_eq!(
+            have: scan("main.rs", rust)?,
+            want: vec![(UsageKind::Identifier, "AppTitle".to_string(), "4:44".to_string())]
+        );
+        Ok(())
+    }
+
+    #[test_util::test]
+    fn groups_usages_by_selected_keys() -> eyre::Result<()> {
+        let keys = BTreeMap::from([
+            (
+                "app.cart.title".to_string(),
+                vec!["app.cart.title".to_string()],
+            ),
+            (
+                "app.cart.total".to_string(),
+                vec!["app.cart.total".to_string()],
+            ),
+            ("app.title".to_string(), vec!["app.title".to_string()]),
+        ]);
+        let content = "t('app.cart.title');\nt(`app.cart.${k}`);\nt('app.cart.legacy');\n";
+        let usages =
+            Scanner::new(keys.keys().cloned())?.scan_source(Path::new("cart.vue"), content);
+        let patterns = [
+            KeyPattern::new("app.cart.*")?,
+            KeyPattern::new("app.cart.legacy")?,
+        ];
+
+        let have: Vec<_> = key_usages(&keys, &patterns, &usages)
+            .into_iter()
+            .map(|key| {
+                let found: Vec<String> = key
+                    .usages
+                    .iter()
+                    .map(|usage| format!("{} {}", usage.kind, usage.line))
+                    .collect();
+                (key.key, key.defined, found)
+            })
+            .collect();
+        sim_assert_eq!(
+            have: have,
+            want: vec![
+                ("app.cart.legacy".to_string(), false, vec!["prefix 2".to_string(), "call 3".to_string()]),
+                ("app.cart.title".to_string(), true, vec!["call 1".to_string(), "text 1".to_string(), "prefix 2".to_string()]),
+                ("app.cart.total".to_string(), true, vec!["prefix 2".to_string()]),
+            ]
+        );
+        Ok(())
+    }
+}
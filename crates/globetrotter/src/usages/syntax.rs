@@ -0,0 +1,393 @@
+//! Extraction of string literals and identifiers from parsed source files.
+
+use super::UsageKind;
+use crate::extract::CandidateKind;
+use globetrotter_model::diagnostics::Span;
+use std::path::Path;
+use tree_sitter::{Node, Parser};
+
+/// A language whose files are parsed rather than searched as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Grammar {
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Rust,
+    Go,
+    Python,
+}
+
+impl Grammar {
+    pub(crate) fn for_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            // The JavaScript grammar parses JSX as well.
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "rs" => Some(Self::Rust),
+            "go" => Some(Self::Go),
+            "py" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    fn language(self) -> tree_sitter::Language {
+        match self {
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+        }
+    }
+
+    /// Returns `true` if nodes of `kind` are string literals.
+    fn is_string(self, kind: &str) -> bool {
+        match self {
+            Self::TypeScript | Self::Tsx | Self::JavaScript => {
+                matches!(kind, "string" | "template_string")
+            }
+            Self::Rust => matches!(kind, "string_literal" | "raw_string_literal"),
+            Self::Go => matches!(kind, "interpreted_string_literal" | "raw_string_literal"),
+            Self::Python => kind == "string",
+        }
+    }
+}
+
+/// A string literal, dynamic key prefix, or identifier in a parsed file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Token {
+    /// The kind of usage the token would be.
+    pub kind: UsageKind,
+    /// The string content, prefix, or identifier.
+    pub text: String,
+    /// The byte range of the token.
+    pub range: Span,
+}
+
+/// A user-facing literal in a parsed file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Literal {
+    pub kind: CandidateKind,
+    /// The attribute name or function the literal belongs to, if any.
+    pub context: Option<String>,
+    /// The text, with JSX whitespace collapsed.
+    pub text: String,
+    /// The byte range replaced when the literal is rewritten.
+    pub range: Span,
+}
+
+/// Parses `content` and returns its likely user-facing literals: JSX text,
+/// string values of `attributes`, and plain string arguments of `functions`.
+///
+/// Returns `None` if the language of `path` is not TypeScript, JavaScript, or
+/// Rust.
+pub(crate) fn literals(
+    path: &Path,
+    content: &str,
+    functions: &[String],
+    attributes: &[String],
+) -> Option<Vec<Literal>> {
+    let grammar = Grammar::for_path(path)?;
+    if matches!(grammar, Grammar::Go | Grammar::Python) {
+        return None;
+    }
+    let mut parser = Parser::new();
+    parser.set_language(&grammar.language()).ok()?;
+    let tree = parser.parse(content, None)?;
+    let source = content.as_bytes();
+
+    let mut literals = Vec::new();
+    let mut cursor = tree.walk();
+    loop {
+        let node = cursor.node();
+        let kind = node.kind();
+        if kind == "jsx_text" {
+            literals.extend(jsx_text(node, source));
+        } else if grammar.is_string(kind)
+            && let Some(text) = plain_string(node, source)
+        {
+            if let Some(name) = jsx_attribute_name(node, source)
+                && attributes.contains(&name)
+            {
+                literals.push(Literal {
+                    kind: CandidateKind::Attribute,
+                    context: Some(name),
+                    text,
+                    range: node.byte_range(),
+                });
+            } else if let Some(function) = callee(node, source)
+                && functions.contains(&function)
+            {
+                literals.push(Literal {
+                    kind: CandidateKind::Argument,
+                    context: Some(function),
+                    text,
+                    range: node.byte_range(),
+                });
+            }
+        }
+
+        if !kind.contains("comment") && !grammar.is_string(kind) && cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return Some(literals);
+            }
+        }
+    }
+}
+
+/// JSX text holding at least one letter, trimmed, unless an expression is
+/// right next to it: a sentence like `Hello {name}!` needs an argument and is
+/// left for a manual extraction.
+fn jsx_text(node: Node<'_>, source: &[u8]) -> Option<Literal> {
+    let raw = text(node, source);
+    if !raw.chars().any(char::is_alphabetic) {
+        return None;
+    }
+    let beside_expression = [node.prev_named_sibling(), node.next_named_sibling()]
+        .into_iter()
+        .flatten()
+        .any(|sibling| sibling.kind() == "jsx_expression");
+    if beside_expression {
+        return None;
+    }
+    let start = node.start_byte() + (raw.len() - raw.trim_start().len());
+    let end = node.end_byte() - (raw.len() - raw.trim_end().len());
+    Some(Literal {
+        kind: CandidateKind::JsxText,
+        context: None,
+        text: raw.split_whitespace().collect::<Vec<_>>().join(" "),
+        range: start..end,
+    })
+}
+
+/// The content of a string literal holding at least one letter, without
+/// interpolations or escape sequences, whose meaning would not survive being
+/// copied into a translation file verbatim.
+fn plain_string(node: Node<'_>, source: &[u8]) -> Option<String> {
+    let mut cursor = node.walk();
+    if node
+        .named_children(&mut cursor)
+        .any(|child| child.kind() == "escape_sequence")
+    {
+        return None;
+    }
+    let (value, interpolated) = string_value(node, source);
+    (!interpolated && value.chars().any(char::is_alphabetic)).then_some(value)
+}
+
+/// The name of the JSX attribute whose value is `node`.
+fn jsx_attribute_name(node: Node<'_>, source: &[u8]) -> Option<String> {
+    let attribute = node
+        .parent()
+        .filter(|parent| parent.kind() == "jsx_attribute")?;
+    let name = attribute.named_child(0).filter(|name| *name != node)?;
+    Some(text(name, source).to_string())
+}
+
+/// The comment suppressing the usages of a line.
+const IGNORE_COMMENT: &str = "globetrotter-ignore";
+
+/// Parses `content` and returns its string literals and identifiers, outside
+/// of comments. Literals passed as the first argument of one of `calls` are
+/// [`UsageKind::Call`] tokens.
+///
+/// A `globetrotter-ignore` comment drops the tokens of its line, or of the
+/// next line if the comment stands on a line of its own.
+///
+/// Returns `None` if the language of `path` is not supported.
+pub(super) fn tokens(path: &Path, content: &str, calls: &[String]) -> Option<Vec<Token>> {
+    let grammar = Grammar::for_path(path)?;
+    let mut parser = Parser::new();
+    parser.set_language(&grammar.language()).ok()?;
+    let tree = parser.parse(content, None)?;
+    let source = content.as_bytes();
+
+    let mut tokens = Vec::new();
+    let mut ignored_rows = Vec::new();
+    let mut cursor = tree.walk();
+    loop {
+        let node = cursor.node();
+        let kind = node.kind();
+        let is_comment = kind.contains("comment");
+        if is_comment {
+            // Comments are never usages, but may suppress them.
+            if is_ignore_comment(text(node, source)) {
+                ignored_rows.push(ignored_row(node, content));
+            }
+        } else if grammar.is_string(kind) {
+            tokens.push((
+                node.start_position().row,
+                string_token(grammar, node, source, calls),
+            ));
+        } else if node.child_count() == 0
+            && kind.ends_with("identifier")
+            && let Ok(text) = node.utf8_text(source)
+        {
+            let token = Token {
+                kind: UsageKind::Identifier,
+                text: text.to_string(),
+                range: node.byte_range(),
+            };
+            tokens.push((node.start_position().row, token));
+        }
+
+        // Strings are descended into too: template substitutions may hold
+        // further usages.
+        if !is_comment && cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return Some(
+                    tokens
+                        .into_iter()
+                        .filter(|(row, _)| !ignored_rows.contains(row))
+                        .map(|(_, token)| token)
+                        .collect(),
+                );
+            }
+        }
+    }
+}
+
+/// Returns `true` if `comment`, with its delimiters, starts with
+/// [`IGNORE_COMMENT`].
+fn is_ignore_comment(comment: &str) -> bool {
+    comment
+        .trim_start_matches(['/', '*', '#', '!'])
+        .trim_start()
+        .starts_with(IGNORE_COMMENT)
+}
+
+/// The row whose tokens the ignore comment `node` suppresses: its own if code
+/// precedes it, the next one otherwise.
+fn ignored_row(node: Node<'_>, content: &str) -> usize {
+    let start = node.start_byte();
+    let line_start = content
+        .get(..start)
+        .and_then(|before| before.rfind('\n'))
+        .map_or(0, |index| index + 1);
+    let row = node.start_position().row;
+    let trailing = content
+        .get(line_start..start)
+        .is_some_and(|before| !before.trim().is_empty());
+    if trailing { row } else { row + 1 }
+}
+
+fn text<'a>(node: Node<'_>, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or_default()
+}
+
+/// The literal content of a string node, up to its first interpolation, and
+/// whether it has one.
+fn string_value(node: Node<'_>, source: &[u8]) -> (String, bool) {
+    let mut value = String::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "template_substitution" | "interpolation" => return (value, true),
+            kind if kind.ends_with("fragment")
+                || kind.ends_with("content")
+                || kind == "escape_sequence" =>
+            {
+                value.push_str(text(child, source));
+            }
+            _ => {}
+        }
+    }
+    (value, false)
+}
+
+/// The literal start of a format string: a string inside a Rust macro call
+/// (`format!("app.{n}")`), passed to a Go `…printf` function, or formatted
+/// with Python's `str.format`.
+fn format_prefix(grammar: Grammar, node: Node<'_>, source: &[u8], value: &str) -> Option<String> {
+    let parent = node.parent()?;
+    let marker = match grammar {
+        Grammar::Rust if parent.kind() == "token_tree" => '{',
+        Grammar::Go
+            if parent.kind() == "argument_list"
+                && parent
+                    .parent()
+                    .filter(|call| call.kind() == "call_expression")
+                    .and_then(|call| call.child_by_field_name("function"))
+                    .is_some_and(|function| text(function, source).ends_with("printf")) =>
+        {
+            '%'
+        }
+        Grammar::Python
+            if parent.kind() == "attribute"
+                && parent
+                    .child_by_field_name("attribute")
+                    .is_some_and(|attribute| text(attribute, source) == "format") =>
+        {
+            '{'
+        }
+        _ => return None,
+    };
+    let end = value.find(marker)?;
+    value.get(..end).map(str::to_string)
+}
+
+/// Returns `true` if the string is the left operand of a `+` concatenation,
+/// as in `"app.step." + n`.
+fn is_concatenated(node: Node<'_>, source: &[u8]) -> bool {
+    node.parent().is_some_and(|parent| {
+        matches!(parent.kind(), "binary_expression" | "binary_operator")
+            && parent.child_by_field_name("left") == Some(node)
+            && parent
+                .child_by_field_name("operator")
+                .is_some_and(|operator| text(operator, source) == "+")
+    })
+}
+
+/// The function or macro (with its `!`) that `node` is the first argument of.
+fn callee(node: Node<'_>, source: &[u8]) -> Option<String> {
+    let parent = node.parent()?;
+    if parent.named_child(0)? != node {
+        return None;
+    }
+    match parent.kind() {
+        "arguments" | "argument_list" => {
+            let call = parent
+                .parent()
+                .filter(|call| matches!(call.kind(), "call_expression" | "call"))?;
+            Some(text(call.child_by_field_name("function")?, source).to_string())
+        }
+        "token_tree" => {
+            let invocation = parent
+                .parent()
+                .filter(|invocation| invocation.kind() == "macro_invocation")?;
+            let name = text(invocation.child_by_field_name("macro")?, source);
+            Some(format!("{name}!"))
+        }
+        _ => None,
+    }
+}
+
+fn string_token(grammar: Grammar, node: Node<'_>, source: &[u8], calls: &[String]) -> Token {
+    let (value, interpolated) = string_value(node, source);
+    let prefix = if interpolated || is_concatenated(node, source) {
+        Some(value.clone())
+    } else {
+        format_prefix(grammar, node, source, &value)
+    };
+    let kind = if prefix.is_some() {
+        UsageKind::Prefix
+    } else if callee(node, source).is_some_and(|callee| calls.contains(&callee)) {
+        UsageKind::Call
+    } else {
+        UsageKind::Literal
+    };
+    Token {
+        kind,
+        text: prefix.unwrap_or(value),
+        range: node.byte_range(),
+    }
+}
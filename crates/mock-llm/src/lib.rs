@@ -0,0 +1,535 @@
+//! A mock OpenAI-compatible chat-completions server for offline tests.
+//!
+//! [`MockServer`] listens on a local port and answers
+//! `POST /v1/chat/completions` from a list of [`Rule`]s, so judge and
+//! translation code can be tested without a model: a canned answer per key,
+//! injected latency, malformed content, or error statuses such as `429`.
+//!
+//! Rules are tried in order and the first that matches a request answers it.
+//! They are built in code or loaded from a JSON fixture:
+//!
+//! ```json
+//! [
+//!   {"key": "actions.save", "reply": {"json": {"consistent": false, "issues": []}}},
+//!   {"strict": true, "times": 1, "reply": {"status": {"status": 429, "retry_after": 1}}},
+//!   {"delay_ms": 50, "reply": {"raw": "not json"}}
+//! ]
+//! ```
+//!
+//! Every request is recorded (see [`MockServer::requests`]), together with the
+//! highest number of requests in flight at once.
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper::{Method, StatusCode};
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// The answer of a [`Rule`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Reply {
+    /// Answers with this JSON value, serialized, as the message content.
+    Json(serde_json::Value),
+    /// Answers with this message content verbatim, such as malformed JSON.
+    Raw(String),
+    /// Answers with an HTTP error status and an OpenAI-style error body.
+    Status {
+        /// The HTTP status code.
+        status: u16,
+        /// Seconds sent in a `Retry-After` header.
+        #[serde(default)]
+        retry_after: Option<u64>,
+    },
+}
+
+/// Which requests to answer, and how.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    /// Matches only prompts containing a `Translation key: <key>` line, as
+    /// the judge's single and batched prompts do.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Matches only prompts containing this text.
+    #[serde(default)]
+    pub contains: Option<String>,
+    /// Matches only requests with (`true`) or without (`false`) a strict
+    /// response schema.
+    #[serde(default)]
+    pub strict: Option<bool>,
+    /// Answers at most this many requests; later ones fall through to the
+    /// following rules.
+    #[serde(default)]
+    pub times: Option<usize>,
+    /// Milliseconds to wait before answering.
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// The answer.
+    pub reply: Reply,
+}
+
+impl Rule {
+    /// A rule answering every request with `reply`.
+    #[must_use]
+    pub fn new(reply: Reply) -> Self {
+        Self {
+            key: None,
+            contains: None,
+            strict: None,
+            times: None,
+            delay_ms: 0,
+            reply,
+        }
+    }
+
+    /// A rule answering with `value` as the message content.
+    #[must_use]
+    pub fn json(value: serde_json::Value) -> Self {
+        Self::new(Reply::Json(value))
+    }
+
+    /// A rule answering with the message content `content` verbatim.
+    #[must_use]
+    pub fn raw(content: impl Into<String>) -> Self {
+        Self::new(Reply::Raw(content.into()))
+    }
+
+    /// A rule answering with the HTTP error `status`.
+    #[must_use]
+    pub fn status(status: u16) -> Self {
+        Self::new(Reply::Status {
+            status,
+            retry_after: None,
+        })
+    }
+
+    /// A rule answering `429 Too Many Requests`, optionally asking the client
+    /// to retry after `retry_after` seconds.
+    #[must_use]
+    pub fn rate_limited(retry_after: Option<u64>) -> Self {
+        Self::new(Reply::Status {
+            status: 429,
+            retry_after,
+        })
+    }
+
+    /// Only matches prompts for the translation key `key`.
+    #[must_use]
+    pub fn for_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Only matches prompts containing `text`.
+    #[must_use]
+    pub fn containing(mut self, text: impl Into<String>) -> Self {
+        self.contains = Some(text.into());
+        self
+    }
+
+    /// Only matches requests with or without a strict response schema.
+    #[must_use]
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = Some(strict);
+        self
+    }
+
+    /// Answers at most `times` requests.
+    #[must_use]
+    pub fn with_times(mut self, times: usize) -> Self {
+        self.times = Some(times);
+        self
+    }
+
+    /// Waits `delay` before answering.
+    #[must_use]
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX);
+        self
+    }
+
+    /// Whether the rule answers `request`.
+    fn matches(&self, request: &Request) -> bool {
+        let key = self.key.as_ref().is_none_or(|key| {
+            request
+                .prompt
+                .lines()
+                .any(|line| line.strip_prefix("Translation key: ") == Some(key.as_str()))
+        });
+        let contains = self
+            .contains
+            .as_ref()
+            .is_none_or(|text| request.prompt.contains(text.as_str()));
+        let strict = self.strict.is_none_or(|strict| strict == request.strict);
+        key && contains && strict
+    }
+}
+
+/// Parses a JSON fixture: an array of rules.
+///
+/// # Errors
+///
+/// Returns an error if `content` is not an array of valid rules.
+pub fn parse_fixture(content: &str) -> serde_json::Result<Vec<Rule>> {
+    serde_json::from_str(content)
+}
+
+/// A request the server received.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    /// The requested model.
+    pub model: String,
+    /// The content of the last message.
+    pub prompt: String,
+    /// Whether the request asked for a strict JSON schema response.
+    pub strict: bool,
+    /// The complete request body.
+    pub body: serde_json::Value,
+}
+
+/// A rule and the number of requests it answered.
+struct Slot {
+    rule: Rule,
+    used: usize,
+}
+
+/// State shared by the connections of a server.
+#[derive(Default)]
+struct State {
+    slots: Mutex<Vec<Slot>>,
+    requests: Mutex<Vec<Request>>,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+impl State {
+    /// Records `request` and picks the reply and delay of the first matching
+    /// rule with answers left.
+    fn answer(&self, request: Request) -> Option<(Reply, Duration)> {
+        let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+        let slot = slots.iter_mut().find(|slot| {
+            slot.rule.times.is_none_or(|times| slot.used < times) && slot.rule.matches(&request)
+        });
+        let answer = slot.map(|slot| {
+            slot.used += 1;
+            (
+                slot.rule.reply.clone(),
+                Duration::from_millis(slot.rule.delay_ms),
+            )
+        });
+        self.requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(request);
+        answer
+    }
+}
+
+/// Counts a request as in flight until dropped.
+struct InFlight<'a>(&'a State);
+
+impl<'a> InFlight<'a> {
+    fn new(state: &'a State) -> Self {
+        let current = state.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        state.max_in_flight.fetch_max(current, Ordering::SeqCst);
+        Self(state)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A running mock server. It stops when dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<State>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts a server on a free local port answering with `rules`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no local port can be bound.
+    pub async fn start(rules: Vec<Rule>) -> std::io::Result<Self> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(State {
+            slots: Mutex::new(
+                rules
+                    .into_iter()
+                    .map(|rule| Slot { rule, used: 0 })
+                    .collect(),
+            ),
+            ..State::default()
+        });
+        let shared = Arc::clone(&state);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = Arc::clone(&shared);
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(move |request| {
+                        let state = Arc::clone(&state);
+                        async move { Ok::<_, std::convert::Infallible>(handle(&state, request).await) }
+                    });
+                    let io = hyper_util::rt::TokioIo::new(stream);
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(io, service)
+                        .await;
+                });
+            }
+        });
+        Ok(Self {
+            address,
+            state,
+            task,
+        })
+    }
+
+    /// The base URL to configure a client with, ending in `/v1`.
+    #[must_use]
+    pub fn base_url(&self) -> String {
+        format!("http://{}/v1", self.address)
+    }
+
+    /// The requests received so far, in arrival order.
+    #[must_use]
+    pub fn requests(&self) -> Vec<Request> {
+        self.state
+            .requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// The highest number of requests that were in flight at once.
+    #[must_use]
+    pub fn max_in_flight(&self) -> usize {
+        self.state.max_in_flight.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Answers one HTTP request.
+async fn handle(state: &State, request: hyper::Request<Incoming>) -> hyper::Response<Full<Bytes>> {
+    let _in_flight = InFlight::new(state);
+    if request.method() != Method::POST || request.uri().path() != "/v1/chat/completions" {
+        return error(StatusCode::NOT_FOUND, "unknown route", None);
+    }
+    let body = match request.into_body().collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(err) => return error(StatusCode::BAD_REQUEST, &err.to_string(), None),
+    };
+    let body: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(body) => body,
+        Err(err) => return error(StatusCode::BAD_REQUEST, &err.to_string(), None),
+    };
+    let text = |pointer: &str| {
+        body.pointer(pointer)
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let request = Request {
+        model: text("/model"),
+        prompt: body
+            .get("messages")
+            .and_then(serde_json::Value::as_array)
+            .and_then(|messages| messages.last())
+            .and_then(|message| message.get("content"))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        strict: body.pointer("/response_format/json_schema/strict")
+            == Some(&serde_json::Value::Bool(true)),
+        body: body.clone(),
+    };
+    let model = request.model.clone();
+    let prompt_tokens = estimate_tokens(&request.prompt);
+    let Some((reply, delay)) = state.answer(request) else {
+        return error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "no mock rule matches the request",
+            None,
+        );
+    };
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+    let content = match reply {
+        Reply::Json(value) => value.to_string(),
+        Reply::Raw(content) => content,
+        Reply::Status {
+            status,
+            retry_after,
+        } => {
+            let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            return error(status, "mock error", retry_after);
+        }
+    };
+    let completion_tokens = estimate_tokens(&content);
+    json(
+        StatusCode::OK,
+        &serde_json::json!({
+            "id": "mock",
+            "object": "chat.completion",
+            "created": 0,
+            "model": model,
+            "choices": [{
+                "index": 0,
+                "finish_reason": "stop",
+                "message": {"role": "assistant", "content": content},
+            }],
+            "usage": {
+                "prompt_tokens": prompt_tokens,
+                "completion_tokens": completion_tokens,
+                "total_tokens": prompt_tokens + completion_tokens,
+            },
+        }),
+    )
+}
+
+/// A rough token count for the `usage` of a completion: four characters per
+/// token.
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// A JSON response.
+fn json(status: StatusCode, body: &serde_json::Value) -> hyper::Response<Full<Bytes>> {
+    let mut response = hyper::Response::new(Full::new(Bytes::from(body.to_string())));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+/// An OpenAI-style error response, with a `Retry-After` header if given.
+fn error(
+    status: StatusCode,
+    message: &str,
+    retry_after: Option<u64>,
+) -> hyper::Response<Full<Bytes>> {
+    let mut response = json(
+        status,
+        &serde_json::json!({
+            "error": {"message": message, "type": "mock_error", "param": null, "code": null},
+        }),
+    );
+    if let Some(seconds) = retry_after {
+        response.headers_mut().insert(
+            hyper::header::RETRY_AFTER,
+            hyper::header::HeaderValue::from(seconds),
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MockServer, Reply, Rule, parse_fixture};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Posts `body` to the chat-completions route and returns the raw HTTP
+    /// response.
+    async fn post(server: &MockServer, body: &serde_json::Value) -> std::io::Result<String> {
+        let address = server.base_url();
+        let host = address
+            .trim_start_matches("http://")
+            .trim_end_matches("/v1");
+        let mut stream = tokio::net::TcpStream::connect(host).await?;
+        let body = body.to_string();
+        stream
+            .write_all(
+                format!(
+                    "POST /v1/chat/completions HTTP/1.1\r\nhost: {host}\r\n\
+                     content-type: application/json\r\ncontent-length: {}\r\n\
+                     connection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .as_bytes(),
+            )
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response)
+    }
+
+    fn chat(prompt: &str, strict: bool) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": "mock",
+            "messages": [{"role": "user", "content": prompt}],
+        });
+        if strict {
+            body["response_format"] =
+                serde_json::json!({"type": "json_schema", "json_schema": {"strict": true}});
+        }
+        body
+    }
+
+    #[test_util::test]
+    async fn answers_from_the_first_matching_rule() {
+        let server = MockServer::start(vec![
+            Rule::rate_limited(Some(2)).with_strict(true).with_times(1),
+            Rule::json(serde_json::json!({"consistent": false}))
+                .for_key("actions.save")
+                .with_delay(Duration::from_millis(10)),
+            Rule::raw("not json"),
+        ])
+        .await?;
+
+        let limited = post(&server, &chat("Translation key: actions.save\n", true)).await?;
+        assert!(limited.starts_with("HTTP/1.1 429"), "{limited}");
+        assert!(
+            limited.to_ascii_lowercase().contains("retry-after: 2"),
+            "{limited}"
+        );
+
+        let saved = post(&server, &chat("Translation key: actions.save\n", true)).await?;
+        assert!(saved.contains(r#"\"consistent\":false"#), "{saved}");
+
+        // Another key falls through to the last rule.
+        let other = post(&server, &chat("Translation key: actions.save_all\n", false)).await?;
+        assert!(other.contains(r#""content":"not json""#), "{other}");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.first().is_some_and(|request| request.strict));
+        assert_eq!(server.max_in_flight(), 1);
+    }
+
+    #[test_util::test]
+    fn parses_a_fixture() {
+        let rules = parse_fixture(
+            r#"[
+                {"key": "a", "reply": {"json": {"consistent": true, "issues": []}}},
+                {"times": 1, "delay_ms": 5, "reply": {"status": {"status": 429, "retry_after": 1}}}
+            ]"#,
+        )?;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(
+            rules.get(1).map(|rule| &rule.reply),
+            Some(&Reply::Status {
+                status: 429,
+                retry_after: Some(1)
+            })
+        );
+    }
+}